@@ -0,0 +1,92 @@
+//! Decoding of program accounts, CPI events and return data.
+use anchor_lang::{event::EVENT_IX_TAG_LE, AccountDeserialize, AnchorDeserialize, Discriminator};
+use confidential_bid::{
+    events::{
+        AuctionCancelled, AuctionClosed, AuctionCreated, AuctionEndedEarly, AuctionExtended,
+        AuctionResultRevealed, AuctionSettled, AuctionUpdated, BidPlaced, BidRefunded, BidRevealed,
+        PrizeClaimed, WinnerChecked,
+    },
+    state::{AuctionState, Bid, BidReduction},
+};
+use inco_token::IncoAccount;
+
+use crate::ClientError;
+
+/// Decodes an `AuctionState`, legacy accounts must go through `migrate_auction` first
+pub fn auction(data: &[u8]) -> Result<AuctionState, ClientError> {
+    AuctionState::try_deserialize(&mut &data[..])
+        .map_err(|err| ClientError::Decode(err.to_string()))
+}
+
+/// Decodes a `Bid`, legacy accounts must go through `migrate_bid` first
+pub fn bid(data: &[u8]) -> Result<Bid, ClientError> {
+    Bid::try_deserialize(&mut &data[..]).map_err(|err| ClientError::Decode(err.to_string()))
+}
+
+/// Handle of the balance held by an Inco token account
+pub fn inco_balance(data: &[u8]) -> Result<u128, ClientError> {
+    IncoAccount::try_deserialize(&mut &data[..])
+        .map(|account| account.amount.0)
+        .map_err(|err| ClientError::Decode(err.to_string()))
+}
+
+/// Decodes a `BidReduction` of a deferred auction
+pub fn reduction(data: &[u8]) -> Result<BidReduction, ClientError> {
+    BidReduction::try_deserialize(&mut &data[..])
+        .map_err(|err| ClientError::Decode(err.to_string()))
+}
+
+/// Decodes the return data of `place_bid`, `check_winner`, `close_auction` and the views
+pub fn return_data<T: AnchorDeserialize>(data: &[u8]) -> Result<T, ClientError> {
+    T::try_from_slice(data).map_err(|err| ClientError::Decode(err.to_string()))
+}
+
+/// Every event the program emits
+pub enum AuctionEvent {
+    AuctionCreated(AuctionCreated),
+    AuctionUpdated(AuctionUpdated),
+    AuctionExtended(AuctionExtended),
+    BidPlaced(BidPlaced),
+    AuctionEndedEarly(AuctionEndedEarly),
+    AuctionClosed(AuctionClosed),
+    WinnerChecked(WinnerChecked),
+    AuctionSettled(AuctionSettled),
+    PrizeClaimed(PrizeClaimed),
+    BidRefunded(BidRefunded),
+    AuctionCancelled(AuctionCancelled),
+    AuctionResultRevealed(AuctionResultRevealed),
+    BidRevealed(BidRevealed),
+}
+
+/// Decodes the data of an inner instruction the program invoked on itself.
+///
+/// Returns `None` for any instruction that is not an event self-CPI.
+pub fn event(ix_data: &[u8]) -> Option<AuctionEvent> {
+    let data = ix_data.strip_prefix(EVENT_IX_TAG_LE)?;
+
+    macro_rules! try_event {
+        ($($event:ident),* $(,)?) => {
+            $(
+                if let Some(body) = data.strip_prefix($event::DISCRIMINATOR) {
+                    return $event::try_from_slice(body).ok().map(AuctionEvent::$event);
+                }
+            )*
+        };
+    }
+    try_event!(
+        AuctionCreated,
+        AuctionUpdated,
+        AuctionExtended,
+        BidPlaced,
+        AuctionEndedEarly,
+        AuctionClosed,
+        WinnerChecked,
+        AuctionSettled,
+        PrizeClaimed,
+        BidRefunded,
+        AuctionCancelled,
+        AuctionResultRevealed,
+        BidRevealed,
+    );
+    None
+}
@@ -0,0 +1,100 @@
+//! Typed errors for callers, with program error codes mapped back to `AuctionError`.
+use std::fmt;
+
+use anchor_lang::error::ERROR_CODE_OFFSET;
+use confidential_bid::error::AuctionError;
+
+#[derive(Debug)]
+pub enum ClientError {
+    /// The program rejected the instruction with one of its own error codes
+    Auction(AuctionError),
+    /// Any other custom error code, raised by Anchor itself or a CPI'd program
+    Custom(u32),
+    /// Account, event or return data did not match the program's layout
+    Decode(String),
+}
+
+/// `AuctionError` variants in declaration order, so index + offset is the code.
+///
+/// Must list every variant, a missing one decodes as `ClientError::Custom`.
+const AUCTION_ERRORS: &[AuctionError] = &[
+    AuctionError::InvalidStartTime,
+    AuctionError::InvalidEndTime,
+    AuctionError::InvalidReservePrice,
+    AuctionError::InsufficientBalance,
+    AuctionError::AuctionNotStarted,
+    AuctionError::AuctionEnded,
+    AuctionError::InvalidTokenAmount,
+    AuctionError::InvalidBidMint,
+    AuctionError::InvalidBidAmount,
+    AuctionError::AuctionNotOpen,
+    AuctionError::InvalidAuction,
+    AuctionError::InvalidBidVault,
+    AuctionError::MathOverflow,
+    AuctionError::BidBelowReserve,
+    AuctionError::Unauthorized,
+    AuctionError::AuctionNotEnded,
+    AuctionError::AuctionNotClosed,
+    AuctionError::TooManyPayoutRecipients,
+    AuctionError::InvalidPayoutShare,
+    AuctionError::InvalidPayoutRecipient,
+    AuctionError::InvalidPrizeMetadata,
+    AuctionError::InvalidRoyaltyCreator,
+    AuctionError::AuctionAlreadyStarted,
+    AuctionError::AuctionHasBids,
+    AuctionError::ExtensionTooLong,
+    AuctionError::NotEnoughBids,
+    AuctionError::InvalidAllowanceAccount,
+    AuctionError::UnknownHandle,
+    AuctionError::MissingAuditorAccounts,
+    AuctionError::InvalidAuditor,
+    AuctionError::BidCountHidden,
+    AuctionError::InvalidBidCommitment,
+    AuctionError::AccountAlreadyMigrated,
+    AuctionError::InvalidLegacyAccount,
+    AuctionError::StandingsNotDeferred,
+    AuctionError::BidOutOfOrder,
+    AuctionError::ReductionGap,
+    AuctionError::ReductionIncomplete,
+    AuctionError::StandingsNotReduced,
+    AuctionError::WinnersNotResolved,
+    AuctionError::InvalidResolveAccounts,
+    AuctionError::InvalidAttestation,
+    AuctionError::BidNotResolved,
+    AuctionError::NotWinner,
+    AuctionError::WinningBidNotRefundable,
+    AuctionError::ReserveMet,
+    AuctionError::AlreadyClaimed,
+    AuctionError::AuctionNotSettled,
+    AuctionError::RevealNotEnabled,
+    AuctionError::ResultAlreadyRevealed,
+    AuctionError::DisclosureNotEnabled,
+    AuctionError::InvalidDisclosureAccounts,
+    AuctionError::BidAlreadyRevealed,
+    AuctionError::MissingAllowanceAccount,
+    AuctionError::AuditorAccessLocked,
+    AuctionError::DisclosureIncomplete,
+    AuctionError::UnprovenEscrow,
+    AuctionError::StandingsFinal,
+];
+
+impl ClientError {
+    /// Maps the code of an `InstructionError::Custom` failure
+    pub fn from_custom_code(code: u32) -> Self {
+        code.checked_sub(ERROR_CODE_OFFSET)
+            .and_then(|index| AUCTION_ERRORS.get(index as usize))
+            .map_or(Self::Custom(code), |err| Self::Auction(*err))
+    }
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Auction(err) => write!(f, "auction error: {err}"),
+            Self::Custom(code) => write!(f, "custom program error: {code:#x}"),
+            Self::Decode(err) => write!(f, "decode error: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
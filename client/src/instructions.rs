@@ -0,0 +1,761 @@
+//! Instruction builders with the full account list of every instruction.
+//!
+//! Builders for instructions that grant decryption take the handles returned
+//! by a simulation of the same instruction. With `None` the allowance
+//! accounts are left out, which is what that first simulation needs.
+//! Auditors are granted the handles afterwards through [`grant_auditor`].
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::{
+        ed25519_program,
+        instruction::{AccountMeta, Instruction},
+        sysvar,
+    },
+    system_program, InstructionData, ToAccountMetas,
+};
+use anchor_spl::associated_token::{self, get_associated_token_address_with_program_id};
+use confidential_bid::{
+    accounts,
+    attestation::{attested_message, ATTESTED_MESSAGE_LEN},
+    constants::DISCLOSURE_ADDRESS,
+    inco::INCO_TOKEN_ID,
+    instruction,
+    returns::PlaceBidResult,
+    state::{AuctionState, AuctionType, Bid, BidReduction, PayoutRecipient},
+    ID,
+};
+use inco_lightning::ID as INCO_LIGHTNING_ID;
+use solana_compute_budget_interface::ComputeBudgetInstruction;
+
+use crate::pda;
+
+/// Compute limit that fits `place_bid` with every allowance
+pub const PLACE_BID_COMPUTE_UNITS: u32 = 400_000;
+/// Compute limit that fits `check_winner` with the winner allowance
+pub const CHECK_WINNER_COMPUTE_UNITS: u32 = 400_000;
+/// Compute limit for `resolve_winners`, each bid costs four Lightning CPIs and its allowances
+pub const RESOLVE_WINNERS_COMPUTE_UNITS: u32 = 1_400_000;
+/// Compute limit for `reduce_bids`, each bid folded costs up to five Lightning CPIs
+pub const REDUCE_BIDS_COMPUTE_UNITS: u32 = 1_400_000;
+/// Compute limit that fits `settle_auction` with the maximum recipients and creators
+pub const SETTLE_AUCTION_COMPUTE_UNITS: u32 = 1_400_000;
+
+/// Prefixes `ix` with a compute unit limit and, when given, a priority fee
+pub fn with_compute_budget(
+    ix: Instruction,
+    units: u32,
+    micro_lamports: Option<u64>,
+) -> Vec<Instruction> {
+    let mut ixs = vec![ComputeBudgetInstruction::set_compute_unit_limit(units)];
+    if let Some(micro_lamports) = micro_lamports {
+        ixs.push(ComputeBudgetInstruction::set_compute_unit_price(
+            micro_lamports,
+        ));
+    }
+    ixs.push(ix);
+    ixs
+}
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// Arguments of `create_auction` besides the accounts
+pub struct CreateAuctionParams {
+    pub start_time: i64,
+    pub end_time: i64,
+    pub reserve_price: u64,
+    pub auction_type: AuctionType,
+    pub token_amount: u64,
+    pub payout_recipients: Vec<PayoutRecipient>,
+    pub auditor: Option<Pubkey>,
+    pub hide_bid_count: bool,
+    pub private_bids: bool,
+    pub deferred_standings: bool,
+    pub reveal_on_settle: bool,
+    pub disclose_bids: bool,
+}
+
+/// `auction_id` is the organizer profile's `auction_count`, 0 for a first auction
+pub fn create_auction(
+    organizer: &Pubkey,
+    auction_id: u64,
+    prize_mint: &Pubkey,
+    prize_token_program: &Pubkey,
+    bid_mint: &Pubkey,
+    params: CreateAuctionParams,
+) -> Instruction {
+    let auction = pda::auction(organizer, auction_id);
+    build(
+        accounts::CreateAuction {
+            organizer: *organizer,
+            organizer_profile: pda::organizer_profile(organizer),
+            mint: *prize_mint,
+            bid_token_mint: *bid_mint,
+            bid_vault: pda::bid_vault(&auction, bid_mint),
+            vault: pda::vault(&auction, prize_mint, prize_token_program),
+            auction,
+            organizer_token_account: get_associated_token_address_with_program_id(
+                organizer,
+                prize_mint,
+                prize_token_program,
+            ),
+            system_program: system_program::ID,
+            associated_token_program: associated_token::ID,
+            token_program: *prize_token_program,
+            inco_token_program: INCO_TOKEN_ID,
+            inco_lightning_program: INCO_LIGHTNING_ID,
+            event_authority: pda::event_authority(),
+            program: ID,
+        },
+        instruction::CreateAuction {
+            start_time: params.start_time,
+            end_time: params.end_time,
+            reserve_price: params.reserve_price,
+            auction_type: params.auction_type,
+            token_amount: params.token_amount,
+            payout_recipients: params.payout_recipients,
+            auditor: params.auditor,
+            hide_bid_count: params.hide_bid_count,
+            private_bids: params.private_bids,
+            deferred_standings: params.deferred_standings,
+            reveal_on_settle: params.reveal_on_settle,
+            disclose_bids: params.disclose_bids,
+        },
+    )
+}
+
+pub fn update_auction(
+    organizer: &Pubkey,
+    auction: &Pubkey,
+    start_time: i64,
+    end_time: i64,
+    reserve_price: u64,
+    auction_type: AuctionType,
+) -> Instruction {
+    build(
+        accounts::UpdateAuction {
+            organizer: *organizer,
+            auction: *auction,
+            event_authority: pda::event_authority(),
+            program: ID,
+        },
+        instruction::UpdateAuction {
+            start_time,
+            end_time,
+            reserve_price,
+            auction_type,
+        },
+    )
+}
+
+pub fn extend_auction(organizer: &Pubkey, auction: &Pubkey, new_end_time: i64) -> Instruction {
+    build(
+        accounts::ExtendAuction {
+            organizer: *organizer,
+            auction: *auction,
+            event_authority: pda::event_authority(),
+            program: ID,
+        },
+        instruction::ExtendAuction { new_end_time },
+    )
+}
+
+/// `bid_secret` is required exactly when the auction has `private_bids` set,
+/// `bidder` then being the one-off key of [`crate::private_bid`] and `payer`
+/// a relayer. `handles` are the simulation's result and the bidder's balance
+/// handle read from its simulated token account, with them the bidder is
+/// granted its new balance and bid amount.
+pub fn place_bid(
+    payer: &Pubkey,
+    bidder: &Pubkey,
+    auction_key: &Pubkey,
+    auction: &AuctionState,
+    ciphertext: Vec<u8>,
+    input_type: u8,
+    bid_secret: Option<&[u8; 32]>,
+    handles: Option<(&PlaceBidResult, u128)>,
+) -> Instruction {
+    let allowance = |handle: fn(&PlaceBidResult, u128) -> u128| {
+        handles.map(|(result, balance)| pda::allowance(handle(result, balance), bidder))
+    };
+
+    build(
+        accounts::PlaceBid {
+            payer: *payer,
+            bidder: *bidder,
+            organizer: auction.organizer,
+            bidder_token_ata: pda::inco_ata(bidder, &auction.bid_token_mint),
+            bid_mint: auction.bid_token_mint,
+            bid_vault: auction.bid_vault,
+            bid: pda::bid(auction_key, bidder, bid_secret),
+            auction: *auction_key,
+            balance_allowance: allowance(|_, balance| balance),
+            bid_allowance: allowance(|result, _| result.bid_amount_handle),
+            system_program: system_program::ID,
+            inco_token_program: INCO_TOKEN_ID,
+            inco_lightning_program: INCO_LIGHTNING_ID,
+            event_authority: pda::event_authority(),
+            program: ID,
+        },
+        instruction::PlaceBid {
+            token_amount: ciphertext,
+            input_type,
+            bid_commitment: bid_secret.map(|secret| Bid::commitment(bidder, secret)),
+            with_allowances: handles.is_some(),
+        },
+    )
+}
+
+pub fn close_auction(organizer: &Pubkey, auction: &Pubkey) -> Instruction {
+    build(
+        accounts::CloseAuction {
+            organizer: *organizer,
+            auction: *auction,
+            system_program: system_program::ID,
+            inco_lightning_program: INCO_LIGHTNING_ID,
+            event_authority: pda::event_authority(),
+            program: ID,
+        },
+        instruction::CloseAuction {},
+    )
+}
+
+/// Same accounts as [`close_auction`], callable while the auction is live
+pub fn end_early(organizer: &Pubkey, auction: &Pubkey) -> Instruction {
+    build(
+        accounts::EndEarly {
+            organizer: *organizer,
+            auction: *auction,
+            system_program: system_program::ID,
+            inco_lightning_program: INCO_LIGHTNING_ID,
+            event_authority: pda::event_authority(),
+            program: ID,
+        },
+        instruction::EndEarly {},
+    )
+}
+
+/// `is_winner_handle` comes from the [`CheckWinnerResult`](crate::CheckWinnerResult)
+/// of a simulation, with it the bidder is granted the winner handle
+pub fn check_winner(
+    payer: &Pubkey,
+    bidder: &Pubkey,
+    auction: &Pubkey,
+    input_type: u8,
+    bid_secret: Option<&[u8; 32]>,
+    is_winner_handle: Option<u128>,
+) -> Instruction {
+    build(
+        accounts::CheckWinner {
+            payer: *payer,
+            bidder: *bidder,
+            bid: pda::bid(auction, bidder, bid_secret),
+            auction: *auction,
+            winner_allowance: is_winner_handle.map(|handle| pda::allowance(handle, bidder)),
+            inco_token_program: INCO_TOKEN_ID,
+            system_program: system_program::ID,
+            inco_lightning_program: INCO_LIGHTNING_ID,
+            event_authority: pda::event_authority(),
+            program: ID,
+        },
+        instruction::CheckWinner {
+            input_type,
+            bid_secret: bid_secret.copied(),
+            with_allowances: is_winner_handle.is_some(),
+        },
+    )
+}
+
+/// `bids` are the bid accounts with sequence `start + reduction.reduced`
+/// onwards, in sequence order; the reduction is created by the first call.
+pub fn reduce_bids(payer: &Pubkey, auction: &Pubkey, start: u32, bids: &[Pubkey]) -> Instruction {
+    let mut ix = build(
+        accounts::ReduceBids {
+            payer: *payer,
+            auction: *auction,
+            reduction: pda::reduction(auction, start),
+            system_program: system_program::ID,
+            inco_lightning_program: INCO_LIGHTNING_ID,
+        },
+        instruction::ReduceBids { start },
+    );
+    ix.accounts.extend(
+        bids.iter()
+            .map(|bid| AccountMeta::new_readonly(*bid, false)),
+    );
+    ix
+}
+
+/// Folds `later` into `earlier`, `later.start` being `earlier.start + earlier.reduced`
+pub fn merge_reductions(
+    payer: &Pubkey,
+    auction: &Pubkey,
+    earlier: &BidReduction,
+    later: &BidReduction,
+) -> Instruction {
+    build(
+        accounts::MergeReductions {
+            payer: *payer,
+            auction: *auction,
+            earlier: pda::reduction(auction, earlier.start),
+            later: pda::reduction(auction, later.start),
+            later_payer: later.payer,
+            inco_lightning_program: INCO_LIGHTNING_ID,
+        },
+        instruction::MergeReductions {},
+    )
+}
+
+/// `reduction` is the one starting at sequence 0 once it covers every bid
+pub fn finalize_reduction(
+    payer: &Pubkey,
+    auction: &Pubkey,
+    reduction: &BidReduction,
+) -> Instruction {
+    build(
+        accounts::FinalizeReduction {
+            payer: *payer,
+            auction: *auction,
+            reduction: pda::reduction(auction, 0),
+            reduction_payer: reduction.payer,
+        },
+        instruction::FinalizeReduction {},
+    )
+}
+
+/// `bids` are `(bid, bidder)` pairs, the bidder is ignored on private
+/// auctions. `is_winner_handles` are read from the bid accounts of a
+/// simulation and must match `bids` one to one; without them no allowance
+/// is granted.
+pub fn resolve_winners(
+    payer: &Pubkey,
+    auction_key: &Pubkey,
+    auction: &AuctionState,
+    bids: &[(Pubkey, Pubkey)],
+    is_winner_handles: Option<&[u128]>,
+) -> Instruction {
+    let mut ix = build(
+        accounts::ResolveWinners {
+            payer: *payer,
+            auction: *auction_key,
+            system_program: system_program::ID,
+            inco_lightning_program: INCO_LIGHTNING_ID,
+            event_authority: pda::event_authority(),
+            program: ID,
+        },
+        instruction::ResolveWinners {
+            with_allowances: is_winner_handles.is_some(),
+        },
+    );
+
+    for (i, (bid, bidder)) in bids.iter().enumerate() {
+        ix.accounts.push(AccountMeta::new(*bid, false));
+        let Some(handle) = is_winner_handles.map(|handles| handles[i]) else {
+            continue;
+        };
+        if !auction.private_bids {
+            ix.accounts.extend([
+                AccountMeta::new(pda::allowance(handle, bidder), false),
+                AccountMeta::new_readonly(*bidder, false),
+            ]);
+        }
+    }
+    ix
+}
+
+/// `royalty_creators` are the verified creators of the prize metadata in
+/// metadata order, empty when the prize has no metadata.
+pub fn settle_auction(
+    organizer: &Pubkey,
+    auction_key: &Pubkey,
+    auction: &AuctionState,
+    royalty_creators: &[Pubkey],
+) -> Instruction {
+    let bid_mint = auction.bid_token_mint;
+    let mut ix = build(
+        accounts::SettleAuction {
+            organizer: *organizer,
+            auction: *auction_key,
+            bid_mint,
+            bid_vault: auction.bid_vault,
+            organizer_bid_ata: pda::inco_ata(organizer, &bid_mint),
+            prize_metadata: pda::prize_metadata(&auction.mint),
+            system_program: system_program::ID,
+            inco_token_program: INCO_TOKEN_ID,
+            inco_lightning_program: INCO_LIGHTNING_ID,
+            event_authority: pda::event_authority(),
+            program: ID,
+        },
+        instruction::SettleAuction {},
+    );
+
+    // Recipient accounts first, in auction order, then the creators
+    let recipients = auction.payout_recipients.iter().map(|p| &p.recipient);
+    ix.accounts.extend(
+        recipients
+            .chain(royalty_creators)
+            .map(|wallet| AccountMeta::new(pda::inco_ata(wallet, &bid_mint), false)),
+    );
+    ix
+}
+
+/// Only the program's upgrade authority can set the attester
+pub fn set_attester(authority: &Pubkey, attester: &Pubkey) -> Instruction {
+    build(
+        accounts::SetAttester {
+            authority: *authority,
+            attester_config: pda::attester_config(),
+            program: ID,
+            program_data: pda::program_data(),
+            system_program: system_program::ID,
+        },
+        instruction::SetAttester {
+            attester: *attester,
+        },
+    )
+}
+
+/// Decryption of `handle` to `plaintext`, with the attester's signature over
+/// [`attested_message`]
+pub struct Attestation {
+    pub signature: [u8; 64],
+    pub handle: u128,
+    pub plaintext: u128,
+}
+
+/// Ed25519 program instruction carrying one attestation, see [`attestations`]
+pub fn attestation(
+    attester: &Pubkey,
+    signature: &[u8; 64],
+    handle: u128,
+    plaintext: u128,
+) -> Instruction {
+    attestations(
+        attester,
+        &[Attestation {
+            signature: *signature,
+            handle,
+            plaintext,
+        }],
+    )
+}
+
+/// Ed25519 program instruction carrying `attestations` signed by `attester`.
+/// It must come right before the instruction consuming them.
+pub fn attestations(attester: &Pubkey, attestations: &[Attestation]) -> Instruction {
+    const OFFSETS_LEN: usize = 14;
+    const SIGNED_LEN: usize = 32 + 64 + ATTESTED_MESSAGE_LEN;
+    // Every offset points into this instruction
+    const THIS_INSTRUCTION: u16 = u16::MAX;
+
+    let mut data = vec![attestations.len() as u8, 0];
+    let signed_start = data.len() + attestations.len() * OFFSETS_LEN;
+    for i in 0..attestations.len() {
+        let pubkey_offset = (signed_start + i * SIGNED_LEN) as u16;
+        let signature_offset = pubkey_offset + 32;
+        let message_offset = signature_offset + 64;
+        for field in [
+            signature_offset,
+            THIS_INSTRUCTION,
+            pubkey_offset,
+            THIS_INSTRUCTION,
+            message_offset,
+            ATTESTED_MESSAGE_LEN as u16,
+            THIS_INSTRUCTION,
+        ] {
+            data.extend_from_slice(&field.to_le_bytes());
+        }
+    }
+    for attestation in attestations {
+        data.extend_from_slice(attester.as_ref());
+        data.extend_from_slice(&attestation.signature);
+        data.extend_from_slice(&attested_message(attestation.handle, attestation.plaintext));
+    }
+
+    Instruction {
+        program_id: ed25519_program::ID,
+        accounts: vec![],
+        data,
+    }
+}
+
+/// Must follow the [`attestation`] of the bid's winner handle decrypting to true
+pub fn claim_prize(
+    payer: &Pubkey,
+    bidder: &Pubkey,
+    auction_key: &Pubkey,
+    auction: &AuctionState,
+    prize_token_program: &Pubkey,
+    bid_secret: Option<&[u8; 32]>,
+) -> Instruction {
+    build(
+        accounts::ClaimPrize {
+            payer: *payer,
+            bidder: *bidder,
+            bid: pda::bid(auction_key, bidder, bid_secret),
+            auction: *auction_key,
+            attester_config: pda::attester_config(),
+            instructions: sysvar::instructions::ID,
+            mint: auction.mint,
+            vault: auction.vault,
+            bidder_token_account: get_associated_token_address_with_program_id(
+                bidder,
+                &auction.mint,
+                prize_token_program,
+            ),
+            bid_mint: auction.bid_token_mint,
+            bid_vault: auction.bid_vault,
+            bidder_bid_ata: pda::inco_ata(bidder, &auction.bid_token_mint),
+            token_program: *prize_token_program,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            inco_token_program: INCO_TOKEN_ID,
+            inco_lightning_program: INCO_LIGHTNING_ID,
+            event_authority: pda::event_authority(),
+            program: ID,
+        },
+        instruction::ClaimPrize {
+            bid_secret: bid_secret.copied(),
+        },
+    )
+}
+
+/// Must follow the [`attestation`] of the bid's winner handle decrypting to
+/// false, unless the auction was cancelled
+pub fn refund_bid(
+    bidder: &Pubkey,
+    auction_key: &Pubkey,
+    auction: &AuctionState,
+    bid_secret: Option<&[u8; 32]>,
+) -> Instruction {
+    build(
+        accounts::RefundBid {
+            bidder: *bidder,
+            bid: pda::bid(auction_key, bidder, bid_secret),
+            auction: *auction_key,
+            attester_config: pda::attester_config(),
+            instructions: sysvar::instructions::ID,
+            bid_mint: auction.bid_token_mint,
+            bid_vault: auction.bid_vault,
+            bidder_bid_ata: pda::inco_ata(bidder, &auction.bid_token_mint),
+            system_program: system_program::ID,
+            inco_token_program: INCO_TOKEN_ID,
+            inco_lightning_program: INCO_LIGHTNING_ID,
+            event_authority: pda::event_authority(),
+            program: ID,
+        },
+        instruction::RefundBid {
+            bid_secret: bid_secret.copied(),
+        },
+    )
+}
+
+/// Must follow the [`attestation`] of the reserve handle decrypting to
+/// false, unless the auction has a public bid count of zero
+pub fn cancel_auction(
+    organizer: &Pubkey,
+    auction_key: &Pubkey,
+    auction: &AuctionState,
+    prize_token_program: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::CancelAuction {
+            organizer: *organizer,
+            auction: *auction_key,
+            attester_config: pda::attester_config(),
+            instructions: sysvar::instructions::ID,
+            mint: auction.mint,
+            vault: auction.vault,
+            organizer_token_account: get_associated_token_address_with_program_id(
+                organizer,
+                &auction.mint,
+                prize_token_program,
+            ),
+            token_program: *prize_token_program,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            event_authority: pda::event_authority(),
+            program: ID,
+        },
+        instruction::CancelAuction {},
+    )
+}
+
+/// Must follow the [`attestations`] of `winning_bid`'s winner handle and the
+/// auction's `clearing_price_handle`
+pub fn reveal_result(payer: &Pubkey, auction: &Pubkey, winning_bid: &Pubkey) -> Instruction {
+    build(
+        accounts::RevealResult {
+            payer: *payer,
+            auction: *auction,
+            winning_bid: *winning_bid,
+            attester_config: pda::attester_config(),
+            instructions: sysvar::instructions::ID,
+            event_authority: pda::event_authority(),
+            program: ID,
+        },
+        instruction::RevealResult {},
+    )
+}
+
+/// `bids` are the `(bid, bid_amount handle)` of the next bids to disclose,
+/// in sequence order from `AuctionState::disclosed_count`
+pub fn disclose_bids(payer: &Pubkey, auction: &Pubkey, bids: &[(Pubkey, u128)]) -> Instruction {
+    let mut ix = build(
+        accounts::DiscloseBids {
+            payer: *payer,
+            auction: *auction,
+            disclosure: DISCLOSURE_ADDRESS,
+            system_program: system_program::ID,
+            inco_lightning_program: INCO_LIGHTNING_ID,
+        },
+        instruction::DiscloseBids {},
+    );
+    ix.accounts.extend(bids.iter().flat_map(|(bid, handle)| {
+        [
+            AccountMeta::new_readonly(*bid, false),
+            AccountMeta::new(pda::allowance(*handle, &DISCLOSURE_ADDRESS), false),
+        ]
+    }));
+    ix
+}
+
+/// Must follow the [`attestation`] of the bid's amount
+pub fn reveal_bid(payer: &Pubkey, auction: &Pubkey, bid: &Pubkey) -> Instruction {
+    build(
+        accounts::RevealBid {
+            payer: *payer,
+            auction: *auction,
+            bid: *bid,
+            attester_config: pda::attester_config(),
+            instructions: sysvar::instructions::ID,
+            event_authority: pda::event_authority(),
+            program: ID,
+        },
+        instruction::RevealBid {},
+    )
+}
+
+/// `bid` is required for bid handles, `bid_secret` for bids of private auctions
+pub fn grant_access(
+    payer: &Pubkey,
+    owner: &Pubkey,
+    auction: &Pubkey,
+    bid: Option<&Pubkey>,
+    handle: u128,
+    allowed_address: &Pubkey,
+    value: bool,
+    bid_secret: Option<&[u8; 32]>,
+) -> Instruction {
+    build(
+        accounts::GrantAccess {
+            payer: *payer,
+            owner: *owner,
+            auction: *auction,
+            bid: bid.copied(),
+            allowance_account: pda::allowance(handle, allowed_address),
+            allowed_address: *allowed_address,
+            system_program: system_program::ID,
+            inco_lightning_program: INCO_LIGHTNING_ID,
+        },
+        instruction::GrantAccess {
+            handle,
+            value,
+            bid_secret: bid_secret.copied(),
+        },
+    )
+}
+
+/// Grants the auction's auditor every current handle of `bid`, or of the
+/// auction without one. Anyone can send it once the handles exist, e.g.
+/// right after the instruction that produced them.
+pub fn grant_auditor(
+    payer: &Pubkey,
+    auction_key: &Pubkey,
+    auction: &AuctionState,
+    bid: Option<(&Pubkey, &Bid)>,
+) -> Instruction {
+    let auditor = auction.auditor.unwrap_or_default();
+    let handles = bid.map_or_else(|| auction.handles(), |(_, bid)| bid.handles());
+    let mut ix = build(
+        accounts::GrantAuditor {
+            payer: *payer,
+            auction: *auction_key,
+            bid: bid.map(|(key, _)| *key),
+            auditor,
+            system_program: system_program::ID,
+            inco_lightning_program: INCO_LIGHTNING_ID,
+        },
+        instruction::GrantAuditor {},
+    );
+    ix.accounts.extend(
+        handles
+            .into_iter()
+            .map(|handle| AccountMeta::new(pda::allowance(handle, &auditor), false)),
+    );
+    ix
+}
+
+/// View, simulate it and decode [`crate::AuctionSummary`] from the return data
+pub fn get_auction_summary(
+    caller: &Pubkey,
+    auction: &Pubkey,
+    bid_secret: Option<&[u8; 32]>,
+) -> Instruction {
+    build(
+        accounts::GetAuctionSummary {
+            caller: *caller,
+            auction: *auction,
+            bid: pda::bid(auction, caller, bid_secret),
+        },
+        instruction::GetAuctionSummary {
+            bid_secret: bid_secret.copied(),
+        },
+    )
+}
+
+/// View, simulate it and decode [`crate::BidStatus`] from the return data
+pub fn get_bid_status(
+    caller: &Pubkey,
+    auction: &Pubkey,
+    bid: &Pubkey,
+    bid_secret: Option<&[u8; 32]>,
+) -> Instruction {
+    build(
+        accounts::GetBidStatus {
+            caller: *caller,
+            auction: *auction,
+            bid: *bid,
+        },
+        instruction::GetBidStatus {
+            bid_secret: bid_secret.copied(),
+        },
+    )
+}
+
+pub fn migrate_auction(payer: &Pubkey, auction: &Pubkey) -> Instruction {
+    build(
+        accounts::MigrateAuction {
+            payer: *payer,
+            auction: *auction,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateAuction {},
+    )
+}
+
+pub fn migrate_bid(payer: &Pubkey, bid: &Pubkey) -> Instruction {
+    build(
+        accounts::MigrateBid {
+            payer: *payer,
+            bid: *bid,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateBid {},
+    )
+}
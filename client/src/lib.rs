@@ -0,0 +1,29 @@
+//! Rust client for the confidential-bid program.
+//!
+//! - [`pda`] derives every address the program checks
+//! - [`instructions`] builds each instruction with its full account list
+//! - [`decode`] reads accounts, CPI events and return data
+//! - [`error`] maps custom error codes back to [`AuctionError`]
+//! - [`private_bid`] derives the one-off keys bidding on private auctions
+//!
+//! Handles only become known by simulating an instruction, so the builders
+//! that grant decryption take the handles from the previous simulation and
+//! leave the allowance accounts out when they are `None`. Handles missing from
+//! an instruction's return data, such as balances, are read from the
+//! simulated accounts with [`decode`].
+pub mod decode;
+pub mod error;
+pub mod instructions;
+pub mod pda;
+pub mod private_bid;
+
+pub use confidential_bid::{
+    error::AuctionError,
+    events,
+    returns::{AuctionSummary, BidStatus, CheckWinnerResult, CloseAuctionResult, PlaceBidResult},
+    state::{
+        AuctionPhase, AuctionState, AuctionStatus, AuctionType, Bid, BidReduction, PayoutRecipient,
+    },
+    ID,
+};
+pub use error::ClientError;
@@ -0,0 +1,91 @@
+//! Addresses derived exactly as the program derives them on-chain.
+use anchor_lang::{
+    event::EVENT_AUTHORITY_SEED, prelude::Pubkey, solana_program::bpf_loader_upgradeable,
+};
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use confidential_bid::{
+    constants::{ATTESTER_SEED, AUCTION_SEED, BID_SEED, ORGANIZER_SEED, REDUCTION_SEED},
+    inco::{allowance_address, inco_ata_address},
+    metadata::metadata_address,
+    state::Bid,
+    ID,
+};
+
+/// Per-organizer profile allocating auction ids
+pub fn organizer_profile(organizer: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[ORGANIZER_SEED, organizer.as_ref()], &ID).0
+}
+
+pub fn auction(organizer: &Pubkey, auction_id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            AUCTION_SEED,
+            organizer.as_ref(),
+            auction_id.to_le_bytes().as_ref(),
+        ],
+        &ID,
+    )
+    .0
+}
+
+/// Bid of `bidder`, keyed by the commitment of `bid_secret` on private auctions
+pub fn bid(auction: &Pubkey, bidder: &Pubkey, bid_secret: Option<&[u8; 32]>) -> Pubkey {
+    let key = Bid::seed_key(
+        bidder,
+        bid_secret.map(|secret| Bid::commitment(bidder, secret)),
+    );
+    Pubkey::find_program_address(&[BID_SEED, auction.as_ref(), key.as_ref()], &ID).0
+}
+
+/// Partial standings of a deferred auction over the bids from sequence `start`
+pub fn reduction(auction: &Pubkey, start: u32) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            REDUCTION_SEED,
+            auction.as_ref(),
+            start.to_le_bytes().as_ref(),
+        ],
+        &ID,
+    )
+    .0
+}
+
+/// Program-wide attester key checked against decryption attestations
+pub fn attester_config() -> Pubkey {
+    Pubkey::find_program_address(&[ATTESTER_SEED], &ID).0
+}
+
+/// Upgradeable loader account holding the program's upgrade authority
+pub fn program_data() -> Pubkey {
+    Pubkey::find_program_address(&[ID.as_ref()], &bpf_loader_upgradeable::ID).0
+}
+
+/// SPL associated token account holding the prize
+pub fn vault(auction: &Pubkey, prize_mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(auction, prize_mint, token_program)
+}
+
+/// Inco token account escrowing every bid
+pub fn bid_vault(auction: &Pubkey, bid_mint: &Pubkey) -> Pubkey {
+    inco_ata_address(auction, bid_mint)
+}
+
+/// Inco associated token account of `wallet`
+pub fn inco_ata(wallet: &Pubkey, mint: &Pubkey) -> Pubkey {
+    inco_ata_address(wallet, mint)
+}
+
+/// Inco Lightning allowance letting `allowed` decrypt `handle`
+pub fn allowance(handle: u128, allowed: &Pubkey) -> Pubkey {
+    allowance_address(handle, allowed)
+}
+
+/// Metaplex metadata of the prize mint
+pub fn prize_metadata(prize_mint: &Pubkey) -> Pubkey {
+    metadata_address(prize_mint)
+}
+
+/// Authority signing the program's `#[event_cpi]` self-invocations
+pub fn event_authority() -> Pubkey {
+    Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], &ID).0
+}
@@ -0,0 +1,36 @@
+//! One-off bid keys for auctions created with `private_bids`.
+//!
+//! A private bid is placed by a bid key used for that auction only: it signs
+//! the bid, owns the Inco token account the bid is drawn from and receives
+//! the allowances, the refund and the prize. A relayer signs as `payer`, so
+//! the bid key never needs lamports. The wallet itself appears in no
+//! transaction; it only signs [`bid_key_message`] off-chain, which lets it
+//! recover the bid key and secret later since Ed25519 signatures are
+//! deterministic.
+//!
+//! The bid key's tokens must come from a source not linked to the wallet;
+//! a direct transfer from the wallet's token account names both keys.
+use anchor_lang::{prelude::Pubkey, solana_program::hash::hashv};
+
+const BID_KEY_DOMAIN: &[u8] = b"confidential-bid:bid-key";
+const BID_SECRET_DOMAIN: &[u8] = b"confidential-bid:bid-secret";
+
+/// Seed of the bid key and secret of the commitment keying a private bid
+pub struct PrivateBid {
+    /// Ed25519 seed of the bid key, e.g. for `keypair_from_seed`
+    pub key_seed: [u8; 32],
+    pub bid_secret: [u8; 32],
+}
+
+/// Message the wallet signs off-chain to derive its bid on `auction`
+pub fn bid_key_message(auction: &Pubkey) -> Vec<u8> {
+    [BID_KEY_DOMAIN, auction.as_ref()].concat()
+}
+
+/// Bid key seed and secret from the wallet's signature over [`bid_key_message`]
+pub fn private_bid(signature: &[u8; 64]) -> PrivateBid {
+    PrivateBid {
+        key_seed: hashv(&[BID_KEY_DOMAIN, signature]).to_bytes(),
+        bid_secret: hashv(&[BID_SECRET_DOMAIN, signature]).to_bytes(),
+    }
+}
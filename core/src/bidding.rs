@@ -0,0 +1,166 @@
+//! Encrypted order statistics and the winner check
+use crate::EncryptedOps;
+
+/// Encrypted order statistics of an auction that has received bids
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Standings<U> {
+    pub highest: U,
+    /// `None` until a second bid, standing for zero
+    pub second_highest: Option<U>,
+    /// Placement key of the bid holding `highest`
+    pub highest_timestamp: U,
+}
+
+/// Standings after a bid of `amount` with placement key `timestamp`, `standings` being
+/// `None` before the first bid.
+///
+/// A bid only takes the lead when strictly greater, so on a tie the earlier
+/// bid keeps it and the later one becomes the second highest at the same
+/// amount. The first bid leads without any operation and the second skips the
+/// comparison against a zero second highest. Every later bid takes 2 `gt` and
+/// 4 `select`, the least these operations allow: one comparison and a select
+/// per leading value, then a comparison and a select for second place.
+pub fn place_bid<O: EncryptedOps>(
+    ops: &mut O,
+    standings: Option<Standings<O::Uint>>,
+    amount: O::Uint,
+    timestamp: O::Uint,
+) -> Result<Standings<O::Uint>, O::Error> {
+    let Some(standings) = standings else {
+        return Ok(Standings {
+            highest: amount,
+            second_highest: None,
+            highest_timestamp: timestamp,
+        });
+    };
+
+    let takes_lead = ops.gt(amount, standings.highest)?;
+    let highest = ops.select(takes_lead, amount, standings.highest)?;
+    let highest_timestamp = ops.select(takes_lead, timestamp, standings.highest_timestamp)?;
+
+    // Second place is the max of the previous second and the min of the bid
+    // and the previous highest
+    let lower = ops.select(takes_lead, standings.highest, amount)?;
+    let second_highest = match standings.second_highest {
+        None => lower,
+        Some(second) => {
+            let beats_second = ops.gt(lower, second)?;
+            ops.select(beats_second, lower, second)?
+        }
+    };
+
+    Ok(Standings {
+        highest,
+        second_highest: Some(second_highest),
+        highest_timestamp,
+    })
+}
+
+/// Standings of two consecutive runs of bids, `earlier` holding the bids
+/// placed first, `None` standing for a run without bids.
+///
+/// Same result as placing every bid of `later` after those of `earlier`: the
+/// later run only takes the lead when strictly greater, and second place is
+/// the max of the lower leader and both seconds.
+pub fn merge_standings<O: EncryptedOps>(
+    ops: &mut O,
+    earlier: Option<Standings<O::Uint>>,
+    later: Option<Standings<O::Uint>>,
+) -> Result<Option<Standings<O::Uint>>, O::Error> {
+    let (earlier, later) = match (earlier, later) {
+        (Some(earlier), Some(later)) => (earlier, later),
+        (earlier, later) => return Ok(earlier.or(later)),
+    };
+
+    let takes_lead = ops.gt(later.highest, earlier.highest)?;
+    let highest = ops.select(takes_lead, later.highest, earlier.highest)?;
+    let highest_timestamp = ops.select(
+        takes_lead,
+        later.highest_timestamp,
+        earlier.highest_timestamp,
+    )?;
+
+    let mut second_highest = ops.select(takes_lead, earlier.highest, later.highest)?;
+    for second in [earlier.second_highest, later.second_highest]
+        .into_iter()
+        .flatten()
+    {
+        let beats_second = ops.gt(second, second_highest)?;
+        second_highest = ops.select(beats_second, second, second_highest)?;
+    }
+
+    Ok(Some(Standings {
+        highest,
+        second_highest: Some(second_highest),
+        highest_timestamp,
+    }))
+}
+
+/// Encrypted placement key of a bid: the unix `timestamp` in the high bits and
+/// its position among the auction's bids in the low 32, so no two bids of an
+/// auction share one even within a second.
+///
+/// The position is the public `sequence`, plus the encrypted count of earlier
+/// bids when the auction hides it, `hidden_sequence` being `None` for the first
+/// bid or when the count is public.
+pub fn placement_key<O: EncryptedOps>(
+    ops: &mut O,
+    timestamp: i64,
+    sequence: u32,
+    hidden_sequence: Option<O::Uint>,
+) -> Result<O::Uint, O::Error> {
+    let key = ops.constant(((timestamp as u128) << 32) | sequence as u128)?;
+    match hidden_sequence {
+        Some(hidden_sequence) => ops.add(key, hidden_sequence),
+        None => Ok(key),
+    }
+}
+
+/// Encrypted participation count after one more bid, `count` being `None`
+/// before the first one
+pub fn count_bid<O: EncryptedOps>(
+    ops: &mut O,
+    count: Option<O::Uint>,
+) -> Result<O::Uint, O::Error> {
+    let one = ops.constant(1)?;
+    match count {
+        Some(count) => ops.add(count, one),
+        None => Ok(one),
+    }
+}
+
+/// Whether the highest bid reached `reserve_price`, `highest` being `None`
+/// when nobody bid.
+///
+/// An auction without bids never meets its reserve, even a zero one, so it
+/// can always be cancelled.
+pub fn reserve_met<O: EncryptedOps>(
+    ops: &mut O,
+    highest: Option<O::Uint>,
+    reserve_price: u64,
+) -> Result<O::Bool, O::Error> {
+    let Some(highest) = highest else {
+        let zero = ops.constant(0)?;
+        return ops.gt(zero, zero);
+    };
+    let reserve = ops.constant(reserve_price as u128)?;
+    ops.ge(highest, reserve)
+}
+
+/// Whether a bid won: it holds the highest amount, it was the earliest at
+/// that amount and the reserve was met.
+///
+/// The placement key decides ties and is unique within an auction, so two
+/// bids at the highest amount never both win, even placed in the same second.
+pub fn is_winner<O: EncryptedOps>(
+    ops: &mut O,
+    amount: O::Uint,
+    timestamp: O::Uint,
+    standings: &Standings<O::Uint>,
+    reserve_met: O::Bool,
+) -> Result<O::Bool, O::Error> {
+    let is_highest = ops.eq(amount, standings.highest)?;
+    let is_earliest = ops.eq(timestamp, standings.highest_timestamp)?;
+    let leads = ops.and(is_highest, is_earliest)?;
+    ops.and(leads, reserve_met)
+}
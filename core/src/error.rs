@@ -0,0 +1,48 @@
+use std::fmt;
+
+/// Broken timing or status rule, named after the matching program error
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RuleError {
+    AuctionNotOpen,
+    AuctionNotStarted,
+    AuctionEnded,
+    AuctionNotEnded,
+    AuctionNotClosed,
+    AuctionNotSettled,
+    AuctionAlreadyStarted,
+    AuctionHasBids,
+    InvalidStartTime,
+    InvalidEndTime,
+    ExtensionTooLong,
+    BidCountHidden,
+    NotEnoughBids,
+    WinnersNotResolved,
+    MathOverflow,
+}
+
+pub type RuleResult<T> = Result<T, RuleError>;
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            RuleError::AuctionNotOpen => "Auction is not open",
+            RuleError::AuctionNotStarted => "Auction has not started yet",
+            RuleError::AuctionEnded => "Auction has ended",
+            RuleError::AuctionNotEnded => "Auction not ended",
+            RuleError::AuctionNotClosed => "Auction must be closed",
+            RuleError::AuctionNotSettled => "Auction must be settled",
+            RuleError::AuctionAlreadyStarted => "Auction has already started",
+            RuleError::AuctionHasBids => "Auction already has bids",
+            RuleError::InvalidStartTime => "Invalid start time",
+            RuleError::InvalidEndTime => "Invalid end time",
+            RuleError::ExtensionTooLong => "Extension exceeds the maximum allowed",
+            RuleError::BidCountHidden => "Bid count is hidden for this auction",
+            RuleError::NotEnoughBids => "Not enough bids to end the auction early",
+            RuleError::WinnersNotResolved => "Every bid must be resolved before settlement",
+            RuleError::MathOverflow => "Math Overflow",
+        };
+        f.write_str(msg)
+    }
+}
+
+impl std::error::Error for RuleError {}
@@ -0,0 +1,18 @@
+//! Auction rules of confidential-bid, free of Anchor and Inco.
+//!
+//! Encrypted values are only touched through [`EncryptedOps`]. The program
+//! implements it with Inco Lightning CPIs, [`Plaintext`] implements it over
+//! plain integers for native tests and off-chain tooling. Timing and status
+//! rules work on public values and fail with a [`RuleError`], which the program
+//! maps onto its own error codes.
+mod bidding;
+mod error;
+mod lifecycle;
+mod ops;
+mod settlement;
+
+pub use bidding::*;
+pub use error::*;
+pub use lifecycle::*;
+pub use ops::*;
+pub use settlement::*;
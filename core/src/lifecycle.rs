@@ -0,0 +1,163 @@
+//! Timing and status rules, all on public auction data
+use crate::{RuleError, RuleResult};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    Open,
+    Closed,
+    Settled,
+    Cancelled,
+}
+
+/// The public fields the timing rules read
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Schedule {
+    pub status: Status,
+    pub start_time: i64,
+    pub end_time: i64,
+}
+
+fn require(condition: bool, error: RuleError) -> RuleResult<()> {
+    if condition {
+        Ok(())
+    } else {
+        Err(error)
+    }
+}
+
+/// Timing rules shared by auction creation and updates
+pub fn validate_schedule(start_time: i64, end_time: i64, now: i64) -> RuleResult<()> {
+    require(start_time > now, RuleError::InvalidStartTime)?;
+    require(end_time > start_time, RuleError::InvalidEndTime)
+}
+
+/// Parameters are frozen once the auction starts or receives a bid
+pub fn check_update(schedule: Schedule, bid_count: u32, now: i64) -> RuleResult<()> {
+    require(schedule.status == Status::Open, RuleError::AuctionNotOpen)?;
+    require(now < schedule.start_time, RuleError::AuctionAlreadyStarted)?;
+    require(bid_count == 0, RuleError::AuctionHasBids)
+}
+
+/// Bids land from `start_time` up to, but excluding, `end_time`
+pub fn check_bid(schedule: Schedule, now: i64) -> RuleResult<()> {
+    require(now >= schedule.start_time, RuleError::AuctionNotStarted)?;
+    require(now < schedule.end_time, RuleError::AuctionEnded)?;
+    require(schedule.status == Status::Open, RuleError::AuctionNotOpen)
+}
+
+/// Status after closing an auction whose end time has passed
+pub fn close(schedule: Schedule, now: i64) -> RuleResult<Status> {
+    require(schedule.status == Status::Open, RuleError::AuctionNotOpen)?;
+    require(now >= schedule.end_time, RuleError::AuctionNotEnded)?;
+    Ok(Status::Closed)
+}
+
+/// Bids of a deferred auction are reduced once bidding is over and before it closes
+pub fn check_reduce(schedule: Schedule, now: i64) -> RuleResult<()> {
+    require(schedule.status == Status::Open, RuleError::AuctionNotOpen)?;
+    require(now >= schedule.end_time, RuleError::AuctionNotEnded)
+}
+
+/// Status after ending a live auction early.
+///
+/// `bid_count` is `None` when the auction hides it, which cannot be checked
+/// against `min_bids`.
+pub fn end_early(
+    schedule: Schedule,
+    bid_count: Option<u32>,
+    min_bids: u32,
+    now: i64,
+) -> RuleResult<Status> {
+    require(schedule.status == Status::Open, RuleError::AuctionNotOpen)?;
+    require(now >= schedule.start_time, RuleError::AuctionNotStarted)?;
+    require(now < schedule.end_time, RuleError::AuctionEnded)?;
+    let bid_count = bid_count.ok_or(RuleError::BidCountHidden)?;
+    require(bid_count >= min_bids, RuleError::NotEnoughBids)?;
+    Ok(Status::Closed)
+}
+
+/// Total extension after moving the end of a live auction to `new_end_time`.
+///
+/// The deadline only ever moves later, and only up to `max_total_extension`
+/// over the whole auction.
+pub fn extend(
+    schedule: Schedule,
+    total_extension: i64,
+    new_end_time: i64,
+    max_total_extension: i64,
+    now: i64,
+) -> RuleResult<i64> {
+    require(schedule.status == Status::Open, RuleError::AuctionNotOpen)?;
+    require(now < schedule.end_time, RuleError::AuctionEnded)?;
+    require(new_end_time > schedule.end_time, RuleError::InvalidEndTime)?;
+
+    let extension = new_end_time
+        .checked_sub(schedule.end_time)
+        .ok_or(RuleError::MathOverflow)?;
+    let total_extension = total_extension
+        .checked_add(extension)
+        .ok_or(RuleError::MathOverflow)?;
+    require(
+        total_extension <= max_total_extension,
+        RuleError::ExtensionTooLong,
+    )?;
+    Ok(total_extension)
+}
+
+/// Winners can only be checked once the reserve check is sealed
+pub fn check_winner_allowed(status: Status) -> RuleResult<()> {
+    require(status == Status::Closed, RuleError::AuctionNotClosed)
+}
+
+/// Settlement waits until every bid has its winner handle.
+///
+/// `bid_count` is `None` when the auction hides it, such auctions cannot tell
+/// and are never held back.
+pub fn check_resolved(bid_count: Option<u32>, resolved_count: u32) -> RuleResult<()> {
+    match bid_count {
+        Some(bid_count) => require(resolved_count >= bid_count, RuleError::WinnersNotResolved),
+        None => Ok(()),
+    }
+}
+
+/// Status after paying out a closed auction
+pub fn settle(status: Status) -> RuleResult<Status> {
+    require(status == Status::Closed, RuleError::AuctionNotClosed)?;
+    Ok(Status::Settled)
+}
+
+/// The winner takes the prize once the auction is closed, before or after settlement
+pub fn check_claim(status: Status) -> RuleResult<()> {
+    require(
+        matches!(status, Status::Closed | Status::Settled),
+        RuleError::AuctionNotClosed,
+    )
+}
+
+/// Losing bids are returned once the auction is closed, cancelled ones included
+pub fn check_refund(status: Status) -> RuleResult<()> {
+    require(
+        matches!(status, Status::Closed | Status::Settled | Status::Cancelled),
+        RuleError::AuctionNotClosed,
+    )
+}
+
+/// Status after calling off an auction whose reserve was not met, closed or
+/// already settled since settling it paid out nothing
+pub fn cancel(status: Status) -> RuleResult<Status> {
+    require(
+        matches!(status, Status::Closed | Status::Settled),
+        RuleError::AuctionNotClosed,
+    )?;
+    Ok(Status::Cancelled)
+}
+
+/// The result is only published once the proceeds are paid out
+pub fn check_reveal(status: Status) -> RuleResult<()> {
+    require(status == Status::Settled, RuleError::AuctionNotSettled)
+}
+
+/// Disclosed bids are recorded once bidding is over
+pub fn check_reveal_bid(status: Status) -> RuleResult<()> {
+    require(status != Status::Open, RuleError::AuctionNotClosed)
+}
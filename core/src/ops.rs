@@ -0,0 +1,84 @@
+use std::convert::Infallible;
+
+/// Operations on encrypted values the auction rules are written against.
+///
+/// Each call may be a CPI, so the rules keep the number of calls as low as the
+/// logic allows.
+pub trait EncryptedOps {
+    /// Encrypted unsigned integer
+    type Uint: Copy;
+    /// Encrypted boolean
+    type Bool: Copy;
+    type Error;
+
+    /// Encrypts a public value
+    fn constant(&mut self, value: u128) -> Result<Self::Uint, Self::Error>;
+    fn gt(&mut self, lhs: Self::Uint, rhs: Self::Uint) -> Result<Self::Bool, Self::Error>;
+    fn ge(&mut self, lhs: Self::Uint, rhs: Self::Uint) -> Result<Self::Bool, Self::Error>;
+    fn eq(&mut self, lhs: Self::Uint, rhs: Self::Uint) -> Result<Self::Bool, Self::Error>;
+    fn and(&mut self, lhs: Self::Bool, rhs: Self::Bool) -> Result<Self::Bool, Self::Error>;
+    fn select(
+        &mut self,
+        control: Self::Bool,
+        if_true: Self::Uint,
+        if_false: Self::Uint,
+    ) -> Result<Self::Uint, Self::Error>;
+    /// Wrapping, as the encrypted arithmetic is
+    fn add(&mut self, lhs: Self::Uint, rhs: Self::Uint) -> Result<Self::Uint, Self::Error>;
+    /// Wrapping, as the encrypted arithmetic is
+    fn sub(&mut self, lhs: Self::Uint, rhs: Self::Uint) -> Result<Self::Uint, Self::Error>;
+    /// Wrapping, as the encrypted arithmetic is
+    fn mul(&mut self, lhs: Self::Uint, rhs: Self::Uint) -> Result<Self::Uint, Self::Error>;
+    /// 0 when dividing by 0
+    fn div(&mut self, lhs: Self::Uint, rhs: Self::Uint) -> Result<Self::Uint, Self::Error>;
+}
+
+/// [`EncryptedOps`] over plain integers, matching the encrypted semantics
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Plaintext;
+
+impl EncryptedOps for Plaintext {
+    type Uint = u128;
+    type Bool = bool;
+    type Error = Infallible;
+
+    fn constant(&mut self, value: u128) -> Result<u128, Infallible> {
+        Ok(value)
+    }
+
+    fn gt(&mut self, lhs: u128, rhs: u128) -> Result<bool, Infallible> {
+        Ok(lhs > rhs)
+    }
+
+    fn ge(&mut self, lhs: u128, rhs: u128) -> Result<bool, Infallible> {
+        Ok(lhs >= rhs)
+    }
+
+    fn eq(&mut self, lhs: u128, rhs: u128) -> Result<bool, Infallible> {
+        Ok(lhs == rhs)
+    }
+
+    fn and(&mut self, lhs: bool, rhs: bool) -> Result<bool, Infallible> {
+        Ok(lhs && rhs)
+    }
+
+    fn select(&mut self, control: bool, if_true: u128, if_false: u128) -> Result<u128, Infallible> {
+        Ok(if control { if_true } else { if_false })
+    }
+
+    fn add(&mut self, lhs: u128, rhs: u128) -> Result<u128, Infallible> {
+        Ok(lhs.wrapping_add(rhs))
+    }
+
+    fn sub(&mut self, lhs: u128, rhs: u128) -> Result<u128, Infallible> {
+        Ok(lhs.wrapping_sub(rhs))
+    }
+
+    fn mul(&mut self, lhs: u128, rhs: u128) -> Result<u128, Infallible> {
+        Ok(lhs.wrapping_mul(rhs))
+    }
+
+    fn div(&mut self, lhs: u128, rhs: u128) -> Result<u128, Infallible> {
+        Ok(lhs.checked_div(rhs).unwrap_or(0))
+    }
+}
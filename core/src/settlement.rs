@@ -0,0 +1,119 @@
+//! Clearing price and the split of the proceeds
+use crate::EncryptedOps;
+
+/// Which bid sets the price the winner pays
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Pricing {
+    /// The highest bid
+    FirstPrice,
+    /// The second highest bid, never less than the reserve (Vickrey)
+    SecondPrice,
+}
+
+/// Price the winner pays, meaningful only when the reserve was met
+pub fn clearing_price<O: EncryptedOps>(
+    ops: &mut O,
+    pricing: Pricing,
+    highest: O::Uint,
+    second_highest: Option<O::Uint>,
+    reserve_price: u64,
+) -> Result<O::Uint, O::Error> {
+    match pricing {
+        Pricing::FirstPrice => Ok(highest),
+        Pricing::SecondPrice => {
+            let reserve = ops.constant(reserve_price as u128)?;
+            // Without a second bid the second highest is zero, the reserve is the price
+            let Some(second) = second_highest else {
+                return Ok(reserve);
+            };
+            let second_ge_reserve = ops.ge(second, reserve)?;
+            ops.select(second_ge_reserve, second, reserve)
+        }
+    }
+}
+
+/// Part of the winning bid above the clearing price, handed back to the
+/// winner. `None` for first-price auctions, where the winner pays their bid.
+pub fn winner_excess<O: EncryptedOps>(
+    ops: &mut O,
+    pricing: Pricing,
+    winning_bid: O::Uint,
+    price: O::Uint,
+) -> Result<Option<O::Uint>, O::Error> {
+    match pricing {
+        Pricing::FirstPrice => Ok(None),
+        Pricing::SecondPrice => ops.sub(winning_bid, price).map(Some),
+    }
+}
+
+/// Amount owed by the winner, nothing when the reserve was not met so
+/// bidders get refunded in full
+pub fn proceeds<O: EncryptedOps>(
+    ops: &mut O,
+    price: O::Uint,
+    reserve_met: O::Bool,
+) -> Result<O::Uint, O::Error> {
+    let zero = ops.constant(0)?;
+    ops.select(reserve_met, price, zero)
+}
+
+/// Encrypted amounts paid out of the proceeds
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Payouts<U> {
+    /// One per creator, in the order of the royalty rates
+    pub royalties: Vec<U>,
+    /// One per payout recipient, in the order of the shares
+    pub shares: Vec<U>,
+    /// What is left for the organizer, rounding included
+    pub remainder: U,
+}
+
+/// Splits `proceeds` between creators, payout recipients and the organizer.
+///
+/// Creator royalties come off the top. `royalty_rates` are the seller fee in
+/// bps times each creator's share in percent, so they are divided by
+/// `bps_denominator * 100`. Recipients are then paid `share_bps` of what is
+/// left and the organizer gets the rest.
+pub fn split_proceeds<O: EncryptedOps>(
+    ops: &mut O,
+    proceeds: O::Uint,
+    royalty_rates: &[u128],
+    share_bps: &[u16],
+    bps_denominator: u16,
+) -> Result<Payouts<O::Uint>, O::Error> {
+    let zero = ops.constant(0)?;
+
+    let mut royalties = Vec::with_capacity(royalty_rates.len());
+    let mut royalty_total = zero;
+    if !royalty_rates.is_empty() {
+        let royalty_denominator = ops.constant(bps_denominator as u128 * 100)?;
+        for rate in royalty_rates {
+            let rate = ops.constant(*rate)?;
+            let scaled = ops.mul(proceeds, rate)?;
+            let royalty = ops.div(scaled, royalty_denominator)?;
+            royalty_total = ops.add(royalty_total, royalty)?;
+            royalties.push(royalty);
+        }
+    }
+    let net_proceeds = ops.sub(proceeds, royalty_total)?;
+
+    let mut shares = Vec::with_capacity(share_bps.len());
+    let mut paid = zero;
+    if !share_bps.is_empty() {
+        let denominator = ops.constant(bps_denominator as u128)?;
+        for bps in share_bps {
+            let bps = ops.constant(*bps as u128)?;
+            let scaled = ops.mul(net_proceeds, bps)?;
+            let share = ops.div(scaled, denominator)?;
+            paid = ops.add(paid, share)?;
+            shares.push(share);
+        }
+    }
+    let remainder = ops.sub(net_proceeds, paid)?;
+
+    Ok(Payouts {
+        royalties,
+        shares,
+        remainder,
+    })
+}
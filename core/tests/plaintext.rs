@@ -0,0 +1,240 @@
+use confidential_bid_core::{
+    cancel, check_bid, check_claim, check_reduce, check_refund, check_resolved, check_reveal,
+    check_reveal_bid, clearing_price, close, count_bid, end_early, extend, is_winner,
+    merge_standings, place_bid, placement_key, proceeds, reserve_met, split_proceeds,
+    winner_excess, Plaintext, Pricing, RuleError, Schedule, Standings, Status,
+};
+
+const OPEN: Schedule = Schedule {
+    status: Status::Open,
+    start_time: 100,
+    end_time: 200,
+};
+
+fn run(bids: &[(u128, u128)]) -> Standings<u128> {
+    let mut standings = None;
+    for (amount, timestamp) in bids {
+        standings = Some(place_bid(&mut Plaintext, standings, *amount, *timestamp).unwrap());
+    }
+    standings.expect("at least one bid")
+}
+
+fn winners(bids: &[(u128, u128)], reserve_price: u64) -> Vec<bool> {
+    let standings = run(bids);
+    let met = reserve_met(&mut Plaintext, Some(standings.highest), reserve_price).unwrap();
+    bids.iter()
+        .map(|(amount, timestamp)| {
+            is_winner(&mut Plaintext, *amount, *timestamp, &standings, met).unwrap()
+        })
+        .collect()
+}
+
+#[test]
+fn bid_window_is_start_inclusive_end_exclusive() {
+    assert_eq!(check_bid(OPEN, 99), Err(RuleError::AuctionNotStarted));
+    assert_eq!(check_bid(OPEN, 100), Ok(()));
+    assert_eq!(check_bid(OPEN, 199), Ok(()));
+    assert_eq!(check_bid(OPEN, 200), Err(RuleError::AuctionEnded));
+
+    let closed = Schedule {
+        status: Status::Closed,
+        ..OPEN
+    };
+    assert_eq!(check_bid(closed, 150), Err(RuleError::AuctionNotOpen));
+}
+
+#[test]
+fn close_and_end_early_share_the_closed_status() {
+    assert_eq!(close(OPEN, 199), Err(RuleError::AuctionNotEnded));
+    assert_eq!(close(OPEN, 200), Ok(Status::Closed));
+
+    assert_eq!(
+        end_early(OPEN, None, 2, 150),
+        Err(RuleError::BidCountHidden)
+    );
+    assert_eq!(
+        end_early(OPEN, Some(1), 2, 150),
+        Err(RuleError::NotEnoughBids)
+    );
+    assert_eq!(end_early(OPEN, Some(2), 2, 150), Ok(Status::Closed));
+    assert_eq!(
+        end_early(OPEN, Some(2), 2, 200),
+        Err(RuleError::AuctionEnded)
+    );
+}
+
+#[test]
+fn settlement_waits_for_every_public_bid_to_resolve() {
+    assert_eq!(
+        check_resolved(Some(3), 2),
+        Err(RuleError::WinnersNotResolved)
+    );
+    assert_eq!(check_resolved(Some(3), 3), Ok(()));
+    assert_eq!(check_resolved(Some(0), 0), Ok(()));
+    assert_eq!(check_resolved(None, 0), Ok(()));
+}
+
+#[test]
+fn extensions_accumulate_up_to_the_cap() {
+    assert_eq!(extend(OPEN, 0, 250, 100, 150), Ok(50));
+    assert_eq!(
+        extend(OPEN, 60, 250, 100, 150),
+        Err(RuleError::ExtensionTooLong)
+    );
+    assert_eq!(
+        extend(OPEN, 0, 200, 100, 150),
+        Err(RuleError::InvalidEndTime)
+    );
+}
+
+#[test]
+fn standings_track_the_two_highest_bids() {
+    let standings = run(&[(300, 1), (500, 2), (400, 3)]);
+    assert_eq!(standings.highest, 500);
+    assert_eq!(standings.second_highest, Some(400));
+    assert_eq!(standings.highest_timestamp, 2);
+
+    assert_eq!(run(&[(300, 1)]).second_highest, None);
+    assert_eq!(run(&[(300, 1), (200, 2)]).second_highest, Some(200));
+    assert_eq!(run(&[(0, 1), (0, 2)]).highest_timestamp, 1);
+}
+
+#[test]
+fn merged_runs_match_placing_every_bid_in_order() {
+    let bids = [(500, 1), (300, 2), (700, 3), (500, 4), (700, 5), (100, 6)];
+    for split in 0..=bids.len() {
+        for merge_at in split..=bids.len() {
+            let runs = [&bids[..split], &bids[split..merge_at], &bids[merge_at..]];
+            let merged = runs.iter().fold(None, |earlier, bids| {
+                let later = (!bids.is_empty()).then(|| run(bids));
+                merge_standings(&mut Plaintext, earlier, later).unwrap()
+            });
+            assert_eq!(merged, Some(run(&bids)), "runs {runs:?}");
+        }
+    }
+    assert_eq!(merge_standings(&mut Plaintext, None, None).unwrap(), None);
+}
+
+#[test]
+fn bids_are_reduced_between_end_time_and_close() {
+    assert_eq!(check_reduce(OPEN, 199), Err(RuleError::AuctionNotEnded));
+    assert_eq!(check_reduce(OPEN, 200), Ok(()));
+
+    let closed = Schedule {
+        status: Status::Closed,
+        ..OPEN
+    };
+    assert_eq!(check_reduce(closed, 250), Err(RuleError::AuctionNotOpen));
+}
+
+#[test]
+fn ties_go_to_the_earliest_bid() {
+    let standings = run(&[(500, 1), (300, 2), (500, 3)]);
+    assert_eq!(standings.second_highest, Some(500));
+    assert_eq!(standings.highest_timestamp, 1);
+    assert_eq!(
+        winners(&[(500, 1), (300, 2), (500, 3)], 0),
+        [true, false, false]
+    );
+}
+
+#[test]
+fn bids_in_the_same_second_get_distinct_keys() {
+    let key = |sequence, hidden| placement_key(&mut Plaintext, 150, sequence, hidden).unwrap();
+    assert_eq!(key(0, None), 150 << 32);
+    assert_ne!(key(0, None), key(1, None));
+    // A hidden count leaves the public sequence at 0
+    assert_eq!(key(0, Some(1)), key(1, None));
+
+    let (first, second) = (key(0, None), key(1, None));
+    assert_eq!(winners(&[(500, first), (500, second)], 0), [true, false]);
+}
+
+#[test]
+fn nobody_wins_below_the_reserve() {
+    assert_eq!(winners(&[(300, 1), (500, 2)], 600), [false, false]);
+    assert_eq!(winners(&[(300, 1), (500, 2)], 500), [false, true]);
+}
+
+#[test]
+fn no_bid_never_meets_the_reserve() {
+    assert!(!reserve_met(&mut Plaintext, None, 0).unwrap());
+    assert!(reserve_met(&mut Plaintext, Some(0), 0).unwrap());
+}
+
+#[test]
+fn the_first_bid_counts_as_one() {
+    let first = count_bid(&mut Plaintext, None).unwrap();
+    assert_eq!(first, 1);
+    assert_eq!(count_bid(&mut Plaintext, Some(first)).unwrap(), 2);
+}
+
+#[test]
+fn vickrey_pays_the_second_highest_floored_at_the_reserve() {
+    let price = |second, reserve| {
+        clearing_price(&mut Plaintext, Pricing::SecondPrice, 700, second, reserve).unwrap()
+    };
+    assert_eq!(price(Some(650), 0), 650);
+    assert_eq!(price(Some(650), 680), 680);
+    assert_eq!(price(None, 680), 680);
+    assert_eq!(
+        clearing_price(&mut Plaintext, Pricing::FirstPrice, 700, Some(650), 0).unwrap(),
+        700
+    );
+}
+
+#[test]
+fn vickrey_winner_gets_back_what_they_bid_above_the_price() {
+    assert_eq!(
+        winner_excess(&mut Plaintext, Pricing::SecondPrice, 700, 650).unwrap(),
+        Some(50)
+    );
+    assert_eq!(
+        winner_excess(&mut Plaintext, Pricing::FirstPrice, 700, 700).unwrap(),
+        None
+    );
+}
+
+#[test]
+fn payouts_follow_the_close() {
+    assert_eq!(check_claim(Status::Open), Err(RuleError::AuctionNotClosed));
+    assert_eq!(check_claim(Status::Settled), Ok(()));
+    assert_eq!(
+        check_claim(Status::Cancelled),
+        Err(RuleError::AuctionNotClosed)
+    );
+    assert_eq!(check_refund(Status::Cancelled), Ok(()));
+    assert_eq!(check_refund(Status::Open), Err(RuleError::AuctionNotClosed));
+    assert_eq!(cancel(Status::Closed), Ok(Status::Cancelled));
+    assert_eq!(cancel(Status::Settled), Ok(Status::Cancelled));
+    assert_eq!(cancel(Status::Open), Err(RuleError::AuctionNotClosed));
+    assert_eq!(cancel(Status::Cancelled), Err(RuleError::AuctionNotClosed));
+    assert_eq!(
+        check_reveal(Status::Closed),
+        Err(RuleError::AuctionNotSettled)
+    );
+    assert_eq!(check_reveal(Status::Settled), Ok(()));
+    assert_eq!(
+        check_reveal_bid(Status::Open),
+        Err(RuleError::AuctionNotClosed)
+    );
+    assert_eq!(check_reveal_bid(Status::Cancelled), Ok(()));
+}
+
+#[test]
+fn proceeds_split_royalties_first_then_shares() {
+    assert_eq!(proceeds(&mut Plaintext, 1_000, false).unwrap(), 0);
+
+    // 5% royalty split 60/40 between two creators, then 25% to a co-owner
+    let payouts = split_proceeds(
+        &mut Plaintext,
+        1_000,
+        &[500 * 60, 500 * 40],
+        &[2_500],
+        10_000,
+    )
+    .unwrap();
+    assert_eq!(payouts.royalties, [30, 20]);
+    assert_eq!(payouts.shares, [237]);
+    assert_eq!(payouts.remainder, 713);
+}
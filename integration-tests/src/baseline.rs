@@ -0,0 +1,190 @@
+//! confidential-bid as first deployed, for comparing instruction costs.
+//!
+//! `scripts/build-baseline.sh` builds it into `target/deploy` as
+//! `confidential_bid_baseline`, next to [`ZERO_HANDLE_LIGHTNING`]. That
+//! program compares bids against the never-assigned handle 0, so it only runs
+//! on the mock built to read 0 as an encrypted zero. Its four instructions
+//! are built here from their first layout, the client only knows the current
+//! one.
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::{
+        hash::hash,
+        instruction::{AccountMeta, Instruction},
+        system_program,
+    },
+    AnchorSerialize,
+};
+use anchor_spl::{
+    associated_token, associated_token::get_associated_token_address, token::spl_token,
+};
+use confidential_bid::{inco::INCO_TOKEN_ID, state::AuctionType};
+use confidential_bid_client::{
+    instructions::{self, CHECK_WINNER_COMPUTE_UNITS, PLACE_BID_COMPUTE_UNITS},
+    pda,
+};
+use inco_lightning::ID as INCO_LIGHTNING_ID;
+use solana_sdk::{signature::Keypair, signer::Signer};
+
+use crate::{add_program, ciphertext, Cost, Harness, InstructionCosts, COST_BIDS};
+
+/// Build of the Inco Lightning mock with the `zero-handle` feature
+pub const ZERO_HANDLE_LIGHTNING: &str = "mock_inco_lightning_zero_handle";
+
+/// Instruction of the first layout: the Anchor discriminator of `name`, then `args`
+fn instruction(name: &str, accounts: Vec<AccountMeta>, args: impl AnchorSerialize) -> Instruction {
+    let mut data = hash(format!("global:{name}").as_bytes()).to_bytes()[..8].to_vec();
+    args.serialize(&mut data).unwrap();
+    Instruction {
+        program_id: confidential_bid::ID,
+        accounts,
+        data,
+    }
+}
+
+fn auction_address(organizer: &Pubkey, auction_id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"auction", organizer.as_ref(), &auction_id.to_le_bytes()],
+        &confidential_bid::ID,
+    )
+    .0
+}
+
+fn bid_address(auction: &Pubkey, bidder: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"bid", auction.as_ref(), bidder.as_ref()],
+        &confidential_bid::ID,
+    )
+    .0
+}
+
+impl Harness {
+    /// Same as [`Harness::new`] with the first deployed program, which has
+    /// no attester, on the [`ZERO_HANDLE_LIGHTNING`] mock
+    pub fn baseline() -> Self {
+        Self::boot(ZERO_HANDLE_LIGHTNING, Keypair::new(), |svm, _| {
+            add_program(svm, confidential_bid::ID, "confidential_bid_baseline")
+        })
+    }
+
+    /// Runs the [`InstructionCosts`] scenario on the first deployed program
+    pub fn measure_baseline_costs(&mut self) -> InstructionCosts {
+        let organizer = self.organizer();
+        let start_time = self.now() + 10;
+        let end_time = start_time + 100;
+        let auction_id = 0u64;
+        let auction = auction_address(&organizer.pubkey(), auction_id);
+        let bid_vault = pda::inco_ata(&auction, &self.bid_mint);
+
+        let create = instruction(
+            "create_auction",
+            vec![
+                AccountMeta::new(organizer.pubkey(), true),
+                AccountMeta::new_readonly(self.prize_mint, false),
+                AccountMeta::new_readonly(self.bid_mint, false),
+                AccountMeta::new(bid_vault, false),
+                AccountMeta::new(
+                    get_associated_token_address(&auction, &self.prize_mint),
+                    false,
+                ),
+                AccountMeta::new(auction, false),
+                AccountMeta::new(
+                    get_associated_token_address(&organizer.pubkey(), &self.prize_mint),
+                    false,
+                ),
+                AccountMeta::new_readonly(system_program::ID, false),
+                AccountMeta::new_readonly(associated_token::ID, false),
+                AccountMeta::new_readonly(spl_token::ID, false),
+                AccountMeta::new_readonly(INCO_TOKEN_ID, false),
+                AccountMeta::new_readonly(INCO_LIGHTNING_ID, false),
+            ],
+            (
+                auction_id,
+                start_time,
+                end_time,
+                0u64,
+                AuctionType::Vickrey,
+                1u64,
+            ),
+        );
+        let create_auction = Cost::of(
+            &self
+                .send(&[create], &[&organizer])
+                .expect("baseline create_auction failed"),
+        );
+
+        let mut bidders = Vec::new();
+        let mut place_bid = Vec::new();
+        for (i, amount) in COST_BIDS.into_iter().enumerate() {
+            let bidder = self.new_bidder(amount);
+            self.warp_to(start_time + i as i64);
+            let place = instruction(
+                "place_bid",
+                vec![
+                    AccountMeta::new(bidder.pubkey(), true),
+                    AccountMeta::new_readonly(organizer.pubkey(), false),
+                    AccountMeta::new(pda::inco_ata(&bidder.pubkey(), &self.bid_mint), false),
+                    AccountMeta::new_readonly(self.bid_mint, false),
+                    AccountMeta::new(bid_vault, false),
+                    AccountMeta::new(bid_address(&auction, &bidder.pubkey()), false),
+                    AccountMeta::new(auction, false),
+                    AccountMeta::new_readonly(system_program::ID, false),
+                    AccountMeta::new_readonly(INCO_TOKEN_ID, false),
+                    AccountMeta::new_readonly(INCO_LIGHTNING_ID, false),
+                ],
+                (ciphertext(amount), 0u8),
+            );
+            let ixs = instructions::with_compute_budget(place, PLACE_BID_COMPUTE_UNITS, None);
+            let meta = self
+                .send(&ixs, &[&bidder])
+                .expect("baseline place_bid failed");
+            place_bid.push(Cost::of(&meta));
+            bidders.push(bidder);
+        }
+
+        self.warp_to(end_time);
+        let close = instruction(
+            "close_auction",
+            vec![
+                AccountMeta::new(organizer.pubkey(), true),
+                AccountMeta::new(auction, false),
+                AccountMeta::new_readonly(system_program::ID, false),
+                AccountMeta::new_readonly(INCO_LIGHTNING_ID, false),
+            ],
+            (),
+        );
+        let close_auction = Cost::of(
+            &self
+                .send(&[close], &[&organizer])
+                .expect("baseline close_auction failed"),
+        );
+
+        let mut check_winner = Vec::new();
+        for bidder in &bidders {
+            let check = instruction(
+                "check_winner",
+                vec![
+                    AccountMeta::new(bidder.pubkey(), true),
+                    AccountMeta::new(bid_address(&auction, &bidder.pubkey()), false),
+                    AccountMeta::new(auction, false),
+                    AccountMeta::new_readonly(INCO_TOKEN_ID, false),
+                    AccountMeta::new_readonly(system_program::ID, false),
+                    AccountMeta::new_readonly(INCO_LIGHTNING_ID, false),
+                ],
+                0u8,
+            );
+            let ixs = instructions::with_compute_budget(check, CHECK_WINNER_COMPUTE_UNITS, None);
+            let meta = self
+                .send(&ixs, &[bidder])
+                .expect("baseline check_winner failed");
+            check_winner.push(Cost::of(&meta));
+        }
+
+        InstructionCosts {
+            create_auction,
+            place_bid,
+            close_auction,
+            check_winner,
+        }
+    }
+}
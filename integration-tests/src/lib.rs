@@ -0,0 +1,1233 @@
+//! In-process SVM harness for confidential-bid.
+//!
+//! Loads the compiled `confidential_bid` program together with the plaintext
+//! Inco mocks at the live Inco program ids, or the program as first deployed
+//! with [`Harness::baseline`]. Their handles are opaque, [`plaintext`]
+//! reads back the value one holds. Run `anchor build` first, the harness reads
+//! the programs from `target/deploy`.
+//!
+//! The clock is only moved by [`Harness::warp_to`], which makes the schedule
+//! checks deterministic.
+//!
+//! `confidential_bid` is deployed behind the upgradeable loader so its upgrade
+//! authority can set the attester, whose attestations the harness signs with
+//! the plaintexts of the handles.
+pub mod baseline;
+pub mod model;
+
+use std::path::PathBuf;
+
+use anchor_lang::{
+    prelude::{Clock, Pubkey},
+    solana_program::{
+        bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+        instruction::Instruction,
+        program_pack::Pack,
+        system_instruction,
+    },
+    AccountDeserialize, InstructionData, ToAccountMetas,
+};
+use anchor_spl::{
+    associated_token::{
+        get_associated_token_address, spl_associated_token_account::instruction as ata_instruction,
+    },
+    token::spl_token,
+};
+use confidential_bid::attestation::attested_message;
+use confidential_bid::{
+    constants::TOKEN_METADATA_PROGRAM_ID,
+    error::AuctionError,
+    inco::INCO_TOKEN_ID,
+    state::{AuctionState, AuctionType, Bid, BidReduction, OrganizerProfile, PayoutRecipient},
+};
+use confidential_bid_client::{
+    decode,
+    instructions::{
+        self, Attestation, CreateAuctionParams, CHECK_WINNER_COMPUTE_UNITS,
+        PLACE_BID_COMPUTE_UNITS, REDUCE_BIDS_COMPUTE_UNITS, RESOLVE_WINNERS_COMPUTE_UNITS,
+        SETTLE_AUCTION_COMPUTE_UNITS,
+    },
+    pda, private_bid, AuctionSummary, CheckWinnerResult, PlaceBidResult,
+};
+use inco_lightning::ID as INCO_LIGHTNING_ID;
+use litesvm::{
+    types::{FailedTransactionMetadata, SimulatedTransactionInfo, TransactionMetadata},
+    LiteSVM,
+};
+use solana_sdk::{
+    account::{Account, ReadableAccount},
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    signer::keypair::keypair_from_seed,
+    transaction::{Transaction, TransactionError},
+};
+
+/// Decimals of the Inco bid mint created by [`Harness::new`]
+pub const BID_MINT_DECIMALS: u8 = 6;
+/// Prize tokens minted to the organizer, one is escrowed per auction
+pub const PRIZE_SUPPLY: u64 = 100;
+/// Unix time the clock starts at
+pub const GENESIS_TIME: i64 = 1_700_000_000;
+
+const AIRDROP_LAMPORTS: u64 = 100_000_000_000;
+/// `Key::MetadataV1` discriminator of a Metaplex Token Metadata account
+const METADATA_V1_KEY: u8 = 4;
+
+pub type TxResult = Result<TransactionMetadata, FailedTransactionMetadata>;
+
+pub struct Harness {
+    pub svm: LiteSVM,
+    pub organizer: Keypair,
+    /// Upgrade authority of `confidential_bid`
+    pub authority: Keypair,
+    /// Key whose signatures the program accepts as decryption attestations
+    pub attester: Keypair,
+    pub prize_mint: Pubkey,
+    pub bid_mint: Pubkey,
+}
+
+/// Options of [`Harness::create_auction_with`], all off by default
+#[derive(Default)]
+struct AuctionFlags {
+    payout_recipients: Vec<PayoutRecipient>,
+    auditor: Option<Pubkey>,
+    hide_bid_count: bool,
+    private_bids: bool,
+    deferred_standings: bool,
+    reveal_on_settle: bool,
+    disclose_bids: bool,
+}
+
+fn deploy_path(program: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../target/deploy")
+        .join(format!("{program}.so"))
+}
+
+fn add_program(svm: &mut LiteSVM, id: Pubkey, program: &str) {
+    let path = deploy_path(program);
+    svm.add_program_from_file(id, &path).unwrap_or_else(|err| {
+        panic!(
+            "failed to load {}: {err}, run `anchor build` first",
+            path.display()
+        )
+    });
+}
+
+/// Deploys `confidential_bid` behind the upgradeable loader, the program data
+/// account first so the program account resolves when it is added
+fn add_upgradeable_program(svm: &mut LiteSVM, authority: &Pubkey) {
+    let path = deploy_path("confidential_bid");
+    let elf = std::fs::read(&path).unwrap_or_else(|err| {
+        panic!(
+            "failed to load {}: {err}, run `anchor build` first",
+            path.display()
+        )
+    });
+    let program_data = pda::program_data();
+
+    let mut data = bincode::serialize(&UpgradeableLoaderState::ProgramData {
+        slot: 0,
+        upgrade_authority_address: Some(*authority),
+    })
+    .unwrap();
+    data.extend_from_slice(&elf);
+    set_loader_account(svm, program_data, data, false);
+
+    let data = bincode::serialize(&UpgradeableLoaderState::Program {
+        programdata_address: program_data,
+    })
+    .unwrap();
+    set_loader_account(svm, confidential_bid::ID, data, true);
+}
+
+fn set_loader_account(svm: &mut LiteSVM, key: Pubkey, data: Vec<u8>, executable: bool) {
+    let account = Account {
+        lamports: svm.minimum_balance_for_rent_exemption(data.len()),
+        data,
+        owner: bpf_loader_upgradeable::ID,
+        executable,
+        rent_epoch: 0,
+    };
+    svm.set_account(key, account)
+        .expect("upgradeable program setup failed");
+}
+
+/// Mock ciphertext of a plaintext amount, as read by the mock Inco programs
+pub fn ciphertext(amount: u64) -> Vec<u8> {
+    amount.to_le_bytes().to_vec()
+}
+
+/// Value held by a handle of the mock Inco programs
+pub fn plaintext(handle: u128) -> u128 {
+    mock_inco_lightning::reveal(handle).expect("handle not issued by the Inco mocks")
+}
+
+/// Whether `result` failed with `expected` from the confidential-bid program
+pub fn is_auction_error(result: &TxResult, expected: AuctionError) -> bool {
+    matches!(
+        result,
+        Err(FailedTransactionMetadata {
+            err: TransactionError::InstructionError(_, InstructionError::Custom(code)),
+            ..
+        }) if *code == u32::from(expected)
+    )
+}
+
+/// Data of `key` as a simulation left it, empty when the simulation did not touch it
+pub fn simulated_data<'a>(info: &'a SimulatedTransactionInfo, key: &Pubkey) -> &'a [u8] {
+    info.post_accounts
+        .iter()
+        .find(|(address, _)| address == key)
+        .map_or(&[], |(_, account)| account.data())
+}
+
+/// Lightning CPIs of the [`COST_BIDS`]: the first bid leads outright and the
+/// second skips the zero second highest. From the third bid on the count is
+/// the one the first deployed program paid for every bid, the standings update
+/// cannot be done in fewer than 2 `e_gt` and 4 `e_select`.
+pub const PLACE_BID_CALLS: [usize; 4] = [2, 6, 8, 8];
+
+/// Amounts bid one second apart on the auction instruction costs are measured on
+pub const COST_BIDS: [u64; 4] = [300, 500, 400, 450];
+
+/// Compute units and Inco Lightning CPIs of one transaction
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cost {
+    pub compute_units: u64,
+    pub lightning_calls: usize,
+}
+
+impl Cost {
+    pub fn of(meta: &TransactionMetadata) -> Self {
+        Self {
+            compute_units: meta.compute_units_consumed,
+            lightning_calls: lightning_calls(meta),
+        }
+    }
+}
+
+/// Cost of each instruction over a Vickrey auction without reserve: its
+/// creation, the [`COST_BIDS`], closing it and checking every bid
+#[derive(Debug)]
+pub struct InstructionCosts {
+    pub create_auction: Cost,
+    pub place_bid: Vec<Cost>,
+    pub close_auction: Cost,
+    pub check_winner: Vec<Cost>,
+}
+
+/// Inco Lightning CPIs a transaction made, counted from its logs
+pub fn lightning_calls(meta: &TransactionMetadata) -> usize {
+    let invoke = format!("Program {INCO_LIGHTNING_ID} invoke [");
+    meta.logs
+        .iter()
+        .filter(|log| log.starts_with(&invoke))
+        .count()
+}
+
+impl Harness {
+    /// Fresh SVM with the programs loaded and the attester set, a funded
+    /// organizer holding [`PRIZE_SUPPLY`] prize tokens and an empty Inco bid mint
+    pub fn new() -> Self {
+        Self::with_lightning("mock_inco_lightning")
+    }
+
+    /// Same as [`Harness::new`], loading the Inco Lightning mock from the
+    /// `lightning` build in `target/deploy`
+    pub fn with_lightning(lightning: &str) -> Self {
+        let authority = Keypair::new();
+        let mut harness = Self::boot(lightning, authority, add_upgradeable_program);
+        let attester = harness.attester.pubkey();
+        harness
+            .set_attester(&harness.authority.insecure_clone(), &attester)
+            .expect("set_attester failed");
+        harness
+    }
+
+    /// SVM with the Inco mocks, the program put in place by `load` and the
+    /// prize and bid mints
+    fn boot(lightning: &str, authority: Keypair, load: impl FnOnce(&mut LiteSVM, &Pubkey)) -> Self {
+        let mut svm = LiteSVM::new();
+        load(&mut svm, &authority.pubkey());
+        add_program(&mut svm, INCO_LIGHTNING_ID, lightning);
+        add_program(&mut svm, INCO_TOKEN_ID, "mock_inco_token");
+
+        let organizer = Keypair::new();
+        svm.airdrop(&organizer.pubkey(), AIRDROP_LAMPORTS).unwrap();
+        svm.airdrop(&authority.pubkey(), AIRDROP_LAMPORTS).unwrap();
+
+        let mut harness = Self {
+            svm,
+            organizer,
+            authority,
+            attester: Keypair::new(),
+            prize_mint: Pubkey::default(),
+            bid_mint: Pubkey::default(),
+        };
+        harness.warp_to(GENESIS_TIME);
+        harness.prize_mint = harness.create_prize_mint();
+        harness.bid_mint = harness.create_bid_mint();
+        harness
+    }
+
+    pub fn now(&self) -> i64 {
+        self.svm.get_sysvar::<Clock>().unix_timestamp
+    }
+
+    /// Moves the clock to `unix_timestamp` and advances the slot, so bids
+    /// placed after a warp get distinct timestamps
+    pub fn warp_to(&mut self, unix_timestamp: i64) {
+        let mut clock = self.svm.get_sysvar::<Clock>();
+        clock.unix_timestamp = unix_timestamp;
+        clock.slot += 1;
+        self.svm.set_sysvar(&clock);
+    }
+
+    /// Signs with the fee payer `signers[0]` and the rest. The blockhash is
+    /// expired first so resending an identical instruction is not deduplicated.
+    pub fn send(&mut self, ixs: &[Instruction], signers: &[&Keypair]) -> TxResult {
+        self.svm.expire_blockhash();
+        let tx = Transaction::new_signed_with_payer(
+            ixs,
+            Some(&signers[0].pubkey()),
+            signers,
+            self.svm.latest_blockhash(),
+        );
+        self.svm.send_transaction(tx)
+    }
+
+    /// Runs `ixs` without committing, for the return data and accounts of a first pass
+    pub fn simulate(
+        &mut self,
+        ixs: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<SimulatedTransactionInfo, FailedTransactionMetadata> {
+        let tx = Transaction::new_signed_with_payer(
+            ixs,
+            Some(&signers[0].pubkey()),
+            signers,
+            self.svm.latest_blockhash(),
+        );
+        self.svm.simulate_transaction(tx)
+    }
+
+    fn organizer(&self) -> Keypair {
+        self.organizer.insecure_clone()
+    }
+
+    fn create_prize_mint(&mut self) -> Pubkey {
+        let organizer = self.organizer();
+        let mint = Keypair::new();
+        let rent = self
+            .svm
+            .minimum_balance_for_rent_exemption(spl_token::state::Mint::LEN);
+        let organizer_ata = get_associated_token_address(&organizer.pubkey(), &mint.pubkey());
+        self.send(
+            &[
+                system_instruction::create_account(
+                    &organizer.pubkey(),
+                    &mint.pubkey(),
+                    rent,
+                    spl_token::state::Mint::LEN as u64,
+                    &spl_token::ID,
+                ),
+                spl_token::instruction::initialize_mint2(
+                    &spl_token::ID,
+                    &mint.pubkey(),
+                    &organizer.pubkey(),
+                    None,
+                    0,
+                )
+                .unwrap(),
+                ata_instruction::create_associated_token_account(
+                    &organizer.pubkey(),
+                    &organizer.pubkey(),
+                    &mint.pubkey(),
+                    &spl_token::ID,
+                ),
+                spl_token::instruction::mint_to(
+                    &spl_token::ID,
+                    &mint.pubkey(),
+                    &organizer_ata,
+                    &organizer.pubkey(),
+                    &[],
+                    PRIZE_SUPPLY,
+                )
+                .unwrap(),
+            ],
+            &[&organizer, &mint],
+        )
+        .expect("prize mint setup failed");
+        mint.pubkey()
+    }
+
+    fn create_bid_mint(&mut self) -> Pubkey {
+        let organizer = self.organizer();
+        let mint = Keypair::new();
+        let ix = Instruction {
+            program_id: INCO_TOKEN_ID,
+            accounts: mock_inco_token::accounts::InitializeMint {
+                mint: mint.pubkey(),
+                payer: organizer.pubkey(),
+                system_program: anchor_lang::system_program::ID,
+                inco_lightning_program: INCO_LIGHTNING_ID,
+            }
+            .to_account_metas(None),
+            data: mock_inco_token::instruction::InitializeMint {
+                decimals: BID_MINT_DECIMALS,
+                mint_authority: organizer.pubkey(),
+                freeze_authority: None,
+            }
+            .data(),
+        };
+        self.send(&[ix], &[&organizer, &mint])
+            .expect("bid mint setup failed");
+        mint.pubkey()
+    }
+
+    /// Writes Metaplex metadata for the prize mint with `creators` given as
+    /// `(address, verified, share)`, as the Token Metadata program would store it
+    pub fn set_prize_metadata(
+        &mut self,
+        seller_fee_basis_points: u16,
+        creators: &[(Pubkey, bool, u8)],
+    ) {
+        let mut data = vec![METADATA_V1_KEY];
+        data.extend_from_slice(self.organizer.pubkey().as_ref()); // update authority
+        data.extend_from_slice(self.prize_mint.as_ref());
+        for field in ["Prize", "PRZ", "https://example.com/prize.json"] {
+            data.extend_from_slice(&(field.len() as u32).to_le_bytes());
+            data.extend_from_slice(field.as_bytes());
+        }
+        data.extend_from_slice(&seller_fee_basis_points.to_le_bytes());
+        data.push(1);
+        data.extend_from_slice(&(creators.len() as u32).to_le_bytes());
+        for (address, verified, share) in creators {
+            data.extend_from_slice(address.as_ref());
+            data.extend_from_slice(&[u8::from(*verified), *share]);
+        }
+        data.extend_from_slice(&[0, 1]); // primary_sale_happened, is_mutable
+
+        let account = Account {
+            lamports: self.svm.minimum_balance_for_rent_exemption(data.len()),
+            data,
+            owner: TOKEN_METADATA_PROGRAM_ID,
+            executable: false,
+            rent_epoch: 0,
+        };
+        self.svm
+            .set_account(pda::prize_metadata(&self.prize_mint), account)
+            .expect("prize metadata setup failed");
+    }
+
+    /// Creates the Inco token account of `wallet` for the bid mint, paid by `payer`
+    fn create_inco_ata(&self, payer: &Pubkey, wallet: &Pubkey) -> Instruction {
+        Instruction {
+            program_id: INCO_TOKEN_ID,
+            accounts: mock_inco_token::accounts::CreateIdempotent {
+                payer: *payer,
+                associated_token: pda::inco_ata(wallet, &self.bid_mint),
+                wallet: *wallet,
+                mint: self.bid_mint,
+                system_program: anchor_lang::system_program::ID,
+                inco_lightning_program: INCO_LIGHTNING_ID,
+            }
+            .to_account_metas(None),
+            data: mock_inco_token::instruction::CreateIdempotent {}.data(),
+        }
+    }
+
+    /// Funded bidder whose Inco token account holds `balance` bid tokens
+    pub fn new_bidder(&mut self, balance: u64) -> Keypair {
+        let bidder = Keypair::new();
+        self.svm
+            .airdrop(&bidder.pubkey(), AIRDROP_LAMPORTS)
+            .unwrap();
+        self.mint_bid_tokens(&bidder.pubkey(), balance);
+        bidder
+    }
+
+    /// One-off key bidding for `wallet` on a private auction and its bid
+    /// secret, derived as clients do. The key holds `balance` bid tokens
+    /// minted by the organizer and no lamports.
+    pub fn new_bid_key(
+        &mut self,
+        wallet: &Keypair,
+        auction: &Pubkey,
+        balance: u64,
+    ) -> (Keypair, [u8; 32]) {
+        let signature = wallet.sign_message(&private_bid::bid_key_message(auction));
+        let bid = private_bid::private_bid(signature.as_ref().try_into().unwrap());
+        let bid_key = keypair_from_seed(&bid.key_seed).unwrap();
+        self.mint_bid_tokens(&bid_key.pubkey(), balance);
+        (bid_key, bid.bid_secret)
+    }
+
+    /// Creates `owner`'s Inco token account and mints it `balance`, paid by the organizer
+    fn mint_bid_tokens(&mut self, owner: &Pubkey, balance: u64) {
+        let organizer = self.organizer();
+        let ata = pda::inco_ata(owner, &self.bid_mint);
+
+        let create = self.create_inco_ata(&organizer.pubkey(), owner);
+        let mint_to = Instruction {
+            program_id: INCO_TOKEN_ID,
+            accounts: mock_inco_token::accounts::MintTo {
+                mint: self.bid_mint,
+                account: ata,
+                mint_authority: organizer.pubkey(),
+                inco_lightning_program: INCO_LIGHTNING_ID,
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: mock_inco_token::instruction::MintTo {
+                ciphertext: ciphertext(balance),
+                input_type: 0,
+            }
+            .data(),
+        };
+        self.send(&[create, mint_to], &[&organizer])
+            .expect("bidder setup failed");
+    }
+
+    /// Creates an auction escrowing one prize token, without auditor or privacy options
+    pub fn create_auction(
+        &mut self,
+        auction_type: AuctionType,
+        start_time: i64,
+        end_time: i64,
+        reserve_price: u64,
+    ) -> Pubkey {
+        self.create_auction_with(
+            auction_type,
+            start_time,
+            end_time,
+            reserve_price,
+            AuctionFlags::default(),
+        )
+        .0
+    }
+
+    /// Same as [`Harness::create_auction`], sharing the proceeds with
+    /// `payout_recipients` given as `(recipient, share_bps)`
+    pub fn create_shared_auction(
+        &mut self,
+        auction_type: AuctionType,
+        start_time: i64,
+        end_time: i64,
+        reserve_price: u64,
+        payout_recipients: &[(Pubkey, u16)],
+    ) -> Pubkey {
+        self.create_auction_with(
+            auction_type,
+            start_time,
+            end_time,
+            reserve_price,
+            AuctionFlags {
+                payout_recipients: payout_recipients
+                    .iter()
+                    .map(|&(recipient, share_bps)| PayoutRecipient {
+                        recipient,
+                        share_bps,
+                    })
+                    .collect(),
+                ..AuctionFlags::default()
+            },
+        )
+        .0
+    }
+
+    /// Same as [`Harness::create_auction`], leaving the standings to `reduce_bids`
+    pub fn create_deferred_auction(
+        &mut self,
+        auction_type: AuctionType,
+        start_time: i64,
+        end_time: i64,
+        reserve_price: u64,
+    ) -> Pubkey {
+        self.create_auction_with(
+            auction_type,
+            start_time,
+            end_time,
+            reserve_price,
+            AuctionFlags {
+                deferred_standings: true,
+                ..AuctionFlags::default()
+            },
+        )
+        .0
+    }
+
+    /// Same as [`Harness::create_auction`], with the result revealed once settled
+    pub fn create_revealing_auction(
+        &mut self,
+        auction_type: AuctionType,
+        start_time: i64,
+        end_time: i64,
+        reserve_price: u64,
+    ) -> Pubkey {
+        self.create_auction_with(
+            auction_type,
+            start_time,
+            end_time,
+            reserve_price,
+            AuctionFlags {
+                reveal_on_settle: true,
+                ..AuctionFlags::default()
+            },
+        )
+        .0
+    }
+
+    /// Same as [`Harness::create_auction`], with `disclose_bids` set
+    pub fn create_disclosed_auction(
+        &mut self,
+        auction_type: AuctionType,
+        start_time: i64,
+        end_time: i64,
+        reserve_price: u64,
+    ) -> Pubkey {
+        self.create_auction_with(
+            auction_type,
+            start_time,
+            end_time,
+            reserve_price,
+            AuctionFlags {
+                disclose_bids: true,
+                ..AuctionFlags::default()
+            },
+        )
+        .0
+    }
+
+    /// Same as [`Harness::create_auction`], with `auditor` granted every handle
+    pub fn create_audited_auction(
+        &mut self,
+        auction_type: AuctionType,
+        start_time: i64,
+        end_time: i64,
+        reserve_price: u64,
+        auditor: &Pubkey,
+    ) -> Pubkey {
+        self.create_auction_with(
+            auction_type,
+            start_time,
+            end_time,
+            reserve_price,
+            AuctionFlags {
+                auditor: Some(*auditor),
+                ..AuctionFlags::default()
+            },
+        )
+        .0
+    }
+
+    /// Same as [`Harness::create_auction`], counting bids encrypted
+    pub fn create_hidden_count_auction(
+        &mut self,
+        auction_type: AuctionType,
+        start_time: i64,
+        end_time: i64,
+        reserve_price: u64,
+    ) -> Pubkey {
+        self.create_auction_with(
+            auction_type,
+            start_time,
+            end_time,
+            reserve_price,
+            AuctionFlags {
+                hide_bid_count: true,
+                ..AuctionFlags::default()
+            },
+        )
+        .0
+    }
+
+    /// Same as [`Harness::create_auction`], with bids keyed by commitments
+    pub fn create_private_auction(
+        &mut self,
+        auction_type: AuctionType,
+        start_time: i64,
+        end_time: i64,
+        reserve_price: u64,
+    ) -> Pubkey {
+        self.create_auction_with(
+            auction_type,
+            start_time,
+            end_time,
+            reserve_price,
+            AuctionFlags {
+                private_bids: true,
+                ..AuctionFlags::default()
+            },
+        )
+        .0
+    }
+
+    fn create_auction_with(
+        &mut self,
+        auction_type: AuctionType,
+        start_time: i64,
+        end_time: i64,
+        reserve_price: u64,
+        flags: AuctionFlags,
+    ) -> (Pubkey, TransactionMetadata) {
+        let organizer = self.organizer();
+        let auction_id = self
+            .try_account::<OrganizerProfile>(&pda::organizer_profile(&organizer.pubkey()))
+            .map_or(0, |profile| profile.auction_count);
+        let ix = instructions::create_auction(
+            &organizer.pubkey(),
+            auction_id,
+            &self.prize_mint,
+            &spl_token::ID,
+            &self.bid_mint,
+            CreateAuctionParams {
+                start_time,
+                end_time,
+                reserve_price,
+                auction_type,
+                token_amount: 1,
+                payout_recipients: flags.payout_recipients,
+                auditor: flags.auditor,
+                hide_bid_count: flags.hide_bid_count,
+                private_bids: flags.private_bids,
+                deferred_standings: flags.deferred_standings,
+                reveal_on_settle: flags.reveal_on_settle,
+                disclose_bids: flags.disclose_bids,
+            },
+        );
+        let meta = self
+            .send(&[ix], &[&organizer])
+            .expect("create_auction failed");
+        (pda::auction(&organizer.pubkey(), auction_id), meta)
+    }
+
+    /// Runs the [`InstructionCosts`] scenario on the current program
+    pub fn measure_costs(&mut self) -> InstructionCosts {
+        let start_time = self.now() + 10;
+        let end_time = start_time + 100;
+        let (auction, created) = self.create_auction_with(
+            AuctionType::Vickrey,
+            start_time,
+            end_time,
+            0,
+            AuctionFlags::default(),
+        );
+
+        let mut bidders = Vec::new();
+        let mut place_bid = Vec::new();
+        for (i, amount) in COST_BIDS.into_iter().enumerate() {
+            let bidder = self.new_bidder(amount);
+            self.warp_to(start_time + i as i64);
+            let meta = self
+                .place_bid(&bidder, &auction, amount)
+                .expect("place_bid failed");
+            place_bid.push(Cost::of(&meta));
+            bidders.push(bidder);
+        }
+
+        self.warp_to(end_time);
+        let close_auction = Cost::of(&self.close_auction(&auction).expect("close_auction failed"));
+        let check_winner = bidders
+            .iter()
+            .map(|bidder| {
+                Cost::of(
+                    &self
+                        .check_winner(bidder, &auction)
+                        .expect("check_winner failed"),
+                )
+            })
+            .collect();
+
+        InstructionCosts {
+            create_auction: Cost::of(&created),
+            place_bid,
+            close_auction,
+            check_winner,
+        }
+    }
+
+    pub fn place_bid(&mut self, bidder: &Keypair, auction: &Pubkey, amount: u64) -> TxResult {
+        let state = self.auction(auction);
+        let ixs = instructions::with_compute_budget(
+            instructions::place_bid(
+                &bidder.pubkey(),
+                &bidder.pubkey(),
+                auction,
+                &state,
+                ciphertext(amount),
+                0,
+                None,
+                None,
+            ),
+            PLACE_BID_COMPUTE_UNITS,
+            None,
+        );
+        self.send(&ixs, &[bidder])
+    }
+
+    /// Places a private bid from `bid_key`, `relayer` paying every fee and account
+    pub fn place_private_bid(
+        &mut self,
+        relayer: &Keypair,
+        bid_key: &Keypair,
+        bid_secret: &[u8; 32],
+        auction: &Pubkey,
+        amount: u64,
+    ) -> TxResult {
+        let state = self.auction(auction);
+        let ixs = instructions::with_compute_budget(
+            instructions::place_bid(
+                &relayer.pubkey(),
+                &bid_key.pubkey(),
+                auction,
+                &state,
+                ciphertext(amount),
+                0,
+                Some(bid_secret),
+                None,
+            ),
+            PLACE_BID_COMPUTE_UNITS,
+            None,
+        );
+        self.send(&ixs, &[relayer, bid_key])
+    }
+
+    /// Places a bid the way clients do: a simulation learns the handles, then
+    /// the bid is sent granting the bidder its new balance and bid amount
+    pub fn place_granted_bid(
+        &mut self,
+        bidder: &Keypair,
+        auction: &Pubkey,
+        amount: u64,
+    ) -> TxResult {
+        let (ixs, _) = self.granted_bid_instructions(bidder, auction, amount)?;
+        self.send(&ixs, &[bidder])
+    }
+
+    /// Instructions of [`Harness::place_granted_bid`] with the handles they
+    /// grant, from a simulation at the current state
+    pub fn granted_bid_instructions(
+        &mut self,
+        bidder: &Keypair,
+        auction: &Pubkey,
+        amount: u64,
+    ) -> Result<(Vec<Instruction>, PlaceBidResult), FailedTransactionMetadata> {
+        let state = self.auction(auction);
+        let place = |handles: Option<(&PlaceBidResult, u128)>| {
+            instructions::with_compute_budget(
+                instructions::place_bid(
+                    &bidder.pubkey(),
+                    &bidder.pubkey(),
+                    auction,
+                    &state,
+                    ciphertext(amount),
+                    0,
+                    None,
+                    handles,
+                ),
+                PLACE_BID_COMPUTE_UNITS,
+                None,
+            )
+        };
+        let simulated = self.simulate(&place(None), &[bidder])?;
+        let result: PlaceBidResult = decode::return_data(&simulated.meta.return_data.data)
+            .expect("missing place_bid result");
+        let ata = pda::inco_ata(&bidder.pubkey(), &state.bid_token_mint);
+        let balance =
+            decode::inco_balance(simulated_data(&simulated, &ata)).expect("missing bidder balance");
+        Ok((place(Some((&result, balance))), result))
+    }
+
+    pub fn close_auction(&mut self, auction: &Pubkey) -> TxResult {
+        let organizer = self.organizer();
+        let ix = instructions::close_auction(&organizer.pubkey(), auction);
+        self.send(&[ix], &[&organizer])
+    }
+
+    /// Discloses the bids of `bidders`, which must continue the bid order, paid by `payer`
+    pub fn disclose_bids(
+        &mut self,
+        payer: &Keypair,
+        auction: &Pubkey,
+        bidders: &[Keypair],
+    ) -> TxResult {
+        let bids: Vec<(Pubkey, u128)> = bidders
+            .iter()
+            .map(|bidder| {
+                (
+                    pda::bid(auction, &bidder.pubkey(), None),
+                    self.bid(auction, &bidder.pubkey()).bid_amount,
+                )
+            })
+            .collect();
+        let ix = instructions::disclose_bids(&payer.pubkey(), auction, &bids);
+        self.send(&[ix], &[payer])
+    }
+
+    /// Reduces the bids of `bidders`, which must be in bid order, paid by the organizer
+    pub fn reduce_bids(&mut self, auction: &Pubkey, start: u32, bidders: &[Keypair]) -> TxResult {
+        let organizer = self.organizer();
+        let bids: Vec<Pubkey> = bidders
+            .iter()
+            .map(|bidder| pda::bid(auction, &bidder.pubkey(), None))
+            .collect();
+        let ixs = instructions::with_compute_budget(
+            instructions::reduce_bids(&organizer.pubkey(), auction, start, &bids),
+            REDUCE_BIDS_COMPUTE_UNITS,
+            None,
+        );
+        self.send(&ixs, &[&organizer])
+    }
+
+    pub fn merge_reductions(&mut self, auction: &Pubkey, earlier: u32, later: u32) -> TxResult {
+        let organizer = self.organizer();
+        let earlier = self.reduction(auction, earlier);
+        let later = self.reduction(auction, later);
+        let ix = instructions::merge_reductions(&organizer.pubkey(), auction, &earlier, &later);
+        self.send(&[ix], &[&organizer])
+    }
+
+    pub fn finalize_reduction(&mut self, auction: &Pubkey) -> TxResult {
+        let organizer = self.organizer();
+        let reduction = self.reduction(auction, 0);
+        let ix = instructions::finalize_reduction(&organizer.pubkey(), auction, &reduction);
+        self.send(&[ix], &[&organizer])
+    }
+
+    pub fn check_winner(&mut self, bidder: &Keypair, auction: &Pubkey) -> TxResult {
+        let ixs = instructions::with_compute_budget(
+            instructions::check_winner(&bidder.pubkey(), &bidder.pubkey(), auction, 0, None, None),
+            CHECK_WINNER_COMPUTE_UNITS,
+            None,
+        );
+        self.send(&ixs, &[bidder])
+    }
+
+    /// Checks `bidder`'s bid and grants it the winner handle, learnt from the
+    /// result of a simulation
+    pub fn check_granted_winner(&mut self, bidder: &Keypair, auction: &Pubkey) -> TxResult {
+        let check = |handle: Option<u128>| {
+            instructions::with_compute_budget(
+                instructions::check_winner(
+                    &bidder.pubkey(),
+                    &bidder.pubkey(),
+                    auction,
+                    0,
+                    None,
+                    handle,
+                ),
+                CHECK_WINNER_COMPUTE_UNITS,
+                None,
+            )
+        };
+        let simulated = self.simulate(&check(None), &[bidder])?;
+        let result: CheckWinnerResult = decode::return_data(&simulated.meta.return_data.data)
+            .expect("missing check_winner result");
+        self.send(&check(Some(result.is_winner_handle)), &[bidder])
+    }
+
+    /// Resolves the bids of `bidders` as the organizer, granting each bidder its winner handle
+    pub fn resolve_winners(&mut self, auction: &Pubkey, bidders: &[Keypair]) -> TxResult {
+        let bids: Vec<(Pubkey, Pubkey)> = bidders
+            .iter()
+            .map(|bidder| (pda::bid(auction, &bidder.pubkey(), None), bidder.pubkey()))
+            .collect();
+        self.resolve_bids(auction, &bids)
+    }
+
+    /// Resolves private bids as the organizer, their bidders staying unnamed
+    pub fn resolve_private_bids(&mut self, auction: &Pubkey, bids: &[Pubkey]) -> TxResult {
+        let bids: Vec<(Pubkey, Pubkey)> =
+            bids.iter().map(|bid| (*bid, Pubkey::default())).collect();
+        self.resolve_bids(auction, &bids)
+    }
+
+    fn resolve_bids(&mut self, auction: &Pubkey, bids: &[(Pubkey, Pubkey)]) -> TxResult {
+        let organizer = self.organizer();
+        let state = self.auction(auction);
+        let resolve = |handles: Option<&[u128]>| {
+            instructions::with_compute_budget(
+                instructions::resolve_winners(&organizer.pubkey(), auction, &state, bids, handles),
+                RESOLVE_WINNERS_COMPUTE_UNITS,
+                None,
+            )
+        };
+
+        let simulated = self.simulate(&resolve(None), &[&organizer])?;
+        let is_winner_handles: Vec<u128> = bids
+            .iter()
+            .map(|(bid, _)| {
+                decode::bid(simulated_data(&simulated, bid))
+                    .expect("missing simulated bid")
+                    .is_winner_handle
+            })
+            .collect();
+        self.send(&resolve(Some(&is_winner_handles)), &[&organizer])
+    }
+
+    /// Grants the auditor the handles of `bidder`'s bid, or of the auction
+    /// without one, paid by the organizer
+    pub fn grant_auditor(&mut self, auction: &Pubkey, bidder: Option<&Pubkey>) -> TxResult {
+        let organizer = self.organizer();
+        let state = self.auction(auction);
+        let bid = bidder.map(|bidder| (pda::bid(auction, bidder, None), self.bid(auction, bidder)));
+        let ix = instructions::grant_auditor(
+            &organizer.pubkey(),
+            auction,
+            &state,
+            bid.as_ref().map(|(key, bid)| (key, bid)),
+        );
+        self.send(&[ix], &[&organizer])
+    }
+
+    /// Sets `allowed`'s access to a handle of `bidder`'s bid, as the bidder
+    pub fn grant_access(
+        &mut self,
+        bidder: &Keypair,
+        auction: &Pubkey,
+        handle: u128,
+        allowed: &Pubkey,
+        value: bool,
+    ) -> TxResult {
+        let bid = pda::bid(auction, &bidder.pubkey(), None);
+        let ix = instructions::grant_access(
+            &bidder.pubkey(),
+            &bidder.pubkey(),
+            auction,
+            Some(&bid),
+            handle,
+            allowed,
+            value,
+            None,
+        );
+        self.send(&[ix], &[bidder])
+    }
+
+    pub fn set_attester(&mut self, authority: &Keypair, attester: &Pubkey) -> TxResult {
+        let ix = instructions::set_attester(&authority.pubkey(), attester);
+        self.send(&[ix], &[authority])
+    }
+
+    /// Attestation of `handle` decrypting to `plaintext`, signed by `signer`
+    pub fn attestation_by(&self, signer: &Keypair, handle: u128, plaintext: u128) -> Instruction {
+        let signature = signer.sign_message(&attested_message(handle, plaintext));
+        instructions::attestation(
+            &signer.pubkey(),
+            signature.as_ref().try_into().unwrap(),
+            handle,
+            plaintext,
+        )
+    }
+
+    /// Truthful attestation of `handle` from the attester
+    pub fn attestation(&self, handle: u128) -> Instruction {
+        self.attestation_by(&self.attester, handle, plaintext(handle))
+    }
+
+    /// Truthful attestations of several handles from the attester, in one instruction
+    pub fn attestations(&self, handles: &[u128]) -> Instruction {
+        let attestations: Vec<Attestation> = handles
+            .iter()
+            .map(|&handle| Attestation {
+                signature: self
+                    .attester
+                    .sign_message(&attested_message(handle, plaintext(handle)))
+                    .as_ref()
+                    .try_into()
+                    .unwrap(),
+                handle,
+                plaintext: plaintext(handle),
+            })
+            .collect();
+        instructions::attestations(&self.attester.pubkey(), &attestations)
+    }
+
+    /// Claims the prize of `bidder`'s bid behind an attestation of its winner handle
+    pub fn claim_prize(&mut self, bidder: &Keypair, auction: &Pubkey) -> TxResult {
+        let state = self.auction(auction);
+        let handle = self.bid(auction, &bidder.pubkey()).is_winner_handle;
+        let ixs = [
+            self.attestation(handle),
+            instructions::claim_prize(
+                &bidder.pubkey(),
+                &bidder.pubkey(),
+                auction,
+                &state,
+                &spl_token::ID,
+                None,
+            ),
+        ];
+        self.send(&ixs, &[bidder])
+    }
+
+    /// Refunds `bidder`'s bid behind an attestation of its winner handle
+    pub fn refund_bid(&mut self, bidder: &Keypair, auction: &Pubkey) -> TxResult {
+        let state = self.auction(auction);
+        let handle = self.bid(auction, &bidder.pubkey()).is_winner_handle;
+        let ixs = [
+            self.attestation(handle),
+            instructions::refund_bid(&bidder.pubkey(), auction, &state, None),
+        ];
+        self.send(&ixs, &[bidder])
+    }
+
+    /// Claims the prize of a private bid with its secret, paid by `relayer`
+    pub fn claim_private_prize(
+        &mut self,
+        relayer: &Keypair,
+        bid_key: &Keypair,
+        bid_secret: &[u8; 32],
+        auction: &Pubkey,
+    ) -> TxResult {
+        let state = self.auction(auction);
+        let bid = pda::bid(auction, &bid_key.pubkey(), Some(bid_secret));
+        let handle = self.bid_at(&bid).is_winner_handle;
+        let ixs = [
+            self.attestation(handle),
+            instructions::claim_prize(
+                &relayer.pubkey(),
+                &bid_key.pubkey(),
+                auction,
+                &state,
+                &spl_token::ID,
+                Some(bid_secret),
+            ),
+        ];
+        self.send(&ixs, &[relayer, bid_key])
+    }
+
+    /// Refunds a private bid with its secret, `relayer` paying the fee
+    pub fn refund_private_bid(
+        &mut self,
+        relayer: &Keypair,
+        bid_key: &Keypair,
+        bid_secret: &[u8; 32],
+        auction: &Pubkey,
+    ) -> TxResult {
+        let state = self.auction(auction);
+        let bid = pda::bid(auction, &bid_key.pubkey(), Some(bid_secret));
+        let handle = self.bid_at(&bid).is_winner_handle;
+        let ixs = [
+            self.attestation(handle),
+            instructions::refund_bid(&bid_key.pubkey(), auction, &state, Some(bid_secret)),
+        ];
+        self.send(&ixs, &[relayer, bid_key])
+    }
+
+    /// Cancels as the organizer behind an attestation of the reserve handle
+    pub fn cancel_auction(&mut self, auction: &Pubkey) -> TxResult {
+        let organizer = self.organizer();
+        let state = self.auction(auction);
+        let ixs = [
+            self.attestation(state.reserve_met_handle),
+            instructions::cancel_auction(&organizer.pubkey(), auction, &state, &spl_token::ID),
+        ];
+        self.send(&ixs, &[&organizer])
+    }
+
+    /// Prize tokens held by `owner`'s associated token account, 0 without one
+    pub fn prize_balance(&self, owner: &Pubkey) -> u64 {
+        self.svm
+            .get_account(&get_associated_token_address(owner, &self.prize_mint))
+            .map_or(0, |account| {
+                spl_token::state::Account::unpack(&account.data)
+                    .unwrap()
+                    .amount
+            })
+    }
+
+    /// Plaintext balance of `owner`'s Inco token account
+    pub fn bid_balance(&self, owner: &Pubkey) -> u128 {
+        let account = self
+            .try_account::<mock_inco_token::IncoAccount>(&pda::inco_ata(owner, &self.bid_mint))
+            .expect("missing Inco token account");
+        plaintext(account.amount.0)
+    }
+
+    /// Settles as the organizer without royalties, creating the organizer's Inco token account
+    pub fn settle_auction(&mut self, auction: &Pubkey) -> TxResult {
+        self.settle_auction_paying(auction, &[])
+    }
+
+    /// Same as [`Harness::settle_auction`], paying royalties to `creators`
+    /// given in metadata order
+    pub fn settle_auction_paying(&mut self, auction: &Pubkey, creators: &[Pubkey]) -> TxResult {
+        let organizer = self.organizer();
+        let state = self.auction(auction);
+        let mut ixs = vec![self.create_inco_ata(&organizer.pubkey(), &organizer.pubkey())];
+        ixs.extend(instructions::with_compute_budget(
+            instructions::settle_auction(&organizer.pubkey(), auction, &state, creators),
+            SETTLE_AUCTION_COMPUTE_UNITS,
+            None,
+        ));
+        self.send(&ixs, &[&organizer])
+    }
+
+    /// Reveals the result with `winner`'s bid behind attestations of its
+    /// winner handle and the clearing price handle
+    pub fn reveal_result(&mut self, auction: &Pubkey, winner: &Keypair) -> TxResult {
+        let organizer = self.organizer();
+        let state = self.auction(auction);
+        let winning_bid = pda::bid(auction, &winner.pubkey(), None);
+        let handles = [
+            self.bid(auction, &winner.pubkey()).is_winner_handle,
+            state.clearing_price_handle,
+        ];
+        let ixs = [
+            self.attestations(&handles),
+            instructions::reveal_result(&organizer.pubkey(), auction, &winning_bid),
+        ];
+        self.send(&ixs, &[&organizer])
+    }
+
+    /// Records the amount of `bidder`'s bid behind an attestation of it
+    pub fn reveal_bid(&mut self, auction: &Pubkey, bidder: &Keypair) -> TxResult {
+        let organizer = self.organizer();
+        let bid = pda::bid(auction, &bidder.pubkey(), None);
+        let ixs = [
+            self.attestation(self.bid(auction, &bidder.pubkey()).bid_amount),
+            instructions::reveal_bid(&organizer.pubkey(), auction, &bid),
+        ];
+        self.send(&ixs, &[&organizer])
+    }
+
+    /// `get_auction_summary` of `auction` for `caller`, simulated by the organizer
+    pub fn auction_summary(&mut self, caller: &Pubkey, auction: &Pubkey) -> AuctionSummary {
+        let organizer = self.organizer();
+        let ix = instructions::get_auction_summary(caller, auction, None);
+        let simulated = self
+            .simulate(&[ix], &[&organizer])
+            .expect("get_auction_summary failed");
+        decode::return_data(&simulated.meta.return_data.data).expect("missing auction summary")
+    }
+
+    pub fn try_account<T: AccountDeserialize>(&self, key: &Pubkey) -> Option<T> {
+        let account = self.svm.get_account(key)?;
+        T::try_deserialize(&mut account.data.as_slice()).ok()
+    }
+
+    pub fn auction(&self, key: &Pubkey) -> AuctionState {
+        self.try_account(key).expect("missing auction account")
+    }
+
+    pub fn reduction(&self, auction: &Pubkey, start: u32) -> BidReduction {
+        self.try_account(&pda::reduction(auction, start))
+            .expect("missing reduction account")
+    }
+
+    pub fn bid(&self, auction: &Pubkey, bidder: &Pubkey) -> Bid {
+        self.bid_at(&pda::bid(auction, bidder, None))
+    }
+
+    pub fn bid_at(&self, key: &Pubkey) -> Bid {
+        self.try_account(key).expect("missing bid account")
+    }
+}
+
+impl Default for Harness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,70 @@
+//! Plaintext reference auction the program is checked against.
+//!
+//! Written from the auction rules rather than from the handlers: the highest
+//! bid wins, ties go to the earliest placed bid, nobody wins when the highest bid is
+//! below the reserve, and the second highest is the runner-up amount, which
+//! equals the highest on a tie.
+
+#[derive(Clone, Copy, Debug)]
+pub struct PlacedBid {
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ReferenceAuction {
+    pub reserve_price: u64,
+    pub bids: Vec<PlacedBid>,
+}
+
+impl ReferenceAuction {
+    pub fn new(reserve_price: u64) -> Self {
+        Self {
+            reserve_price,
+            bids: Vec::new(),
+        }
+    }
+
+    pub fn place(&mut self, amount: u64, timestamp: i64) {
+        self.bids.push(PlacedBid { amount, timestamp });
+    }
+
+    /// 0 before any bid
+    pub fn highest(&self) -> u64 {
+        self.bids.iter().map(|bid| bid.amount).max().unwrap_or(0)
+    }
+
+    /// `None` until a second bid
+    pub fn second_highest(&self) -> Option<u64> {
+        let mut amounts: Vec<u64> = self.bids.iter().map(|bid| bid.amount).collect();
+        amounts.sort_unstable_by(|a, b| b.cmp(a));
+        amounts.get(1).copied()
+    }
+
+    /// Index of the first bid placed at the highest amount
+    fn leader(&self) -> Option<usize> {
+        let highest = self.highest();
+        self.bids.iter().position(|bid| bid.amount == highest)
+    }
+
+    /// Placement key of the leading bid, its timestamp above its index, 0
+    /// before any bid
+    pub fn highest_timestamp(&self) -> u128 {
+        self.leader()
+            .map_or(0, |i| ((self.bids[i].timestamp as u128) << 32) | i as u128)
+    }
+
+    /// Never met without a bid
+    pub fn reserve_met(&self) -> bool {
+        !self.bids.is_empty() && self.highest() >= self.reserve_price
+    }
+
+    /// Index of the winning bid, `None` when the reserve is not met
+    pub fn winner(&self) -> Option<usize> {
+        self.leader().filter(|_| self.reserve_met())
+    }
+
+    pub fn is_winner(&self, index: usize) -> bool {
+        self.winner() == Some(index)
+    }
+}
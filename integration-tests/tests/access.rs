@@ -0,0 +1,148 @@
+//! Decryption allowances granted to bidders and auditors.
+use confidential_bid::{error::AuctionError, state::AuctionType};
+use confidential_bid_client::{decode, pda, CheckWinnerResult};
+use confidential_bid_integration_tests::{is_auction_error, plaintext, Harness};
+use solana_sdk::{instruction::AccountMeta, pubkey::Pubkey, signer::Signer};
+
+#[test]
+fn granted_bid_lets_the_bidder_decrypt_its_amount() {
+    let mut harness = Harness::new();
+    let start_time = harness.now() + 10;
+    let auction = harness.create_auction(AuctionType::Normal, start_time, start_time + 100, 0);
+    let bidder = harness.new_bidder(1_000);
+    harness.warp_to(start_time);
+
+    harness
+        .place_granted_bid(&bidder, &auction, 400)
+        .expect("place_bid failed");
+    let bid = harness.bid(&auction, &bidder.pubkey());
+    let allowance = pda::allowance(bid.bid_amount, &bidder.pubkey());
+    assert!(harness.svm.get_account(&allowance).is_some());
+}
+
+#[test]
+fn checked_winner_lets_the_bidder_decrypt_its_result() {
+    let mut harness = Harness::new();
+    let start_time = harness.now() + 10;
+    let end_time = start_time + 100;
+    let auction = harness.create_auction(AuctionType::Normal, start_time, end_time, 0);
+    let bidder = harness.new_bidder(1_000);
+    harness.warp_to(start_time);
+    harness
+        .place_bid(&bidder, &auction, 400)
+        .expect("place_bid failed");
+    harness.warp_to(end_time);
+    harness
+        .close_auction(&auction)
+        .expect("close_auction failed");
+
+    let meta = harness
+        .check_granted_winner(&bidder, &auction)
+        .expect("check_winner failed");
+    let result: CheckWinnerResult =
+        decode::return_data(&meta.return_data.data).expect("missing check_winner result");
+    let bid = harness.bid(&auction, &bidder.pubkey());
+    assert_eq!(result.is_winner_handle, bid.is_winner_handle);
+    assert_eq!(plaintext(result.is_winner_handle), 1);
+    let allowance = pda::allowance(result.is_winner_handle, &bidder.pubkey());
+    assert!(harness.svm.get_account(&allowance).is_some());
+}
+
+#[test]
+fn requested_grants_need_their_allowance_accounts() {
+    let mut harness = Harness::new();
+    let start_time = harness.now() + 10;
+    let auction = harness.create_auction(AuctionType::Normal, start_time, start_time + 100, 0);
+    let bidder = harness.new_bidder(1_000);
+    harness.warp_to(start_time);
+
+    let (mut ixs, result) = harness
+        .granted_bid_instructions(&bidder, &auction, 400)
+        .expect("simulation failed");
+    // Leave out the bid amount allowance, Anchor reads the program id as `None`
+    let place = ixs.last_mut().unwrap();
+    let bid_allowance = pda::allowance(result.bid_amount_handle, &bidder.pubkey());
+    for meta in place.accounts.iter_mut() {
+        if meta.pubkey == bid_allowance {
+            *meta = AccountMeta::new_readonly(confidential_bid::ID, false);
+        }
+    }
+
+    let skipped = harness.send(&ixs, &[&bidder]);
+    assert!(is_auction_error(
+        &skipped,
+        AuctionError::MissingAllowanceAccount
+    ));
+}
+
+#[test]
+fn auditor_is_granted_bids_after_they_land() {
+    let mut harness = Harness::new();
+    let auditor = Pubkey::new_unique();
+    let start_time = harness.now() + 10;
+    let auction = harness.create_audited_auction(
+        AuctionType::Normal,
+        start_time,
+        start_time + 100,
+        0,
+        &auditor,
+    );
+    let bidder = harness.new_bidder(1_000);
+    harness.warp_to(start_time);
+
+    // The handles are learnt by simulation exactly as without an auditor
+    harness
+        .place_granted_bid(&bidder, &auction, 400)
+        .expect("place_bid failed");
+    harness
+        .grant_auditor(&auction, Some(&bidder.pubkey()))
+        .expect("grant_auditor failed for the bid");
+    harness
+        .grant_auditor(&auction, None)
+        .expect("grant_auditor failed for the auction");
+
+    let bid = harness.bid(&auction, &bidder.pubkey());
+    let state = harness.auction(&auction);
+    for handle in [bid.bid_amount, bid.time_stamp, state.highest_bid] {
+        let allowance = pda::allowance(handle, &auditor);
+        assert!(harness.svm.get_account(&allowance).is_some());
+    }
+}
+
+#[test]
+fn bidders_cannot_revoke_the_auditor() {
+    let mut harness = Harness::new();
+    let auditor = Pubkey::new_unique();
+    let start_time = harness.now() + 10;
+    let auction = harness.create_audited_auction(
+        AuctionType::Normal,
+        start_time,
+        start_time + 100,
+        0,
+        &auditor,
+    );
+    let bidder = harness.new_bidder(1_000);
+    harness.warp_to(start_time);
+    harness
+        .place_bid(&bidder, &auction, 400)
+        .expect("place_bid failed");
+    harness
+        .grant_auditor(&auction, Some(&bidder.pubkey()))
+        .expect("grant_auditor failed");
+
+    let handle = harness.bid(&auction, &bidder.pubkey()).bid_amount;
+    let revoked = harness.grant_access(&bidder, &auction, handle, &auditor, false);
+    assert!(is_auction_error(
+        &revoked,
+        AuctionError::AuditorAccessLocked
+    ));
+
+    // Other addresses can still be granted and revoked
+    let friend = Pubkey::new_unique();
+    harness
+        .grant_access(&bidder, &auction, handle, &friend, true)
+        .expect("grant failed");
+    harness
+        .grant_access(&bidder, &auction, handle, &friend, false)
+        .expect("revoke failed");
+}
@@ -0,0 +1,93 @@
+//! The client's error table and addresses against the program itself.
+use anchor_lang::{
+    error::ERROR_CODE_OFFSET, event::EVENT_AUTHORITY_SEED, solana_program::hash::hashv,
+};
+use confidential_bid::{
+    constants::{ATTESTER_SEED, AUCTION_SEED, BID_SEED, ORGANIZER_SEED},
+    state::{AttesterConfig, AuctionType, OrganizerProfile},
+    ID,
+};
+use confidential_bid_client::{pda, ClientError};
+use confidential_bid_integration_tests::Harness;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+/// Variants of `AuctionError`, each one carries a `#[msg]`
+fn auction_error_count() -> u32 {
+    include_str!("../../programs/confidential-bid/src/error.rs")
+        .matches("#[msg(")
+        .count() as u32
+}
+
+#[test]
+fn every_program_error_code_decodes_to_its_variant() {
+    let count = auction_error_count();
+    for index in 0..count {
+        let code = ERROR_CODE_OFFSET + index;
+        match ClientError::from_custom_code(code) {
+            ClientError::Auction(err) => assert_eq!(u32::from(err), code, "{err:?}"),
+            other => panic!("code {code} decoded as {other:?}"),
+        }
+    }
+    assert!(matches!(
+        ClientError::from_custom_code(ERROR_CODE_OFFSET + count),
+        ClientError::Custom(_)
+    ));
+}
+
+#[test]
+fn addresses_match_the_program_seeds() {
+    let mut harness = Harness::new();
+    let organizer = harness.organizer.pubkey();
+    let start_time = harness.now() + 10;
+    let public = harness.create_auction(AuctionType::Normal, start_time, start_time + 100, 0);
+    let private =
+        harness.create_private_auction(AuctionType::Normal, start_time, start_time + 100, 0);
+    let bidder = harness.new_bidder(1_000);
+    let (bid_key, bid_secret) = harness.new_bid_key(&Keypair::new(), &private, 1_000);
+    harness.warp_to(start_time);
+    harness
+        .place_bid(&bidder, &public, 400)
+        .expect("place_bid failed");
+    let relayer = harness.organizer.insecure_clone();
+    harness
+        .place_private_bid(&relayer, &bid_key, &bid_secret, &private, 300)
+        .expect("private place_bid failed");
+
+    // Bumps stored on-chain come from the program's own seed constraints
+    let (profile, bump) = Pubkey::find_program_address(&[ORGANIZER_SEED, organizer.as_ref()], &ID);
+    assert_eq!(pda::organizer_profile(&organizer), profile);
+    let stored: OrganizerProfile = harness.try_account(&profile).expect("missing profile");
+    assert_eq!(stored.bump, bump);
+
+    let auction_id = harness.auction(&public).auction_id;
+    let (auction, bump) = Pubkey::find_program_address(
+        &[AUCTION_SEED, organizer.as_ref(), &auction_id.to_le_bytes()],
+        &ID,
+    );
+    assert_eq!(pda::auction(&organizer, auction_id), auction);
+    assert_eq!(auction, public);
+    assert_eq!(harness.auction(&auction).auction_bump, bump);
+
+    let (bid, bump) =
+        Pubkey::find_program_address(&[BID_SEED, public.as_ref(), bidder.pubkey().as_ref()], &ID);
+    assert_eq!(pda::bid(&public, &bidder.pubkey(), None), bid);
+    assert_eq!(harness.bid_at(&bid).bid_bump, bump);
+
+    let commitment = hashv(&[bid_key.pubkey().as_ref(), &bid_secret]).to_bytes();
+    let (bid, bump) = Pubkey::find_program_address(&[BID_SEED, private.as_ref(), &commitment], &ID);
+    assert_eq!(
+        pda::bid(&private, &bid_key.pubkey(), Some(&bid_secret)),
+        bid
+    );
+    assert_eq!(harness.bid_at(&bid).bid_bump, bump);
+
+    let (attester_config, bump) = Pubkey::find_program_address(&[ATTESTER_SEED], &ID);
+    assert_eq!(pda::attester_config(), attester_config);
+    let stored: AttesterConfig = harness
+        .try_account(&attester_config)
+        .expect("missing attester config");
+    assert_eq!(stored.bump, bump);
+
+    let (event_authority, _) = Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], &ID);
+    assert_eq!(pda::event_authority(), event_authority);
+}
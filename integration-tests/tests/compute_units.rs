@@ -0,0 +1,53 @@
+//! Cost of each auction instruction.
+//!
+//! Compute units depend on the toolchain and the mocks, so they are printed
+//! rather than asserted; only the Inco Lightning CPI counts, fixed by the
+//! design, are. Run with `--nocapture` to see them.
+use confidential_bid_integration_tests::{
+    baseline::ZERO_HANDLE_LIGHTNING, Cost, Harness, InstructionCosts, PLACE_BID_CALLS,
+};
+
+fn print_costs(program: &str, costs: &InstructionCosts) {
+    println!("{program}:");
+    println!("  create_auction {:?}", costs.create_auction);
+    for (i, cost) in costs.place_bid.iter().enumerate() {
+        println!("  place_bid #{} {cost:?}", i + 1);
+    }
+    println!("  close_auction {:?}", costs.close_auction);
+    for (i, cost) in costs.check_winner.iter().enumerate() {
+        println!("  check_winner #{} {cost:?}", i + 1);
+    }
+}
+
+fn calls(costs: &[Cost]) -> Vec<usize> {
+    costs.iter().map(|cost| cost.lightning_calls).collect()
+}
+
+#[test]
+fn instruction_costs() {
+    let costs = Harness::new().measure_costs();
+    print_costs("confidential_bid", &costs);
+
+    assert_eq!(calls(&costs.place_bid), PLACE_BID_CALLS);
+    assert_eq!(costs.close_auction.lightning_calls, 2);
+    assert_eq!(calls(&costs.check_winner), [4; 4]);
+}
+
+#[test]
+#[ignore = "needs the baseline build of scripts/build-baseline.sh"]
+fn instruction_costs_against_the_first_deployed_program() {
+    let before = Harness::baseline().measure_baseline_costs();
+    // Same mock for both, so the compute units compare
+    let after = Harness::with_lightning(ZERO_HANDLE_LIGHTNING).measure_costs();
+    print_costs("confidential_bid_baseline", &before);
+    print_costs("confidential_bid", &after);
+
+    // Only the first two bids got cheaper, later ones make as many CPIs
+    assert_eq!(calls(&before.place_bid), [8; 4]);
+    assert_eq!(calls(&after.place_bid), PLACE_BID_CALLS);
+    assert_eq!(
+        before.close_auction.lightning_calls,
+        after.close_auction.lightning_calls
+    );
+    assert_eq!(calls(&before.check_winner), calls(&after.check_winner));
+}
@@ -0,0 +1,164 @@
+//! Auctions created with `deferred_standings`, whose bids are reduced in
+//! batches by the `reduce_bids` crank after the end time.
+use confidential_bid::{
+    error::AuctionError,
+    state::{AuctionStatus, AuctionType, BidReduction},
+};
+use confidential_bid_client::pda;
+use confidential_bid_integration_tests::{
+    is_auction_error, model::ReferenceAuction, plaintext, Harness,
+};
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+const START_DELAY: i64 = 10;
+const DURATION: i64 = 100;
+
+/// Places `amounts` one second apart on a new deferred auction and warps to its end.
+/// Returns the auction, the bidders in bid order and the reference auction.
+fn bid_and_end(
+    harness: &mut Harness,
+    reserve_price: u64,
+    amounts: &[u64],
+) -> (Pubkey, Vec<Keypair>, ReferenceAuction) {
+    let start_time = harness.now() + START_DELAY;
+    let end_time = start_time + DURATION;
+    let auction =
+        harness.create_deferred_auction(AuctionType::Vickrey, start_time, end_time, reserve_price);
+
+    let mut model = ReferenceAuction::new(reserve_price);
+    let mut bidders = Vec::new();
+    for (i, amount) in amounts.iter().enumerate() {
+        let bidder = harness.new_bidder(1_000);
+        harness.warp_to(start_time + i as i64);
+        harness
+            .place_bid(&bidder, &auction, *amount)
+            .expect("place_bid failed");
+        model.place(*amount, harness.now());
+        bidders.push(bidder);
+    }
+
+    harness.warp_to(end_time);
+    (auction, bidders, model)
+}
+
+#[test]
+fn bids_only_record_until_reduced() {
+    let mut harness = Harness::new();
+    let (auction, bidders, _) = bid_and_end(&mut harness, 0, &[300, 500]);
+
+    let state = harness.auction(&auction);
+    assert_eq!(state.highest_bid, 0);
+    assert_eq!(state.second_highest_bid, None);
+    assert_eq!(state.bid_count, 2);
+    let sequences: Vec<u32> = bidders
+        .iter()
+        .map(|bidder| harness.bid(&auction, &bidder.pubkey()).sequence)
+        .collect();
+    assert_eq!(sequences, [0, 1]);
+
+    let unreduced = harness.close_auction(&auction);
+    assert!(is_auction_error(
+        &unreduced,
+        AuctionError::StandingsNotReduced
+    ));
+}
+
+#[test]
+fn reduction_waits_for_the_end_and_a_deferred_auction() {
+    let mut harness = Harness::new();
+    let start_time = harness.now() + START_DELAY;
+    let end_time = start_time + DURATION;
+    let deferred = harness.create_deferred_auction(AuctionType::Normal, start_time, end_time, 0);
+    let tracked = harness.create_auction(AuctionType::Normal, start_time, end_time, 0);
+
+    harness.warp_to(start_time);
+    let live = harness.reduce_bids(&deferred, 0, &[]);
+    assert!(is_auction_error(&live, AuctionError::AuctionNotEnded));
+
+    harness.warp_to(end_time);
+    let not_deferred = harness.reduce_bids(&tracked, 0, &[]);
+    assert!(is_auction_error(
+        &not_deferred,
+        AuctionError::StandingsNotDeferred
+    ));
+}
+
+#[test]
+fn batches_must_follow_bid_order() {
+    let mut harness = Harness::new();
+    let (auction, bidders, _) = bid_and_end(&mut harness, 0, &[300, 500, 400]);
+
+    let skipped = harness.reduce_bids(&auction, 0, &bidders[1..]);
+    assert!(is_auction_error(&skipped, AuctionError::BidOutOfOrder));
+
+    harness.reduce_bids(&auction, 0, &bidders[..1]).unwrap();
+    let incomplete = harness.finalize_reduction(&auction);
+    assert!(is_auction_error(
+        &incomplete,
+        AuctionError::ReductionIncomplete
+    ));
+
+    harness.reduce_bids(&auction, 2, &bidders[2..]).unwrap();
+    let gap = harness.merge_reductions(&auction, 0, 2);
+    assert!(is_auction_error(&gap, AuctionError::ReductionGap));
+}
+
+#[test]
+fn merged_batches_match_the_reference() {
+    let amounts = [500, 300, 700, 500, 700, 100];
+    let mut harness = Harness::new();
+    let (auction, bidders, model) = bid_and_end(&mut harness, 600, &amounts);
+
+    // Three batches reduced independently, merged back to front
+    harness.reduce_bids(&auction, 0, &bidders[..2]).unwrap();
+    harness.reduce_bids(&auction, 2, &bidders[2..4]).unwrap();
+    harness.reduce_bids(&auction, 4, &bidders[4..]).unwrap();
+    harness.merge_reductions(&auction, 2, 4).unwrap();
+    harness.merge_reductions(&auction, 0, 2).unwrap();
+    harness.finalize_reduction(&auction).unwrap();
+
+    let state = harness.auction(&auction);
+    assert!(state.standings_final);
+    assert_eq!(plaintext(state.highest_bid) as u64, model.highest());
+    assert_eq!(
+        state
+            .second_highest_bid
+            .map(|second| plaintext(second) as u64),
+        model.second_highest()
+    );
+    assert_eq!(
+        plaintext(state.highest_timestamp),
+        model.highest_timestamp()
+    );
+    // Merged and finalized reductions are closed
+    assert!(harness
+        .try_account::<BidReduction>(&pda::reduction(&auction, 0))
+        .is_none());
+    // and can't be reduced again to overwrite the standings
+    let reduced_again = harness.reduce_bids(&auction, 0, &bidders[..1]);
+    assert!(is_auction_error(
+        &reduced_again,
+        AuctionError::StandingsFinal
+    ));
+
+    harness.close_auction(&auction).unwrap();
+    for (i, bidder) in bidders.iter().enumerate() {
+        harness.check_winner(bidder, &auction).unwrap();
+        let won = plaintext(harness.bid(&auction, &bidder.pubkey()).is_winner_handle) == 1;
+        assert_eq!(won, model.is_winner(i), "bid {i}");
+    }
+}
+
+#[test]
+fn auction_without_bids_finalizes_empty() {
+    let mut harness = Harness::new();
+    let (auction, _, _) = bid_and_end(&mut harness, 0, &[]);
+
+    harness.reduce_bids(&auction, 0, &[]).unwrap();
+    harness.finalize_reduction(&auction).unwrap();
+    harness.close_auction(&auction).unwrap();
+
+    let state = harness.auction(&auction);
+    assert!(state.auction_status == AuctionStatus::Closed);
+    assert_eq!(state.highest_bid, 0);
+}
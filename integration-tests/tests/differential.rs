@@ -0,0 +1,127 @@
+//! Random bid sequences run through the program and through the plaintext
+//! [`ReferenceAuction`], comparing the order statistics after every bid and
+//! each `check_winner` result after closing.
+use confidential_bid::state::AuctionType;
+use confidential_bid_integration_tests::{model::ReferenceAuction, plaintext, Harness};
+use proptest::prelude::*;
+use solana_sdk::signer::Signer;
+
+const MAX_AMOUNT: u64 = 10_000;
+const MAX_BIDS: usize = 8;
+
+#[derive(Clone, Debug)]
+struct Scenario {
+    vickrey: bool,
+    reserve_price: u64,
+    /// (amount, seconds after the previous bid)
+    bids: Vec<(u64, i64)>,
+}
+
+fn scenario() -> impl Strategy<Value = Scenario> {
+    (
+        any::<bool>(),
+        0..=MAX_AMOUNT,
+        prop::collection::vec((0..=MAX_AMOUNT, 1..=5i64), 1..=MAX_BIDS),
+    )
+        .prop_map(|(vickrey, reserve_price, bids)| Scenario {
+            vickrey,
+            reserve_price,
+            bids,
+        })
+}
+
+/// Amounts drawn from a few values so ties are common, some placed in the
+/// same second
+fn tied_scenario() -> impl Strategy<Value = Scenario> {
+    (
+        any::<bool>(),
+        0..=3u64,
+        prop::collection::vec((0..=3u64, 0..=2i64), 2..=MAX_BIDS),
+    )
+        .prop_map(|(vickrey, reserve_price, bids)| Scenario {
+            vickrey,
+            reserve_price,
+            bids,
+        })
+}
+
+fn run(scenario: &Scenario) -> Result<(), TestCaseError> {
+    let mut harness = Harness::new();
+    let auction_type = if scenario.vickrey {
+        AuctionType::Vickrey
+    } else {
+        AuctionType::Normal
+    };
+    let start_time = harness.now() + 1;
+    let duration: i64 = scenario.bids.iter().map(|(_, gap)| gap).sum();
+    let end_time = start_time + duration + 1;
+    let auction =
+        harness.create_auction(auction_type, start_time, end_time, scenario.reserve_price);
+    let mut reference = ReferenceAuction::new(scenario.reserve_price);
+
+    let mut now = start_time;
+    let mut bidders = Vec::new();
+    for (amount, gap) in &scenario.bids {
+        let bidder = harness.new_bidder(*amount);
+        harness.warp_to(now);
+        harness
+            .place_bid(&bidder, &auction, *amount)
+            .map_err(|failed| TestCaseError::fail(format!("place_bid: {:?}", failed.err)))?;
+        reference.place(*amount, now);
+        bidders.push(bidder);
+
+        let state = harness.auction(&auction);
+        prop_assert_eq!(plaintext(state.highest_bid), reference.highest() as u128);
+        prop_assert_eq!(
+            state.second_highest_bid.map(plaintext),
+            reference.second_highest().map(u128::from)
+        );
+        prop_assert_eq!(
+            plaintext(state.highest_timestamp),
+            reference.highest_timestamp()
+        );
+        now += gap;
+    }
+
+    harness.warp_to(end_time);
+    harness
+        .close_auction(&auction)
+        .map_err(|failed| TestCaseError::fail(format!("close_auction: {:?}", failed.err)))?;
+    prop_assert_eq!(
+        plaintext(harness.auction(&auction).reserve_met_handle),
+        u128::from(reference.reserve_met())
+    );
+
+    let mut winners = Vec::new();
+    for (i, bidder) in bidders.iter().enumerate() {
+        harness
+            .check_winner(bidder, &auction)
+            .map_err(|failed| TestCaseError::fail(format!("check_winner: {:?}", failed.err)))?;
+        let bid = harness.bid(&auction, &bidder.pubkey());
+        prop_assert_eq!(
+            plaintext(bid.is_winner_handle),
+            u128::from(reference.is_winner(i))
+        );
+        if plaintext(bid.is_winner_handle) == 1 {
+            winners.push(i);
+        }
+    }
+    // At most one winner, even among equal bids in the same second
+    prop_assert_eq!(winners, reference.winner().into_iter().collect::<Vec<_>>());
+    Ok(())
+}
+
+proptest! {
+    // Every case boots a fresh SVM, keep the count modest
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn program_matches_reference(scenario in scenario()) {
+        run(&scenario)?;
+    }
+
+    #[test]
+    fn program_matches_reference_on_ties(scenario in tied_scenario()) {
+        run(&scenario)?;
+    }
+}
@@ -0,0 +1,139 @@
+//! Auctions created with `disclose_bids`, whose bid amounts are granted to the
+//! disclosure address by the `disclose_bids` crank and recorded by `reveal_bid`.
+use confidential_bid::{error::AuctionError, state::AuctionType};
+use confidential_bid_integration_tests::{is_auction_error, Harness};
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+/// Places `amounts` one second apart on a disclosing auction and warps to its end.
+/// Returns the bidders in bid order.
+fn ended_auction(harness: &mut Harness, amounts: &[u64]) -> (Pubkey, Vec<Keypair>) {
+    let start_time = harness.now() + 10;
+    let end_time = start_time + 100;
+    let auction = harness.create_disclosed_auction(AuctionType::Normal, start_time, end_time, 0);
+
+    let mut bidders = Vec::new();
+    for (i, amount) in amounts.iter().enumerate() {
+        let bidder = harness.new_bidder(1_000);
+        harness.warp_to(start_time + i as i64);
+        harness
+            .place_bid(&bidder, &auction, *amount)
+            .expect("place_bid failed");
+        bidders.push(bidder);
+    }
+    harness.warp_to(end_time);
+    (auction, bidders)
+}
+
+#[test]
+fn revealed_bid_book_recomputes_the_winner() {
+    let mut harness = Harness::new();
+    let amounts = [300, 500, 500, 400];
+    let (auction, bidders) = ended_auction(&mut harness, &amounts);
+
+    let early = harness.reveal_bid(&auction, &bidders[0]);
+    assert!(is_auction_error(&early, AuctionError::AuctionNotClosed));
+    let cranker = harness.new_bidder(0);
+    let live = harness.disclose_bids(&cranker, &auction, &bidders);
+    assert!(is_auction_error(&live, AuctionError::AuctionNotClosed));
+    harness
+        .close_auction(&auction)
+        .expect("close_auction failed");
+
+    // Anyone cranks, in batches; no bid is revealed until all are disclosed
+    harness
+        .disclose_bids(&cranker, &auction, &bidders[..2])
+        .expect("disclose_bids failed");
+    assert_eq!(harness.auction(&auction).disclosed_count, 2);
+    let partial = harness.reveal_bid(&auction, &bidders[0]);
+    assert!(is_auction_error(
+        &partial,
+        AuctionError::DisclosureIncomplete
+    ));
+    harness
+        .disclose_bids(&cranker, &auction, &bidders[2..])
+        .expect("disclose_bids failed");
+
+    let mut book = Vec::new();
+    for bidder in &bidders {
+        harness
+            .reveal_bid(&auction, bidder)
+            .expect("reveal_bid failed");
+        let bid = harness.bid(&auction, &bidder.pubkey());
+        book.push((
+            bid.sequence,
+            bid.revealed_amount.expect("amount not recorded"),
+        ));
+    }
+    let revealed: Vec<u64> = book.iter().map(|(_, amount)| *amount).collect();
+    assert_eq!(revealed, amounts);
+
+    // Highest amount, the earlier bid winning ties
+    let winner = book
+        .iter()
+        .max_by_key(|(sequence, amount)| (*amount, std::cmp::Reverse(*sequence)))
+        .unwrap();
+    assert_eq!(winner.0, 1);
+
+    let again = harness.reveal_bid(&auction, &bidders[0]);
+    assert!(is_auction_error(&again, AuctionError::BidAlreadyRevealed));
+}
+
+#[test]
+fn disclosure_continues_the_bid_order() {
+    let mut harness = Harness::new();
+    let (auction, bidders) = ended_auction(&mut harness, &[300, 500, 400]);
+    harness
+        .close_auction(&auction)
+        .expect("close_auction failed");
+    let cranker = harness.new_bidder(0);
+
+    let empty = harness.disclose_bids(&cranker, &auction, &[]);
+    assert!(is_auction_error(
+        &empty,
+        AuctionError::InvalidDisclosureAccounts
+    ));
+    let skipped = harness.disclose_bids(&cranker, &auction, &bidders[1..]);
+    assert!(is_auction_error(&skipped, AuctionError::BidOutOfOrder));
+    harness
+        .disclose_bids(&cranker, &auction, &bidders[..1])
+        .expect("disclose_bids failed");
+    let repeated = harness.disclose_bids(&cranker, &auction, &bidders[..2]);
+    assert!(is_auction_error(&repeated, AuctionError::BidOutOfOrder));
+    harness
+        .disclose_bids(&cranker, &auction, &bidders[1..])
+        .expect("disclose_bids failed");
+    assert_eq!(harness.auction(&auction).disclosed_count, 3);
+}
+
+#[test]
+fn bids_stay_confidential_without_the_flag() {
+    let mut harness = Harness::new();
+    let start_time = harness.now() + 10;
+    let end_time = start_time + 100;
+    let auction = harness.create_auction(AuctionType::Normal, start_time, end_time, 0);
+    let bidder = harness.new_bidder(1_000);
+    harness.warp_to(start_time);
+    harness
+        .place_bid(&bidder, &auction, 300)
+        .expect("place_bid failed");
+    harness.warp_to(end_time);
+    harness
+        .close_auction(&auction)
+        .expect("close_auction failed");
+
+    let cranker = harness.new_bidder(0);
+    let disclose = harness.disclose_bids(&cranker, &auction, std::slice::from_ref(&bidder));
+    assert!(is_auction_error(
+        &disclose,
+        AuctionError::DisclosureNotEnabled
+    ));
+    let reveal = harness.reveal_bid(&auction, &bidder);
+    assert!(is_auction_error(
+        &reveal,
+        AuctionError::DisclosureNotEnabled
+    ));
+    assert_eq!(
+        harness.bid(&auction, &bidder.pubkey()).revealed_amount,
+        None
+    );
+}
@@ -0,0 +1,135 @@
+//! Auctions created with `hide_bid_count`, counting their bids encrypted.
+use confidential_bid::{error::AuctionError, state::AuctionType};
+use confidential_bid_client::instructions;
+use confidential_bid_integration_tests::{is_auction_error, plaintext, Harness, PRIZE_SUPPLY};
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+const BALANCE: u64 = 1_000;
+
+/// Places `amounts` in the same second on a new hidden-count auction.
+/// Returns the end time and the bidders in bid order.
+fn hidden_count_auction(
+    harness: &mut Harness,
+    reserve_price: u64,
+    amounts: &[u64],
+) -> (Pubkey, i64, Vec<Keypair>) {
+    let start_time = harness.now() + 10;
+    let end_time = start_time + 100;
+    let auction = harness.create_hidden_count_auction(
+        AuctionType::Vickrey,
+        start_time,
+        end_time,
+        reserve_price,
+    );
+    harness.warp_to(start_time);
+    let mut bidders = Vec::new();
+    for amount in amounts {
+        let bidder = harness.new_bidder(BALANCE);
+        harness
+            .place_bid(&bidder, &auction, *amount)
+            .expect("place_bid failed");
+        bidders.push(bidder);
+    }
+    (auction, end_time, bidders)
+}
+
+#[test]
+fn bids_are_only_counted_encrypted() {
+    let mut harness = Harness::new();
+    let (auction, _, bidders) = hidden_count_auction(&mut harness, 0, &[500, 700, 700]);
+
+    let state = harness.auction(&auction);
+    assert_eq!(state.bid_count, 0);
+    assert_eq!(plaintext(state.encrypted_bid_count), 3);
+    // Every bid keeps sequence 0, the encrypted count still tells them apart
+    let keys: Vec<u128> = bidders
+        .iter()
+        .map(|bidder| plaintext(harness.bid(&auction, &bidder.pubkey()).time_stamp))
+        .collect();
+    assert!(keys[0] < keys[1] && keys[1] < keys[2]);
+
+    // Nothing to check against the minimum bid count
+    let organizer = harness.organizer.insecure_clone();
+    let ended = harness.send(
+        &[instructions::end_early(&organizer.pubkey(), &auction)],
+        &[&organizer],
+    );
+    assert!(is_auction_error(&ended, AuctionError::BidCountHidden));
+}
+
+#[test]
+fn hidden_count_auction_settles() {
+    let mut harness = Harness::new();
+    let (auction, end_time, bidders) = hidden_count_auction(&mut harness, 0, &[500, 700, 700]);
+    harness.warp_to(end_time);
+    harness
+        .close_auction(&auction)
+        .expect("close_auction failed");
+    harness
+        .resolve_winners(&auction, &bidders)
+        .expect("resolve_winners failed");
+    let [loser, winner, tied] = &bidders[..] else {
+        unreachable!()
+    };
+
+    harness
+        .claim_prize(winner, &auction)
+        .expect("claim_prize failed");
+    assert_eq!(harness.prize_balance(&winner.pubkey()), 1);
+    // Pays the tied second 700
+    assert_eq!(harness.bid_balance(&winner.pubkey()), 300);
+    for bidder in [loser, tied] {
+        harness
+            .refund_bid(bidder, &auction)
+            .expect("refund_bid failed");
+        assert_eq!(harness.bid_balance(&bidder.pubkey()), u128::from(BALANCE));
+    }
+    harness
+        .settle_auction(&auction)
+        .expect("settle_auction failed");
+    assert_eq!(harness.bid_balance(&harness.organizer.pubkey()), 700);
+    assert_eq!(harness.bid_balance(&auction), 0);
+}
+
+#[test]
+fn hidden_count_auction_below_the_reserve_is_cancelled() {
+    let mut harness = Harness::new();
+    let (auction, end_time, bidders) = hidden_count_auction(&mut harness, 800, &[500, 700]);
+    harness.warp_to(end_time);
+    harness
+        .close_auction(&auction)
+        .expect("close_auction failed");
+    harness
+        .cancel_auction(&auction)
+        .expect("cancel_auction failed");
+    assert_eq!(
+        harness.prize_balance(&harness.organizer.pubkey()),
+        PRIZE_SUPPLY
+    );
+
+    for bidder in &bidders {
+        harness
+            .refund_bid(bidder, &auction)
+            .expect("refund_bid failed");
+        assert_eq!(harness.bid_balance(&bidder.pubkey()), u128::from(BALANCE));
+    }
+}
+
+#[test]
+fn hidden_count_auction_without_bids_is_cancelled() {
+    let mut harness = Harness::new();
+    let (auction, end_time, _) = hidden_count_auction(&mut harness, 0, &[]);
+    harness.warp_to(end_time);
+    harness
+        .close_auction(&auction)
+        .expect("close_auction failed");
+    // No bid never meets even a zero reserve
+    assert_eq!(plaintext(harness.auction(&auction).reserve_met_handle), 0);
+    harness
+        .cancel_auction(&auction)
+        .expect("cancel_auction failed");
+    assert_eq!(
+        harness.prize_balance(&harness.organizer.pubkey()),
+        PRIZE_SUPPLY
+    );
+}
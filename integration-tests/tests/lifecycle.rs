@@ -0,0 +1,166 @@
+use confidential_bid::{
+    error::AuctionError,
+    state::{AuctionStatus, AuctionType},
+};
+use confidential_bid_client::pda;
+use confidential_bid_integration_tests::{is_auction_error, plaintext, Harness};
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+const START_DELAY: i64 = 10;
+const DURATION: i64 = 100;
+
+/// Creates an auction starting `START_DELAY` seconds from now, returning it with its schedule
+fn scheduled_auction(harness: &mut Harness, auction_type: AuctionType) -> (Pubkey, i64, i64) {
+    let start_time = harness.now() + START_DELAY;
+    let end_time = start_time + DURATION;
+    let auction = harness.create_auction(auction_type, start_time, end_time, 0);
+    (auction, start_time, end_time)
+}
+
+/// Places `amounts` one second apart from the start, then closes after the end.
+/// Returns the bidders in bid order.
+fn run_auction(
+    harness: &mut Harness,
+    auction_type: AuctionType,
+    amounts: &[u64],
+) -> (Pubkey, Vec<Keypair>) {
+    let (auction, start_time, end_time) = scheduled_auction(harness, auction_type);
+
+    let mut bidders = Vec::new();
+    for (i, amount) in amounts.iter().enumerate() {
+        let bidder = harness.new_bidder(1_000);
+        harness.warp_to(start_time + i as i64);
+        harness
+            .place_bid(&bidder, &auction, *amount)
+            .expect("place_bid failed");
+        bidders.push(bidder);
+    }
+
+    harness.warp_to(end_time);
+    harness
+        .close_auction(&auction)
+        .expect("close_auction failed");
+    (auction, bidders)
+}
+
+/// Runs `check_winner` for every bidder and returns the plaintext results
+fn winners(harness: &mut Harness, auction: &Pubkey, bidders: &[Keypair]) -> Vec<bool> {
+    bidders
+        .iter()
+        .map(|bidder| {
+            harness
+                .check_winner(bidder, auction)
+                .expect("check_winner failed");
+            plaintext(harness.bid(auction, &bidder.pubkey()).is_winner_handle) == 1
+        })
+        .collect()
+}
+
+#[test]
+fn bids_are_rejected_outside_the_schedule() {
+    let mut harness = Harness::new();
+    let (auction, _, end_time) = scheduled_auction(&mut harness, AuctionType::Normal);
+    let bidder = harness.new_bidder(1_000);
+
+    let early = harness.place_bid(&bidder, &auction, 100);
+    assert!(is_auction_error(&early, AuctionError::AuctionNotStarted));
+
+    harness.warp_to(end_time);
+    let late = harness.place_bid(&bidder, &auction, 100);
+    assert!(is_auction_error(&late, AuctionError::AuctionEnded));
+
+    // The last second before the end still accepts bids
+    harness.warp_to(end_time - 1);
+    harness.place_bid(&bidder, &auction, 100).unwrap();
+}
+
+#[test]
+fn auction_cannot_close_before_its_end() {
+    let mut harness = Harness::new();
+    let (auction, start_time, end_time) = scheduled_auction(&mut harness, AuctionType::Normal);
+
+    let before_start = harness.close_auction(&auction);
+    assert!(is_auction_error(
+        &before_start,
+        AuctionError::AuctionNotEnded
+    ));
+
+    harness.warp_to(start_time);
+    let live = harness.close_auction(&auction);
+    assert!(is_auction_error(&live, AuctionError::AuctionNotEnded));
+
+    harness.warp_to(end_time);
+    harness.close_auction(&auction).unwrap();
+    assert!(harness.auction(&auction).auction_status == AuctionStatus::Closed);
+}
+
+#[test]
+fn normal_auction_lifecycle() {
+    let mut harness = Harness::new();
+    let (auction, bidders) = run_auction(&mut harness, AuctionType::Normal, &[300, 500, 400]);
+
+    let state = harness.auction(&auction);
+    assert_eq!(plaintext(state.highest_bid), 500);
+    assert_eq!(state.second_highest_bid.map(plaintext), Some(400));
+    assert_eq!(state.bid_count, 3);
+    assert_eq!(plaintext(state.reserve_met_handle), 1);
+
+    assert_eq!(
+        winners(&mut harness, &auction, &bidders),
+        [false, true, false]
+    );
+}
+
+#[test]
+fn vickrey_auction_lifecycle() {
+    let mut harness = Harness::new();
+    let (auction, bidders) = run_auction(&mut harness, AuctionType::Vickrey, &[200, 700, 650, 100]);
+
+    let state = harness.auction(&auction);
+    assert!(state.auction_type == AuctionType::Vickrey);
+    assert_eq!(plaintext(state.highest_bid), 700);
+    assert_eq!(state.second_highest_bid.map(plaintext), Some(650));
+
+    assert_eq!(
+        winners(&mut harness, &auction, &bidders),
+        [false, true, false, false]
+    );
+}
+
+#[test]
+fn tied_bids_go_to_the_earliest() {
+    let mut harness = Harness::new();
+    let (auction, bidders) = run_auction(&mut harness, AuctionType::Vickrey, &[500, 300, 500]);
+
+    let state = harness.auction(&auction);
+    assert_eq!(plaintext(state.highest_bid), 500);
+    assert_eq!(state.second_highest_bid.map(plaintext), Some(500));
+
+    assert_eq!(
+        winners(&mut harness, &auction, &bidders),
+        [true, false, false]
+    );
+}
+
+#[test]
+fn organizer_resolves_every_bid_at_once() {
+    let mut harness = Harness::new();
+    let (auction, bidders) = run_auction(&mut harness, AuctionType::Normal, &[300, 500, 400]);
+
+    harness.resolve_winners(&auction, &bidders[..2]).unwrap();
+    assert_eq!(harness.auction(&auction).resolved_count, 2);
+
+    // Already resolved bids are not counted twice
+    harness.resolve_winners(&auction, &bidders).unwrap();
+    harness.check_winner(&bidders[1], &auction).unwrap();
+    assert_eq!(harness.auction(&auction).resolved_count, 3);
+
+    for (bidder, won) in bidders.iter().zip([false, true, false]) {
+        let bid = harness.bid(&auction, &bidder.pubkey());
+        assert!(bid.resolved);
+        assert_eq!(plaintext(bid.is_winner_handle) == 1, won);
+        // The bidder was granted decryption without signing
+        let allowance = pda::allowance(bid.is_winner_handle, &bidder.pubkey());
+        assert!(harness.svm.get_account(&allowance).is_some());
+    }
+}
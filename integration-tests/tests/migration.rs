@@ -0,0 +1,318 @@
+//! Migration of accounts written by the first deployed layouts.
+use anchor_lang::{AnchorSerialize, Discriminator};
+use confidential_bid::{
+    constants::{AUCTION_STATE_VERSION, LEGACY_BID_VERSION},
+    error::AuctionError,
+    state::{AuctionState, AuctionStatus, AuctionType, Bid, PayoutRecipient},
+};
+use confidential_bid_client::{instructions, pda};
+use confidential_bid_integration_tests::{is_auction_error, Harness};
+use solana_sdk::{account::Account, pubkey::Pubkey, signer::Signer};
+
+/// `8 + AuctionState::LEN` as first deployed
+const LEGACY_AUCTION_LEN: usize = 8 + 266;
+/// `8 + AuctionState::INIT_SPACE` at version 1
+const V1_AUCTION_LEN: usize = 8 + 562;
+/// `8 + Bid::INIT_SPACE` as first deployed
+const LEGACY_BID_LEN: usize = 8 + 122;
+
+/// Borsh-encodes `fields` in order after `discriminator`, zero-padded to `len`
+fn legacy_data(discriminator: &[u8], fields: &[&dyn Fn(&mut Vec<u8>)], len: usize) -> Vec<u8> {
+    let mut data = discriminator.to_vec();
+    for field in fields {
+        field(&mut data);
+    }
+    assert!(data.len() <= len, "legacy fields overflow the account");
+    data.resize(len, 0);
+    data
+}
+
+fn field<T: AnchorSerialize>(value: T) -> impl Fn(&mut Vec<u8>) {
+    move |data| value.serialize(data).unwrap()
+}
+
+fn set_legacy_account(harness: &mut Harness, key: &Pubkey, data: Vec<u8>) {
+    let lamports = harness.svm.minimum_balance_for_rent_exemption(data.len());
+    harness
+        .svm
+        .set_account(
+            *key,
+            Account {
+                lamports,
+                data,
+                owner: confidential_bid::ID,
+                executable: false,
+                rent_epoch: 0,
+            },
+        )
+        .unwrap();
+}
+
+#[test]
+fn migrates_a_first_layout_auction() {
+    let mut harness = Harness::new();
+    let key = Pubkey::new_unique();
+    let organizer = harness.organizer.pubkey();
+    let (mint, vault, bid_token_mint, bid_vault) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let data = legacy_data(
+        AuctionState::DISCRIMINATOR,
+        &[
+            &field(organizer),
+            &field(mint),
+            &field(None::<Pubkey>),
+            &field(vault),
+            &field(bid_token_mint),
+            &field(bid_vault),
+            &field(700u128),
+            &field(None::<u128>),
+            &field(1_700_000_050u128),
+            &field(1u128),
+            &field(1_700_000_000i64),
+            &field(1_700_000_100i64),
+            &field(500u64),
+            &field(7u64),
+            &field(3u32),
+            &field(AuctionStatus::Closed),
+            &field(AuctionType::Vickrey),
+            &field(false),
+            &field(254u8),
+        ],
+        LEGACY_AUCTION_LEN,
+    );
+    set_legacy_account(&mut harness, &key, data);
+
+    let payer = harness.organizer.insecure_clone();
+    harness
+        .send(
+            &[instructions::migrate_auction(&payer.pubkey(), &key)],
+            &[&payer],
+        )
+        .expect("migrate_auction failed");
+
+    let auction = harness.auction(&key);
+    assert_eq!(auction.organizer, organizer);
+    assert_eq!(
+        (
+            auction.mint,
+            auction.vault,
+            auction.bid_token_mint,
+            auction.bid_vault
+        ),
+        (mint, vault, bid_token_mint, bid_vault)
+    );
+    assert_eq!(auction.highest_bidder, None);
+    assert_eq!(auction.highest_bid, 700);
+    assert_eq!(auction.second_highest_bid, None);
+    assert_eq!(auction.highest_timestamp, 1_700_000_050);
+    assert_eq!(auction.reserve_met_handle, 1);
+    assert_eq!(
+        (auction.start_time, auction.end_time),
+        (1_700_000_000, 1_700_000_100)
+    );
+    assert_eq!(auction.total_extension, 0);
+    assert_eq!(auction.reserve_price, 500);
+    assert_eq!(auction.auction_id, 7);
+    assert_eq!(auction.bid_count, 3);
+    assert!(auction.auction_status == AuctionStatus::Closed);
+    assert!(auction.auction_type == AuctionType::Vickrey);
+    assert!(!auction.prize_claimed);
+    assert_eq!(auction.auction_bump, 254);
+    assert_eq!(auction.auditor, None);
+    assert!(auction.payout_recipients.is_empty());
+    assert!(!auction.hide_bid_count && !auction.private_bids);
+}
+
+#[test]
+fn migrates_a_version_1_auction() {
+    let mut harness = Harness::new();
+    let key = Pubkey::new_unique();
+    let organizer = harness.organizer.pubkey();
+    let (auditor, winning_bid) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let recipient = PayoutRecipient {
+        recipient: Pubkey::new_unique(),
+        share_bps: 2_500,
+    };
+    let data = legacy_data(
+        AuctionState::DISCRIMINATOR,
+        &[
+            &field(organizer),
+            &field(Pubkey::new_unique()),
+            &field(None::<Pubkey>),
+            &field(Pubkey::new_unique()),
+            &field(Pubkey::new_unique()),
+            &field(Pubkey::new_unique()),
+            &field(700u128),
+            &field(Some(600u128)),
+            &field(1_700_000_050u128),
+            &field(1u128),
+            &field(1_700_000_000i64),
+            &field(1_700_000_100i64),
+            &field(60i64),
+            &field(500u64),
+            &field(7u64),
+            &field(3u32),
+            &field(0u128),
+            &field(false),
+            &field(false),
+            &field(AuctionStatus::Settled),
+            &field(AuctionType::Vickrey),
+            &field(true),
+            &field(Some(auditor)),
+            &field(vec![recipient]),
+            &field(254u8),
+            &field(1u8),
+            &field(false),
+            &field(false),
+            &field(3u32),
+            &field(true),
+            &field(Some(600u64)),
+            &field(Some(winning_bid)),
+            &field(true),
+            &field([0u8; 14]),
+        ],
+        V1_AUCTION_LEN,
+    );
+    set_legacy_account(&mut harness, &key, data);
+
+    let payer = harness.organizer.insecure_clone();
+    let migrate = [instructions::migrate_auction(&payer.pubkey(), &key)];
+    harness
+        .send(&migrate, &[&payer])
+        .expect("migrate_auction failed");
+
+    let auction = harness.auction(&key);
+    assert_eq!(auction.version, AUCTION_STATE_VERSION);
+    assert_eq!(auction.organizer, organizer);
+    assert_eq!(
+        (auction.highest_bid, auction.second_highest_bid),
+        (700, Some(600))
+    );
+    assert_eq!(auction.total_extension, 60);
+    assert!(auction.auction_status == AuctionStatus::Settled);
+    assert_eq!(auction.auditor, Some(auditor));
+    assert!(auction.payout_recipients == [recipient]);
+    assert_eq!(auction.resolved_count, 3);
+    assert!(auction.reveal_on_settle && auction.disclose_bids);
+    assert_eq!(
+        (auction.clearing_price, auction.winning_bid),
+        (Some(600), Some(winning_bid))
+    );
+    // Version 1 kept neither the price handle nor a disclosure count
+    assert_eq!(auction.clearing_price_handle, 0);
+    assert_eq!(auction.disclosed_count, 0);
+
+    let again = harness.send(&migrate, &[&payer]);
+    assert!(is_auction_error(
+        &again,
+        AuctionError::AccountAlreadyMigrated
+    ));
+}
+
+#[test]
+fn migrates_a_first_layout_bid() {
+    let mut harness = Harness::new();
+    let key = Pubkey::new_unique();
+    let (bidder, auction) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let data = legacy_data(
+        Bid::DISCRIMINATOR,
+        &[
+            &field(bidder),
+            &field(auction),
+            &field(400u128),
+            &field(1_700_000_020u128),
+            &field(1u128),
+            &field(true),
+            &field(253u8),
+        ],
+        LEGACY_BID_LEN,
+    );
+    set_legacy_account(&mut harness, &key, data);
+
+    let payer = harness.organizer.insecure_clone();
+    harness
+        .send(
+            &[instructions::migrate_bid(&payer.pubkey(), &key)],
+            &[&payer],
+        )
+        .expect("migrate_bid failed");
+
+    let bid = harness.bid_at(&key);
+    assert_eq!((bid.bidder, bid.auction), (bidder, auction));
+    assert_eq!(
+        (bid.bid_amount, bid.time_stamp, bid.is_winner_handle),
+        (400, 1_700_000_020, 1)
+    );
+    assert!(bid.claimed);
+    assert_eq!(bid.bid_bump, 253);
+    assert_eq!(bid.commitment, [0; 32]);
+    assert_eq!(bid.version, LEGACY_BID_VERSION);
+}
+
+#[test]
+fn migrated_bid_cannot_be_refunded() {
+    let mut harness = Harness::new();
+    let start_time = harness.now() + 10;
+    let end_time = start_time + 100;
+    let auction = harness.create_auction(AuctionType::Normal, start_time, end_time, 1_000);
+    let bidder = harness.new_bidder(1_000);
+    let other = harness.new_bidder(1_000);
+    harness.warp_to(start_time);
+    for (bidder, amount) in [(&bidder, 100), (&other, 900)] {
+        harness
+            .place_bid(bidder, &auction, amount)
+            .expect("place_bid failed");
+    }
+
+    // The first layout kept the submitted ciphertext, which may stand for more
+    // than was escrowed, here the other bidder's 900
+    let key = pda::bid(&auction, &bidder.pubkey(), None);
+    let placed = harness.bid_at(&key);
+    let claimed_amount = harness.bid(&auction, &other.pubkey()).bid_amount;
+    let data = legacy_data(
+        Bid::DISCRIMINATOR,
+        &[
+            &field(bidder.pubkey()),
+            &field(auction),
+            &field(claimed_amount),
+            &field(placed.time_stamp),
+            &field(0u128),
+            &field(false),
+            &field(placed.bid_bump),
+        ],
+        LEGACY_BID_LEN,
+    );
+    set_legacy_account(&mut harness, &key, data);
+    let payer = harness.organizer.insecure_clone();
+    harness
+        .send(
+            &[instructions::migrate_bid(&payer.pubkey(), &key)],
+            &[&payer],
+        )
+        .expect("migrate_bid failed");
+
+    harness.warp_to(end_time);
+    harness
+        .close_auction(&auction)
+        .expect("close_auction failed");
+    harness
+        .cancel_auction(&auction)
+        .expect("cancel_auction failed");
+    let state = harness.auction(&auction);
+    let refund = harness.send(
+        &[instructions::refund_bid(
+            &bidder.pubkey(),
+            &auction,
+            &state,
+            None,
+        )],
+        &[&bidder],
+    );
+    assert!(is_auction_error(&refund, AuctionError::UnprovenEscrow));
+    assert_eq!(harness.bid_balance(&bidder.pubkey()), 900);
+    assert_eq!(harness.bid_balance(&auction), 1_000);
+}
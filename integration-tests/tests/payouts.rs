@@ -0,0 +1,298 @@
+use anchor_spl::token::spl_token;
+use confidential_bid::{
+    error::AuctionError,
+    state::{AuctionPhase, AuctionStatus, AuctionType},
+};
+use confidential_bid_client::instructions;
+use confidential_bid_integration_tests::{is_auction_error, plaintext, Harness, PRIZE_SUPPLY};
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+const BALANCE: u64 = 1_000;
+
+/// Places `amounts` one second apart on a new auction and closes it after the end.
+/// Returns the bidders in bid order.
+fn closed_auction(
+    harness: &mut Harness,
+    auction_type: AuctionType,
+    reserve_price: u64,
+    amounts: &[u64],
+) -> (Pubkey, Vec<Keypair>) {
+    let start_time = harness.now() + 10;
+    let end_time = start_time + 100;
+    let auction = harness.create_auction(auction_type, start_time, end_time, reserve_price);
+
+    let mut bidders = Vec::new();
+    for (i, amount) in amounts.iter().enumerate() {
+        let bidder = harness.new_bidder(BALANCE);
+        harness.warp_to(start_time + i as i64);
+        harness
+            .place_bid(&bidder, &auction, *amount)
+            .expect("place_bid failed");
+        bidders.push(bidder);
+    }
+
+    harness.warp_to(end_time);
+    harness
+        .close_auction(&auction)
+        .expect("close_auction failed");
+    (auction, bidders)
+}
+
+#[test]
+fn vickrey_winner_claims_the_prize_and_losers_are_refunded() {
+    let mut harness = Harness::new();
+    let (auction, bidders) =
+        closed_auction(&mut harness, AuctionType::Vickrey, 0, &[500, 700, 300]);
+    harness
+        .resolve_winners(&auction, &bidders)
+        .expect("resolve_winners failed");
+    let winner = &bidders[1];
+
+    let refund = harness.refund_bid(winner, &auction);
+    assert!(is_auction_error(
+        &refund,
+        AuctionError::WinningBidNotRefundable
+    ));
+    let stolen = harness.claim_prize(&bidders[2], &auction);
+    assert!(is_auction_error(&stolen, AuctionError::NotWinner));
+
+    harness
+        .claim_prize(winner, &auction)
+        .expect("claim_prize failed");
+    assert_eq!(harness.prize_balance(&winner.pubkey()), 1);
+    // Pays the second highest bid, the rest of the 700 comes back
+    assert_eq!(harness.bid_balance(&winner.pubkey()), 500);
+    let again = harness.claim_prize(winner, &auction);
+    assert!(is_auction_error(&again, AuctionError::AlreadyClaimed));
+
+    harness
+        .refund_bid(&bidders[0], &auction)
+        .expect("refund_bid failed");
+    assert_eq!(
+        harness.bid_balance(&bidders[0].pubkey()),
+        u128::from(BALANCE)
+    );
+}
+
+#[test]
+fn underfunded_bid_escrows_and_refunds_nothing() {
+    let mut harness = Harness::new();
+    let start_time = harness.now() + 10;
+    let end_time = start_time + 100;
+    let auction = harness.create_auction(AuctionType::Vickrey, start_time, end_time, 0);
+    let winner = harness.new_bidder(BALANCE);
+    let underfunded = harness.new_bidder(100);
+    let loser = harness.new_bidder(BALANCE);
+    for (i, (bidder, amount)) in [(&winner, 500), (&underfunded, 800), (&loser, 300)]
+        .into_iter()
+        .enumerate()
+    {
+        harness.warp_to(start_time + i as i64);
+        harness
+            .place_bid(bidder, &auction, amount)
+            .expect("place_bid failed");
+    }
+
+    // The 800 never reached the vault, so the bid stands at zero
+    assert_eq!(
+        plaintext(harness.bid(&auction, &underfunded.pubkey()).bid_amount),
+        0
+    );
+    assert_eq!(harness.bid_balance(&underfunded.pubkey()), 100);
+    assert_eq!(harness.bid_balance(&auction), 800);
+
+    harness.warp_to(end_time);
+    harness
+        .close_auction(&auction)
+        .expect("close_auction failed");
+    let bidders = [winner, underfunded, loser];
+    harness
+        .resolve_winners(&auction, &bidders)
+        .expect("resolve_winners failed");
+    let [winner, underfunded, loser] = &bidders;
+
+    harness
+        .refund_bid(underfunded, &auction)
+        .expect("refund_bid failed");
+    assert_eq!(harness.bid_balance(&underfunded.pubkey()), 100);
+    harness
+        .claim_prize(winner, &auction)
+        .expect("claim_prize failed");
+    // Pays the escrowed second highest bid, not the unfunded 800
+    assert_eq!(harness.bid_balance(&winner.pubkey()), 700);
+    harness
+        .refund_bid(loser, &auction)
+        .expect("refund_bid failed");
+    assert_eq!(harness.bid_balance(&loser.pubkey()), u128::from(BALANCE));
+    harness
+        .settle_auction(&auction)
+        .expect("settle_auction failed");
+    assert_eq!(harness.bid_balance(&harness.organizer.pubkey()), 300);
+    assert_eq!(harness.bid_balance(&auction), 0);
+}
+
+#[test]
+fn equal_bids_in_the_same_second_leave_one_winner() {
+    let mut harness = Harness::new();
+    let start_time = harness.now() + 10;
+    let end_time = start_time + 100;
+    let auction = harness.create_auction(AuctionType::Normal, start_time, end_time, 0);
+    let bidders = [harness.new_bidder(BALANCE), harness.new_bidder(BALANCE)];
+    harness.warp_to(start_time);
+    for bidder in &bidders {
+        harness
+            .place_bid(bidder, &auction, 500)
+            .expect("place_bid failed");
+    }
+
+    harness.warp_to(end_time);
+    harness
+        .close_auction(&auction)
+        .expect("close_auction failed");
+    harness
+        .resolve_winners(&auction, &bidders)
+        .expect("resolve_winners failed");
+    let [first, second] = &bidders;
+    let won =
+        |bidder: &Keypair| plaintext(harness.bid(&auction, &bidder.pubkey()).is_winner_handle);
+    assert_eq!((won(first), won(second)), (1, 0));
+
+    harness
+        .claim_prize(first, &auction)
+        .expect("claim_prize failed");
+    assert_eq!(harness.prize_balance(&first.pubkey()), 1);
+    assert_eq!(harness.bid_balance(&first.pubkey()), 500);
+    harness
+        .refund_bid(second, &auction)
+        .expect("refund_bid failed");
+    assert_eq!(harness.bid_balance(&second.pubkey()), u128::from(BALANCE));
+    harness
+        .settle_auction(&auction)
+        .expect("settle_auction failed");
+    assert_eq!(harness.bid_balance(&harness.organizer.pubkey()), 500);
+    assert_eq!(harness.bid_balance(&auction), 0);
+}
+
+#[test]
+fn claims_need_an_attestation_from_the_attester() {
+    let mut harness = Harness::new();
+    let (auction, bidders) = closed_auction(&mut harness, AuctionType::Normal, 0, &[500]);
+    let bidder = &bidders[0];
+
+    let unresolved = harness.claim_prize(bidder, &auction);
+    assert!(is_auction_error(&unresolved, AuctionError::BidNotResolved));
+    harness
+        .resolve_winners(&auction, &bidders)
+        .expect("resolve_winners failed");
+
+    let state = harness.auction(&auction);
+    let handle = harness.bid(&auction, &bidder.pubkey()).is_winner_handle;
+    let claim = instructions::claim_prize(
+        &bidder.pubkey(),
+        &bidder.pubkey(),
+        &auction,
+        &state,
+        &spl_token::ID,
+        None,
+    );
+    let missing = harness.send(&[claim.clone()], &[bidder]);
+    assert!(is_auction_error(&missing, AuctionError::InvalidAttestation));
+
+    let forger = Keypair::new();
+    let forged = harness.attestation_by(&forger, handle, 1);
+    let forged = harness.send(&[forged, claim], &[bidder]);
+    assert!(is_auction_error(&forged, AuctionError::InvalidAttestation));
+
+    harness
+        .claim_prize(bidder, &auction)
+        .expect("claim_prize failed");
+    assert_eq!(harness.prize_balance(&bidder.pubkey()), 1);
+}
+
+#[test]
+fn unmet_reserve_cancels_the_auction_and_refunds_every_bid() {
+    let mut harness = Harness::new();
+    let (auction, bidders) = closed_auction(&mut harness, AuctionType::Normal, 600, &[500, 400]);
+    let organizer = harness.organizer.pubkey();
+    assert_eq!(harness.prize_balance(&organizer), PRIZE_SUPPLY - 1);
+
+    harness
+        .cancel_auction(&auction)
+        .expect("cancel_auction failed");
+    assert!(harness.auction(&auction).auction_status == AuctionStatus::Cancelled);
+    let summary = harness.auction_summary(&organizer, &auction);
+    assert!(summary.phase == AuctionPhase::Cancelled);
+    assert_eq!(harness.prize_balance(&organizer), PRIZE_SUPPLY);
+
+    // Refunds no longer wait for the winner check
+    for bidder in &bidders {
+        harness
+            .refund_bid(bidder, &auction)
+            .expect("refund_bid failed");
+        assert_eq!(harness.bid_balance(&bidder.pubkey()), u128::from(BALANCE));
+    }
+    let claim = harness.claim_prize(&bidders[0], &auction);
+    assert!(is_auction_error(&claim, AuctionError::AuctionNotClosed));
+}
+
+#[test]
+fn settled_auction_with_unmet_reserve_can_still_be_cancelled() {
+    let mut harness = Harness::new();
+    let (auction, bidders) = closed_auction(&mut harness, AuctionType::Vickrey, 600, &[500, 400]);
+    let organizer = harness.organizer.pubkey();
+    harness
+        .resolve_winners(&auction, &bidders)
+        .expect("resolve_winners failed");
+
+    // Settling pays out nothing and leaves the prize in escrow
+    harness
+        .settle_auction(&auction)
+        .expect("settle_auction failed");
+    assert_eq!(harness.bid_balance(&organizer), 0);
+    assert_eq!(harness.prize_balance(&organizer), PRIZE_SUPPLY - 1);
+
+    harness
+        .cancel_auction(&auction)
+        .expect("cancel_auction failed");
+    assert!(harness.auction(&auction).auction_status == AuctionStatus::Cancelled);
+    assert_eq!(harness.prize_balance(&organizer), PRIZE_SUPPLY);
+    for bidder in &bidders {
+        harness
+            .refund_bid(bidder, &auction)
+            .expect("refund_bid failed");
+        assert_eq!(harness.bid_balance(&bidder.pubkey()), u128::from(BALANCE));
+    }
+}
+
+#[test]
+fn met_reserve_cannot_be_cancelled() {
+    let mut harness = Harness::new();
+    let (auction, bidders) = closed_auction(&mut harness, AuctionType::Normal, 400, &[500]);
+
+    let cancel = harness.cancel_auction(&auction);
+    assert!(is_auction_error(&cancel, AuctionError::ReserveMet));
+
+    // Nor once settled, the proceeds are paid out by then
+    harness
+        .resolve_winners(&auction, &bidders)
+        .expect("resolve_winners failed");
+    harness
+        .settle_auction(&auction)
+        .expect("settle_auction failed");
+    let cancel = harness.cancel_auction(&auction);
+    assert!(is_auction_error(&cancel, AuctionError::ReserveMet));
+}
+
+#[test]
+fn only_the_upgrade_authority_sets_the_attester() {
+    let mut harness = Harness::new();
+    let stranger = harness.new_bidder(0);
+
+    let takeover = harness.set_attester(&stranger, &stranger.pubkey());
+    assert!(is_auction_error(&takeover, AuctionError::Unauthorized));
+
+    let authority = harness.authority.insecure_clone();
+    harness
+        .set_attester(&authority, &stranger.pubkey())
+        .expect("set_attester failed");
+}
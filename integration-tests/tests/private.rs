@@ -0,0 +1,85 @@
+//! Auctions created with `private_bids`, bid on by one-off bid keys.
+use confidential_bid::state::{AuctionType, Bid};
+use confidential_bid_client::{instructions, pda};
+use confidential_bid_integration_tests::Harness;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+#[test]
+fn private_bid_never_names_the_wallet() {
+    let mut harness = Harness::new();
+    let start_time = harness.now() + 10;
+    let auction =
+        harness.create_private_auction(AuctionType::Normal, start_time, start_time + 100, 0);
+    let wallet = Keypair::new();
+    let relayer = harness.new_bidder(0);
+    let (bid_key, bid_secret) = harness.new_bid_key(&wallet, &auction, 1_000);
+    harness.warp_to(start_time);
+
+    harness
+        .place_private_bid(&relayer, &bid_key, &bid_secret, &auction, 400)
+        .expect("place_bid failed");
+
+    let bid = harness.bid_at(&pda::bid(&auction, &bid_key.pubkey(), Some(&bid_secret)));
+    assert_eq!(bid.bidder, Pubkey::default());
+    assert_eq!(
+        bid.commitment,
+        Bid::commitment(&bid_key.pubkey(), &bid_secret)
+    );
+    assert_eq!(harness.bid_balance(&bid_key.pubkey()), 600);
+    // The wallet never touched the chain and the bid key never paid for anything
+    assert!(harness.svm.get_account(&wallet.pubkey()).is_none());
+    assert_eq!(harness.svm.get_balance(&bid_key.pubkey()).unwrap_or(0), 0);
+
+    // The wallet alone recovers the same key and secret
+    let (recovered, recovered_secret) = harness.new_bid_key(&wallet, &auction, 0);
+    assert_eq!(recovered.pubkey(), bid_key.pubkey());
+    assert_eq!(recovered_secret, bid_secret);
+}
+
+#[test]
+fn private_bids_claim_and_refund_with_their_secret() {
+    let mut harness = Harness::new();
+    let start_time = harness.now() + 10;
+    let end_time = start_time + 100;
+    let auction = harness.create_private_auction(AuctionType::Normal, start_time, end_time, 0);
+    let relayer = harness.new_bidder(0);
+
+    let mut bids = Vec::new();
+    for (i, amount) in [500, 300].into_iter().enumerate() {
+        let (bid_key, bid_secret) = harness.new_bid_key(&Keypair::new(), &auction, 1_000);
+        harness.warp_to(start_time + i as i64);
+        harness
+            .place_private_bid(&relayer, &bid_key, &bid_secret, &auction, amount)
+            .expect("place_bid failed");
+        bids.push((bid_key, bid_secret));
+    }
+    harness.warp_to(end_time);
+    harness
+        .close_auction(&auction)
+        .expect("close_auction failed");
+    let keys: Vec<Pubkey> = bids
+        .iter()
+        .map(|(bid_key, secret)| pda::bid(&auction, &bid_key.pubkey(), Some(secret)))
+        .collect();
+    harness
+        .resolve_private_bids(&auction, &keys)
+        .expect("resolve_winners failed");
+
+    let (winner, winner_secret) = &bids[0];
+    let (loser, loser_secret) = &bids[1];
+    // Another bid's secret does not open the bid
+    let state = harness.auction(&auction);
+    let wrong_secret =
+        instructions::refund_bid(&loser.pubkey(), &auction, &state, Some(winner_secret));
+    assert!(harness.send(&[wrong_secret], &[&relayer, loser]).is_err());
+
+    harness
+        .claim_private_prize(&relayer, winner, winner_secret, &auction)
+        .expect("claim_prize failed");
+    harness
+        .refund_private_bid(&relayer, loser, loser_secret, &auction)
+        .expect("refund_bid failed");
+    assert_eq!(harness.prize_balance(&winner.pubkey()), 1);
+    assert_eq!(harness.bid_balance(&loser.pubkey()), 1_000);
+    assert_eq!(harness.svm.get_balance(&winner.pubkey()).unwrap_or(0), 0);
+}
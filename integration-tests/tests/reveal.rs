@@ -0,0 +1,174 @@
+//! Auctions created with `reveal_on_settle`, whose clearing price and winning
+//! bid are published by `reveal_result` once settled.
+use confidential_bid::{error::AuctionError, state::AuctionType};
+use confidential_bid_client::{instructions, pda};
+use confidential_bid_integration_tests::{is_auction_error, Harness};
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+/// Places `amounts` one second apart, closes the auction and resolves every bid.
+/// Returns the bidders in bid order.
+fn resolved_auction(
+    harness: &mut Harness,
+    auction_type: AuctionType,
+    reserve_price: u64,
+    amounts: &[u64],
+    reveal_on_settle: bool,
+) -> (Pubkey, Vec<Keypair>) {
+    let start_time = harness.now() + 10;
+    let end_time = start_time + 100;
+    let auction = if reveal_on_settle {
+        harness.create_revealing_auction(auction_type, start_time, end_time, reserve_price)
+    } else {
+        harness.create_auction(auction_type, start_time, end_time, reserve_price)
+    };
+
+    let mut bidders = Vec::new();
+    for (i, amount) in amounts.iter().enumerate() {
+        let bidder = harness.new_bidder(1_000);
+        harness.warp_to(start_time + i as i64);
+        harness
+            .place_bid(&bidder, &auction, *amount)
+            .expect("place_bid failed");
+        bidders.push(bidder);
+    }
+
+    harness.warp_to(end_time);
+    harness
+        .close_auction(&auction)
+        .expect("close_auction failed");
+    harness
+        .resolve_winners(&auction, &bidders)
+        .expect("resolve_winners failed");
+    (auction, bidders)
+}
+
+#[test]
+fn vickrey_reveal_publishes_the_price_floored_at_the_reserve() {
+    let mut harness = Harness::new();
+    let (auction, bidders) = resolved_auction(
+        &mut harness,
+        AuctionType::Vickrey,
+        550,
+        &[500, 700, 300],
+        true,
+    );
+    let winner = &bidders[1];
+
+    let early = harness.reveal_result(&auction, winner);
+    assert!(is_auction_error(&early, AuctionError::AuctionNotSettled));
+    harness
+        .settle_auction(&auction)
+        .expect("settle_auction failed");
+
+    let loser = harness.reveal_result(&auction, &bidders[0]);
+    assert!(is_auction_error(&loser, AuctionError::NotWinner));
+    harness
+        .reveal_result(&auction, winner)
+        .expect("reveal_result failed");
+
+    let state = harness.auction(&auction);
+    assert_eq!(state.clearing_price, Some(550));
+    assert_eq!(
+        state.winning_bid,
+        Some(pda::bid(&auction, &winner.pubkey(), None))
+    );
+    let again = harness.reveal_result(&auction, winner);
+    assert!(is_auction_error(
+        &again,
+        AuctionError::ResultAlreadyRevealed
+    ));
+}
+
+#[test]
+fn first_price_reveal_publishes_the_highest_bid() {
+    let mut harness = Harness::new();
+    let (auction, bidders) =
+        resolved_auction(&mut harness, AuctionType::Normal, 0, &[300, 500, 400], true);
+    harness
+        .settle_auction(&auction)
+        .expect("settle_auction failed");
+
+    let winner = &bidders[1];
+
+    // The winning bid holds the price too, but it is not the handle settlement computed
+    let organizer = harness.organizer.insecure_clone();
+    let bid = harness.bid(&auction, &winner.pubkey());
+    assert_ne!(
+        bid.bid_amount,
+        harness.auction(&auction).clearing_price_handle
+    );
+    let ixs = [
+        harness.attestations(&[bid.is_winner_handle, bid.bid_amount]),
+        instructions::reveal_result(
+            &organizer.pubkey(),
+            &auction,
+            &pda::bid(&auction, &winner.pubkey(), None),
+        ),
+    ];
+    let other_handle = harness.send(&ixs, &[&organizer]);
+    assert!(is_auction_error(
+        &other_handle,
+        AuctionError::InvalidAttestation
+    ));
+
+    harness
+        .reveal_result(&auction, winner)
+        .expect("reveal_result failed");
+    assert_eq!(harness.auction(&auction).clearing_price, Some(500));
+}
+
+#[test]
+fn results_stay_confidential_without_the_flag() {
+    let mut harness = Harness::new();
+    let (auction, bidders) =
+        resolved_auction(&mut harness, AuctionType::Normal, 0, &[300, 500], false);
+    harness
+        .settle_auction(&auction)
+        .expect("settle_auction failed");
+
+    let reveal = harness.reveal_result(&auction, &bidders[1]);
+    assert!(is_auction_error(&reveal, AuctionError::RevealNotEnabled));
+    assert_eq!(harness.auction(&auction).clearing_price, None);
+}
+
+#[test]
+fn vickrey_reveal_attests_the_price_rather_than_the_standings() {
+    let mut harness = Harness::new();
+    let (auction, bidders) = resolved_auction(
+        &mut harness,
+        AuctionType::Vickrey,
+        0,
+        &[500, 700, 300],
+        true,
+    );
+    harness
+        .settle_auction(&auction)
+        .expect("settle_auction failed");
+    let winner = &bidders[1];
+    let state = harness.auction(&auction);
+
+    // The highest bid is not what the price is proven with
+    let attestations = harness.attestations(&[
+        harness.bid(&auction, &winner.pubkey()).is_winner_handle,
+        state.highest_bid,
+    ]);
+    let organizer = harness.organizer.insecure_clone();
+    let ixs = [
+        attestations,
+        instructions::reveal_result(
+            &organizer.pubkey(),
+            &auction,
+            &pda::bid(&auction, &winner.pubkey(), None),
+        ),
+    ];
+    let standings = harness.send(&ixs, &[&organizer]);
+    assert!(is_auction_error(
+        &standings,
+        AuctionError::InvalidAttestation
+    ));
+
+    harness
+        .reveal_result(&auction, winner)
+        .expect("reveal_result failed");
+    assert_eq!(harness.auction(&auction).clearing_price, Some(500));
+}
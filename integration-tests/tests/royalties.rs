@@ -0,0 +1,151 @@
+//! Creator royalties paid at settlement from the prize's Metaplex metadata.
+use confidential_bid::{error::AuctionError, state::AuctionType};
+use confidential_bid_client::{instructions, pda};
+use confidential_bid_integration_tests::{is_auction_error, Harness};
+use solana_sdk::{pubkey::Pubkey, signer::Signer};
+
+/// Resolved first-price auction where a bid of 500 beats one of 300
+fn resolved_auction(harness: &mut Harness) -> Pubkey {
+    resolved_shared_auction(harness, &[])
+}
+
+/// Same as [`resolved_auction`], sharing the proceeds with `payout_recipients`
+fn resolved_shared_auction(harness: &mut Harness, payout_recipients: &[(Pubkey, u16)]) -> Pubkey {
+    let start_time = harness.now() + 10;
+    let end_time = start_time + 100;
+    let auction = harness.create_shared_auction(
+        AuctionType::Normal,
+        start_time,
+        end_time,
+        0,
+        payout_recipients,
+    );
+
+    let mut bidders = Vec::new();
+    for (i, amount) in [500, 300].into_iter().enumerate() {
+        let bidder = harness.new_bidder(1_000);
+        harness.warp_to(start_time + i as i64);
+        harness
+            .place_bid(&bidder, &auction, amount)
+            .expect("place_bid failed");
+        bidders.push(bidder);
+    }
+    harness.warp_to(end_time);
+    harness
+        .close_auction(&auction)
+        .expect("close_auction failed");
+    harness
+        .resolve_winners(&auction, &bidders)
+        .expect("resolve_winners failed");
+    auction
+}
+
+#[test]
+fn settlement_pays_verified_creators_off_the_top() {
+    let mut harness = Harness::new();
+    let auction = resolved_auction(&mut harness);
+    let creators: Vec<Pubkey> = (0..3).map(|_| harness.new_bidder(0).pubkey()).collect();
+    // 10% royalty split 70/30, the unverified creator is never paid
+    harness.set_prize_metadata(
+        1_000,
+        &[
+            (creators[0], true, 70),
+            (creators[1], true, 30),
+            (creators[2], false, 0),
+        ],
+    );
+
+    harness
+        .settle_auction_paying(&auction, &creators[..2])
+        .expect("settle_auction failed");
+    assert_eq!(harness.bid_balance(&creators[0]), 35);
+    assert_eq!(harness.bid_balance(&creators[1]), 15);
+    assert_eq!(harness.bid_balance(&creators[2]), 0);
+    assert_eq!(harness.bid_balance(&harness.organizer.pubkey()), 450);
+}
+
+#[test]
+fn organizer_cannot_skip_royalties() {
+    let mut harness = Harness::new();
+    let auction = resolved_auction(&mut harness);
+    let creator = harness.new_bidder(0).pubkey();
+    harness.set_prize_metadata(500, &[(creator, true, 100)]);
+
+    let without_creators = harness.settle_auction(&auction);
+    assert!(is_auction_error(
+        &without_creators,
+        AuctionError::InvalidRoyaltyCreator
+    ));
+
+    // Swapping the metadata account for any other one is refused
+    let organizer = harness.organizer.insecure_clone();
+    let state = harness.auction(&auction);
+    let mut settle = instructions::settle_auction(&organizer.pubkey(), &auction, &state, &[]);
+    let metadata = pda::prize_metadata(&state.mint);
+    settle
+        .accounts
+        .iter_mut()
+        .filter(|meta| meta.pubkey == metadata)
+        .for_each(|meta| meta.pubkey = Pubkey::new_unique());
+    let swapped = harness.send(&[settle], &[&organizer]);
+    assert!(is_auction_error(
+        &swapped,
+        AuctionError::InvalidPrizeMetadata
+    ));
+
+    harness
+        .settle_auction_paying(&auction, &[creator])
+        .expect("settle_auction failed");
+    assert_eq!(harness.bid_balance(&creator), 25);
+}
+
+#[test]
+fn settlement_pays_royalties_then_recipients_then_the_organizer() {
+    let mut harness = Harness::new();
+    let recipients: Vec<Pubkey> = (0..3).map(|_| harness.new_bidder(0).pubkey()).collect();
+    let auction = resolved_shared_auction(
+        &mut harness,
+        &[
+            (recipients[0], 2_000),
+            (recipients[1], 1_500),
+            (recipients[2], 500),
+        ],
+    );
+    let creators: Vec<Pubkey> = (0..2).map(|_| harness.new_bidder(0).pubkey()).collect();
+    harness.set_prize_metadata(1_000, &[(creators[0], true, 70), (creators[1], true, 30)]);
+
+    // Recipient accounts out of auction order are refused
+    let organizer = harness.organizer.insecure_clone();
+    let state = harness.auction(&auction);
+    let mut settle = instructions::settle_auction(&organizer.pubkey(), &auction, &state, &creators);
+    let first = settle.accounts.len() - creators.len() - recipients.len();
+    settle.accounts.swap(first, first + 1);
+    let reordered = harness.send(&[settle], &[&organizer]);
+    assert!(is_auction_error(
+        &reordered,
+        AuctionError::InvalidPayoutRecipient
+    ));
+
+    // Nor is any other account in place of a recipient's Inco token account
+    let mut settle = instructions::settle_auction(&organizer.pubkey(), &auction, &state, &creators);
+    settle.accounts[first + 2].pubkey = pda::inco_ata(&organizer.pubkey(), &state.bid_token_mint);
+    let replaced = harness.send(&[settle], &[&organizer]);
+    assert!(is_auction_error(
+        &replaced,
+        AuctionError::InvalidPayoutRecipient
+    ));
+
+    harness
+        .settle_auction_paying(&auction, &creators)
+        .expect("settle_auction failed");
+    // 10% of 500 goes to the creators, the recipients share the other 450
+    assert_eq!(harness.bid_balance(&creators[0]), 35);
+    assert_eq!(harness.bid_balance(&creators[1]), 15);
+    assert_eq!(harness.bid_balance(&recipients[0]), 90);
+    assert_eq!(harness.bid_balance(&recipients[1]), 67);
+    assert_eq!(harness.bid_balance(&recipients[2]), 22);
+    // The organizer gets the rest, rounding included
+    assert_eq!(harness.bid_balance(&harness.organizer.pubkey()), 271);
+    // Only the losing bid is left in the vault
+    assert_eq!(harness.bid_balance(&auction), 300);
+}
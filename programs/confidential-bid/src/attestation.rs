@@ -0,0 +1,94 @@
+//! Plaintexts of Inco handles, proven by an attester signature.
+//!
+//! The attester signs [`attested_message`], the handle followed by its
+//! plaintext. Clients put that signature in an Ed25519 program instruction
+//! right before the instruction consuming it, which reads it back through the
+//! instructions sysvar. The Ed25519 program fails the transaction on a bad
+//! signature, so only the signer and the message are checked here.
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        ed25519_program,
+        sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+    },
+};
+use inco_lightning::{Ebool, Euint128};
+
+use crate::error::AuctionError;
+
+/// Offsets follow the signature count and a padding byte
+const OFFSETS_START: usize = 2;
+const OFFSETS_LEN: usize = 14;
+/// `*_instruction_index` value pointing at the Ed25519 instruction itself
+const THIS_INSTRUCTION: u16 = u16::MAX;
+pub const ATTESTED_MESSAGE_LEN: usize = 32;
+
+/// Message signed for `handle` decrypting to `plaintext`, both little-endian
+pub fn attested_message(handle: u128, plaintext: u128) -> [u8; ATTESTED_MESSAGE_LEN] {
+    let mut message = [0; ATTESTED_MESSAGE_LEN];
+    message[..16].copy_from_slice(&handle.to_le_bytes());
+    message[16..].copy_from_slice(&plaintext.to_le_bytes());
+    message
+}
+
+/// Decrypted value of a boolean handle
+pub fn verify_ebool(instructions: &AccountInfo, attester: &Pubkey, handle: Ebool) -> Result<bool> {
+    verify_plaintext(instructions, attester, handle.0).map(|plaintext| plaintext != 0)
+}
+
+/// Decrypted value of an integer handle
+pub fn verify_euint128(
+    instructions: &AccountInfo,
+    attester: &Pubkey,
+    handle: Euint128,
+) -> Result<u128> {
+    verify_plaintext(instructions, attester, handle.0)
+}
+
+/// Plaintext `attester` signed for `handle` in the Ed25519 instruction just
+/// before the current one
+fn verify_plaintext(instructions: &AccountInfo, attester: &Pubkey, handle: u128) -> Result<u128> {
+    let current = load_current_index_checked(instructions)?;
+    let previous = current
+        .checked_sub(1)
+        .ok_or(AuctionError::InvalidAttestation)?;
+    let ix = load_instruction_at_checked(previous as usize, instructions)?;
+    require_keys_eq!(
+        ix.program_id,
+        ed25519_program::ID,
+        AuctionError::InvalidAttestation
+    );
+
+    let data = ix.data.as_slice();
+    let count = *data.first().ok_or(AuctionError::InvalidAttestation)? as usize;
+    for i in 0..count {
+        let start = OFFSETS_START + i * OFFSETS_LEN;
+        let offsets = data
+            .get(start..start + OFFSETS_LEN)
+            .ok_or(AuctionError::InvalidAttestation)?;
+        let field = |at: usize| u16::from_le_bytes([offsets[at], offsets[at + 1]]);
+
+        // Offsets into other instructions were verified against bytes we do not read
+        if [field(2), field(6), field(12)] != [THIS_INSTRUCTION; 3] {
+            continue;
+        }
+        let pubkey = slice(data, field(4), 32)?;
+        let message = slice(data, field(8), field(10))?;
+        if pubkey != attester.as_ref() || message.len() != ATTESTED_MESSAGE_LEN {
+            continue;
+        }
+        if message[..16] == handle.to_le_bytes() {
+            let mut plaintext = [0; 16];
+            plaintext.copy_from_slice(&message[16..]);
+            return Ok(u128::from_le_bytes(plaintext));
+        }
+    }
+
+    err!(AuctionError::InvalidAttestation)
+}
+
+fn slice(data: &[u8], offset: u16, len: u16) -> Result<&[u8]> {
+    let start = offset as usize;
+    data.get(start..start + len as usize)
+        .ok_or_else(|| AuctionError::InvalidAttestation.into())
+}
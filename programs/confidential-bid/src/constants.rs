@@ -1,2 +1,35 @@
+use anchor_lang::prelude::*;
+
 pub const AUCTION_SEED: &[u8] = b"auction";
 pub const BID_SEED: &[u8] = b"bid";
+pub const ORGANIZER_SEED: &[u8] = b"organizer";
+pub const METADATA_SEED: &[u8] = b"metadata";
+pub const REDUCTION_SEED: &[u8] = b"reduction";
+pub const ATTESTER_SEED: &[u8] = b"attester";
+pub const MAX_PAYOUT_RECIPIENTS: usize = 4;
+pub const BPS_DENOMINATOR: u16 = 10_000;
+/// `input_type` telling the Inco Token program the amount is an existing handle, not a ciphertext
+pub const HANDLE_INPUT_TYPE: u8 = 1;
+/// Upper bound on how far the organizer may push back `end_time` over the auction's life
+pub const MAX_TOTAL_EXTENSION: i64 = 7 * 24 * 60 * 60;
+pub const MIN_BIDS_TO_END_EARLY: u32 = 2;
+/// Current `AuctionState` layout, older accounts are brought up to it by `migrate_auction`
+pub const AUCTION_STATE_VERSION: u8 = 2;
+/// Current `Bid` layout, older accounts are brought up to it by `migrate_bid`
+pub const BID_VERSION: u8 = 1;
+/// Version of bids migrated from the first layout, whose `bid_amount` is the
+/// submitted ciphertext rather than the escrowed amount, so they can neither be
+/// refunded nor claim the prize
+pub const LEGACY_BID_VERSION: u8 = 0;
+pub const AUCTION_STATE_RESERVED: usize = 26;
+pub const BID_RESERVED: usize = 18;
+/// Allowed to decrypt every bid amount of auctions created with `disclose_bids`.
+/// Its secret key is `sha256("confidential-bid:disclosure")`, published so
+/// anyone can have Inco decrypt those amounts.
+pub const DISCLOSURE_ADDRESS: Pubkey = pubkey!("3eJ39DNPF6R5sjzGS83dzkENW5wtC8pqCaUyYMixUV1J");
+/// Schema version carried by every event
+pub const EVENT_VERSION: u8 = 1;
+/// Layout version of the view results, bumped whenever a field is added
+pub const VIEW_VERSION: u8 = 2;
+pub const TOKEN_METADATA_PROGRAM_ID: Pubkey =
+    pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
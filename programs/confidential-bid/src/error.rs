@@ -1,4 +1,7 @@
+//! Error codes and messages are public, none of them include amounts or handles.
 use anchor_lang::prelude::error_code;
+use confidential_bid_core::RuleError;
+
 #[error_code]
 pub enum AuctionError {
     #[msg("Invalid start time")]
@@ -35,4 +38,108 @@ pub enum AuctionError {
     AuctionNotEnded,
     #[msg("Auction must be closed")]
     AuctionNotClosed,
+    #[msg("Too many payout recipients")]
+    TooManyPayoutRecipients,
+    #[msg("Invalid payout share")]
+    InvalidPayoutShare,
+    #[msg("Invalid payout recipient account")]
+    InvalidPayoutRecipient,
+    #[msg("Invalid prize metadata account")]
+    InvalidPrizeMetadata,
+    #[msg("Invalid royalty creator account")]
+    InvalidRoyaltyCreator,
+    #[msg("Auction has already started")]
+    AuctionAlreadyStarted,
+    #[msg("Auction already has bids")]
+    AuctionHasBids,
+    #[msg("Extension exceeds the maximum allowed")]
+    ExtensionTooLong,
+    #[msg("Not enough bids to end the auction early")]
+    NotEnoughBids,
+    #[msg("Allowance account does not match the handle and allowed address")]
+    InvalidAllowanceAccount,
+    #[msg("Handle does not belong to this auction or bid")]
+    UnknownHandle,
+    #[msg("Auditor accounts are required for this auction")]
+    MissingAuditorAccounts,
+    #[msg("Auditor does not match the auction")]
+    InvalidAuditor,
+    #[msg("Bid count is hidden for this auction")]
+    BidCountHidden,
+    #[msg("Bid commitment must be given exactly for private auctions")]
+    InvalidBidCommitment,
+    #[msg("Account already uses the current layout")]
+    AccountAlreadyMigrated,
+    #[msg("Account is not a legacy account of this program")]
+    InvalidLegacyAccount,
+    #[msg("Auction does not defer its standings")]
+    StandingsNotDeferred,
+    #[msg("Bid is not the next one to reduce")]
+    BidOutOfOrder,
+    #[msg("Reductions do not cover consecutive bids")]
+    ReductionGap,
+    #[msg("Reduction does not cover every bid")]
+    ReductionIncomplete,
+    #[msg("Bids must be reduced before closing")]
+    StandingsNotReduced,
+    #[msg("Every bid must be resolved before settlement")]
+    WinnersNotResolved,
+    #[msg("Remaining accounts do not match the bids to resolve")]
+    InvalidResolveAccounts,
+    #[msg("Missing or invalid decryption attestation")]
+    InvalidAttestation,
+    #[msg("Winner check has not run for this bid")]
+    BidNotResolved,
+    #[msg("Bid did not win the auction")]
+    NotWinner,
+    #[msg("Winning bid cannot be refunded")]
+    WinningBidNotRefundable,
+    #[msg("Reserve price was met")]
+    ReserveMet,
+    #[msg("Already claimed")]
+    AlreadyClaimed,
+    #[msg("Auction must be settled")]
+    AuctionNotSettled,
+    #[msg("Auction does not reveal its result")]
+    RevealNotEnabled,
+    #[msg("Result already revealed")]
+    ResultAlreadyRevealed,
+    #[msg("Auction does not disclose its bids")]
+    DisclosureNotEnabled,
+    #[msg("Disclosure accounts must pair each bid with its allowance")]
+    InvalidDisclosureAccounts,
+    #[msg("Bid already revealed")]
+    BidAlreadyRevealed,
+    #[msg("Allowance account missing for a requested grant")]
+    MissingAllowanceAccount,
+    #[msg("The auditor's access cannot be revoked")]
+    AuditorAccessLocked,
+    #[msg("Every bid must be disclosed before any is revealed")]
+    DisclosureIncomplete,
+    #[msg("Bid migrated from the first layout has no proven escrow")]
+    UnprovenEscrow,
+    #[msg("Standings are already final")]
+    StandingsFinal,
+}
+
+impl From<RuleError> for AuctionError {
+    fn from(error: RuleError) -> Self {
+        match error {
+            RuleError::AuctionNotOpen => AuctionError::AuctionNotOpen,
+            RuleError::AuctionNotStarted => AuctionError::AuctionNotStarted,
+            RuleError::AuctionEnded => AuctionError::AuctionEnded,
+            RuleError::AuctionNotEnded => AuctionError::AuctionNotEnded,
+            RuleError::AuctionNotClosed => AuctionError::AuctionNotClosed,
+            RuleError::AuctionNotSettled => AuctionError::AuctionNotSettled,
+            RuleError::AuctionAlreadyStarted => AuctionError::AuctionAlreadyStarted,
+            RuleError::AuctionHasBids => AuctionError::AuctionHasBids,
+            RuleError::InvalidStartTime => AuctionError::InvalidStartTime,
+            RuleError::InvalidEndTime => AuctionError::InvalidEndTime,
+            RuleError::ExtensionTooLong => AuctionError::ExtensionTooLong,
+            RuleError::BidCountHidden => AuctionError::BidCountHidden,
+            RuleError::NotEnoughBids => AuctionError::NotEnoughBids,
+            RuleError::WinnersNotResolved => AuctionError::WinnersNotResolved,
+            RuleError::MathOverflow => AuctionError::MathOverflow,
+        }
+    }
 }
@@ -1,9 +1,21 @@
+//! Events are emitted with `emit_cpi!` so they land in instruction data,
+//! which unlike program logs is never truncated. Handlers return their events
+//! and the instruction entrypoints emit them, where `ctx` is in scope.
+//!
+//! They only carry data that is already public in account data (ids, keys,
+//! times, the bid count unless hidden and the handles stored on the bid
+//! account). Winner and reserve handles are never emitted, so log observers
+//! cannot learn them before the owners do. `BidPlaced` is emitted for every
+//! bid, so hiding the count keeps it out of account data and events but not
+//! out of reach of anyone counting events. Every event starts with
+//! `version`, set to `EVENT_VERSION`.
 use anchor_lang::prelude::*;
 
 use crate::state::AuctionType;
 
 #[event]
 pub struct AuctionCreated {
+    pub version: u8,
     pub auction_id: u64,
     pub organizer: Pubkey,
     pub mint: Pubkey,
@@ -14,11 +26,123 @@ pub struct AuctionCreated {
     pub reserve_price: u64,
     pub auction_type: AuctionType,
     pub bid_token_mint: Pubkey,
+    pub auditor: Option<Pubkey>,
+    /// Only `bid_count` is hidden, each bid still emits `BidPlaced` and owns a
+    /// `Bid` account naming the auction, which together give the count away
+    pub hide_bid_count: bool,
+    pub private_bids: bool,
+}
+
+#[event]
+pub struct AuctionUpdated {
+    pub version: u8,
+    pub auction_id: u64,
+    pub organizer: Pubkey,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub reserve_price: u64,
+    pub auction_type: AuctionType,
+}
+
+#[event]
+pub struct AuctionExtended {
+    pub version: u8,
+    pub auction_id: u64,
+    pub organizer: Pubkey,
+    pub previous_end_time: i64,
+    pub new_end_time: i64,
+}
+
+#[event]
+pub struct BidPlaced {
+    pub version: u8,
+    pub auction: Pubkey,
+    pub bid: Pubkey,
+    pub bid_amount_handle: u128,
+    pub bid_count: Option<u32>, // None when the auction hides its bid count
+    pub slot: u64,
+}
+
+#[event]
+pub struct AuctionEndedEarly {
+    pub version: u8,
+    pub auction_id: u64,
+    pub organizer: Pubkey,
+    pub scheduled_end_time: i64,
+    pub bid_count: u32,
+    pub timestamp: i64,
 }
 
 #[event]
 pub struct AuctionClosed {
+    pub version: u8,
     pub auction_id: u64,
     pub organizer: Pubkey,
     pub timestamp: i64,
 }
+
+#[event]
+pub struct WinnerChecked {
+    pub version: u8,
+    pub auction: Pubkey,
+    pub bid: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuctionSettled {
+    pub version: u8,
+    pub auction_id: u64,
+    pub organizer: Pubkey,
+    pub recipient_count: u8,
+    pub creator_count: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PrizeClaimed {
+    pub version: u8,
+    pub auction: Pubkey,
+    pub bid: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BidRefunded {
+    pub version: u8,
+    pub auction: Pubkey,
+    pub bid: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuctionCancelled {
+    pub version: u8,
+    pub auction_id: u64,
+    pub organizer: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Published by `reveal_result` on auctions created with `reveal_on_settle`
+#[event]
+pub struct AuctionResultRevealed {
+    pub version: u8,
+    pub auction_id: u64,
+    pub organizer: Pubkey,
+    pub winning_bid: Pubkey,
+    pub winner: Pubkey, // Default key for private bids, as on the bid account
+    pub clearing_price: u64,
+    pub timestamp: i64,
+}
+
+/// Published by `reveal_bid` on auctions created with `disclose_bids`
+#[event]
+pub struct BidRevealed {
+    pub version: u8,
+    pub auction: Pubkey,
+    pub bid: Pubkey,
+    pub sequence: u32,
+    pub amount: u64,
+    pub timestamp: i64,
+}
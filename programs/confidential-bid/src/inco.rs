@@ -0,0 +1,153 @@
+use anchor_lang::prelude::*;
+use confidential_bid_core::EncryptedOps;
+use inco_lightning::{
+    cpi::{
+        allow, as_euint128, e_add, e_and, e_div, e_eq, e_ge, e_gt, e_mul, e_select, e_sub, Allow,
+        Operation,
+    },
+    Ebool, Euint128, ID as INCO_LIGHTNING_ID,
+};
+pub use inco_token::ID as INCO_TOKEN_ID;
+
+use crate::error::AuctionError;
+
+/// Inco associated token account of `wallet` for `mint`
+pub fn inco_ata_address(wallet: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[wallet.as_ref(), INCO_TOKEN_ID.as_ref(), mint.as_ref()],
+        &INCO_TOKEN_ID,
+    )
+    .0
+}
+
+/// Allowance PDA the Inco Lightning program uses to record that `allowed` may decrypt `handle`
+pub fn allowance_address(handle: u128, allowed: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[handle.to_le_bytes().as_ref(), allowed.as_ref()],
+        &INCO_LIGHTNING_ID,
+    )
+    .0
+}
+
+/// Grants or revokes decryption of `handle` for `allowed_address`.
+///
+/// The allowance account is checked against its expected derivation first, so
+/// a wrong account fails the instruction instead of silently granting nothing.
+pub fn set_allowance<'info>(
+    inco_lightning_program: &AccountInfo<'info>,
+    signer: &AccountInfo<'info>,
+    allowance_account: &AccountInfo<'info>,
+    allowed_address: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    handle: u128,
+    value: bool,
+) -> Result<()> {
+    require_keys_eq!(
+        allowance_account.key(),
+        allowance_address(handle, allowed_address.key),
+        AuctionError::InvalidAllowanceAccount
+    );
+
+    let cpi_ctx = CpiContext::new(
+        inco_lightning_program.clone(),
+        Allow {
+            allowance_account: allowance_account.clone(),
+            signer: signer.clone(),
+            allowed_address: allowed_address.clone(),
+            system_program: system_program.clone(),
+        },
+    );
+    allow(cpi_ctx, handle, value, allowed_address.key())
+}
+
+/// [`EncryptedOps`] over Inco Lightning CPIs, each one signed by `signer`
+pub struct IncoOps<'info> {
+    program: AccountInfo<'info>,
+    signer: AccountInfo<'info>,
+    scalar_byte: u8,
+}
+
+impl<'info> IncoOps<'info> {
+    pub fn new(program: AccountInfo<'info>, signer: AccountInfo<'info>) -> Self {
+        Self {
+            program,
+            signer,
+            scalar_byte: 0,
+        }
+    }
+
+    /// Passes `scalar_byte` to every binary operation instead of 0
+    pub fn with_scalar_byte(self, scalar_byte: u8) -> Self {
+        Self {
+            scalar_byte,
+            ..self
+        }
+    }
+
+    fn ctx(&self) -> CpiContext<'_, '_, '_, 'info, Operation<'info>> {
+        CpiContext::new(
+            self.program.clone(),
+            Operation {
+                signer: self.signer.clone(),
+            },
+        )
+    }
+}
+
+impl<'info> EncryptedOps for IncoOps<'info> {
+    type Uint = Euint128;
+    type Bool = Ebool;
+    type Error = Error;
+
+    fn constant(&mut self, value: u128) -> Result<Euint128> {
+        as_euint128(self.ctx(), value)
+    }
+
+    fn gt(&mut self, lhs: Euint128, rhs: Euint128) -> Result<Ebool> {
+        e_gt(self.ctx(), lhs, rhs, self.scalar_byte)
+    }
+
+    fn ge(&mut self, lhs: Euint128, rhs: Euint128) -> Result<Ebool> {
+        e_ge(self.ctx(), lhs, rhs, self.scalar_byte)
+    }
+
+    fn eq(&mut self, lhs: Euint128, rhs: Euint128) -> Result<Ebool> {
+        e_eq(self.ctx(), lhs, rhs, self.scalar_byte)
+    }
+
+    fn and(&mut self, lhs: Ebool, rhs: Ebool) -> Result<Ebool> {
+        // Inco only defines `and` over integers, booleans are 0 or 1 handles
+        let result = e_and(
+            self.ctx(),
+            Euint128(lhs.0),
+            Euint128(rhs.0),
+            self.scalar_byte,
+        )?;
+        Ok(Ebool(result.0))
+    }
+
+    fn select(
+        &mut self,
+        control: Ebool,
+        if_true: Euint128,
+        if_false: Euint128,
+    ) -> Result<Euint128> {
+        e_select(self.ctx(), control, if_true, if_false, self.scalar_byte)
+    }
+
+    fn add(&mut self, lhs: Euint128, rhs: Euint128) -> Result<Euint128> {
+        e_add(self.ctx(), lhs, rhs, self.scalar_byte)
+    }
+
+    fn sub(&mut self, lhs: Euint128, rhs: Euint128) -> Result<Euint128> {
+        e_sub(self.ctx(), lhs, rhs, self.scalar_byte)
+    }
+
+    fn mul(&mut self, lhs: Euint128, rhs: Euint128) -> Result<Euint128> {
+        e_mul(self.ctx(), lhs, rhs, self.scalar_byte)
+    }
+
+    fn div(&mut self, lhs: Euint128, rhs: Euint128) -> Result<Euint128> {
+        e_div(self.ctx(), lhs, rhs, self.scalar_byte)
+    }
+}
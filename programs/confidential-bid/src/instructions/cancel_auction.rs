@@ -0,0 +1,109 @@
+use anchor_lang::{prelude::*, solana_program::sysvar::instructions as instructions_sysvar};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+use confidential_bid_core as rules;
+use inco_lightning::Ebool;
+
+use crate::{
+    attestation::verify_ebool,
+    constants::{ATTESTER_SEED, AUCTION_SEED, EVENT_VERSION},
+    error::AuctionError,
+    events::AuctionCancelled,
+    state::{AttesterConfig, AuctionState},
+    vault::transfer_prize,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelAuction<'info> {
+    #[account(mut)]
+    pub organizer: Signer<'info>,
+
+    /// - Auction state PDA
+    #[account(
+        mut,
+        seeds=[
+            AUCTION_SEED,
+            organizer.key().as_ref(),
+            auction.auction_id.to_le_bytes().as_ref()
+        ],
+        bump = auction.auction_bump,
+    )]
+    pub auction: Account<'info, AuctionState>,
+
+    #[account(seeds = [ATTESTER_SEED], bump = attester_config.bump)]
+    pub attester_config: Account<'info, AttesterConfig>,
+
+    /// CHECK: instructions sysvar holding the Ed25519 attestation of the reserve handle
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    #[account(address = auction.mint @ AuctionError::InvalidAuction)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, address = auction.vault @ AuctionError::InvalidAuction)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Organizer's token account getting the prize back
+    #[account(
+        init_if_needed,
+        payer = organizer,
+        associated_token::mint = mint,
+        associated_token::authority = organizer,
+        associated_token::token_program = token_program,
+    )]
+    pub organizer_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CancelAuction<'info> {
+    /// Calls off a closed auction whose reserve was not met, proven by an
+    /// attested decryption of the reserve handle, and returns the prize to
+    /// the organizer. Bidders then take back their bids with `refund_bid`.
+    ///
+    /// A settled auction can still be called off this way, settling it with
+    /// the reserve unmet paid out nothing and left the prize in escrow.
+    pub fn handler(&mut self) -> Result<AuctionCancelled> {
+        let cancelled =
+            rules::cancel((&self.auction.auction_status).into()).map_err(AuctionError::from)?;
+        require!(
+            self.organizer.key() == self.auction.organizer,
+            AuctionError::Unauthorized
+        );
+        require!(!self.auction.prize_claimed, AuctionError::AlreadyClaimed);
+
+        // An auction known to have no bids has nobody to win it, whatever the reserve
+        let no_bids = !self.auction.hide_bid_count && self.auction.bid_count == 0;
+        if !no_bids {
+            let reserve_met = verify_ebool(
+                &self.instructions,
+                &self.attester_config.attester,
+                Ebool(self.auction.reserve_met_handle),
+            )?;
+            require!(!reserve_met, AuctionError::ReserveMet);
+        }
+
+        transfer_prize(
+            &self.auction,
+            &self.mint.to_account_info(),
+            &self.vault.to_account_info(),
+            &self.organizer_token_account.to_account_info(),
+            &self.token_program.to_account_info(),
+            self.vault.amount,
+            self.mint.decimals,
+        )?;
+        self.auction.auction_status = cancelled.into();
+
+        Ok(AuctionCancelled {
+            version: EVENT_VERSION,
+            auction_id: self.auction.auction_id,
+            organizer: self.organizer.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        })
+    }
+}
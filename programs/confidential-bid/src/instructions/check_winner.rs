@@ -1,18 +1,25 @@
 use anchor_lang::prelude::*;
-use inco_lightning::{
-    cpi::{allow, e_and, e_eq, Allow, Operation},
-    Ebool, Euint128, IncoLightning, ID as INCO_LIGHTNING_ID,
-};
+use confidential_bid_core as rules;
+use inco_lightning::{Ebool, Euint128, IncoLightning, ID as INCO_LIGHTNING_ID};
 
 use crate::{
-    constants::{AUCTION_SEED, BID_SEED},
+    constants::{AUCTION_SEED, BID_SEED, EVENT_VERSION},
     error::AuctionError,
-    state::{AuctionState, AuctionStatus, Bid},
+    events::WinnerChecked,
+    inco::{set_allowance, IncoOps, INCO_TOKEN_ID},
+    returns::CheckWinnerResult,
+    state::{AuctionState, Bid},
 };
 
+#[event_cpi]
 #[derive(Accounts)]
+#[instruction(input_type: u8, bid_secret: Option<[u8; 32]>)]
 pub struct CheckWinner<'info> {
+    /// Pays the Inco operations and allowances, the bidder itself or a
+    /// relayer for private bids
     #[account(mut)]
+    pub payer: Signer<'info>,
+
     pub bidder: Signer<'info>,
 
     #[account(
@@ -20,9 +27,13 @@ pub struct CheckWinner<'info> {
             seeds = [
                 BID_SEED,
                 auction.key().as_ref(),
-                bidder.key().as_ref()
+                Bid::seed_key(
+                    &bidder.key(),
+                    bid_secret.map(|secret| Bid::commitment(&bidder.key(), &secret))
+                ).as_ref()
             ],
-            bump
+            bump,
+            constraint = bid.auction == auction.key() @ AuctionError::InvalidAuction
         )]
     pub bid: Account<'info, Bid>,
 
@@ -38,7 +49,13 @@ pub struct CheckWinner<'info> {
     )]
     pub auction: Account<'info, AuctionState>,
 
+    /// CHECK: Inco allowance PDA for the winner handle, derivation checked on-chain,
+    /// required with `with_allowances`
+    #[account(mut)]
+    pub winner_allowance: Option<UncheckedAccount<'info>>,
+
     /// CHECK: Inco Token program
+    #[account(address = INCO_TOKEN_ID)]
     pub inco_token_program: AccountInfo<'info>,
 
     pub system_program: Program<'info, System>,
@@ -49,94 +66,83 @@ pub struct CheckWinner<'info> {
 }
 
 impl<'info> CheckWinner<'info> {
+    /// `with_allowances` grants the bidder its winner handle, which needs
+    /// `winner_allowance`; simulations that only learn the handle leave it unset.
     pub fn handler(
         &mut self,
         input_type: u8,
-        remaining_accounts: &[AccountInfo<'info>],
-    ) -> Result<()> {
-        let auction = &self.auction;
-        let bid = &mut self.bid;
+        bid_secret: Option<[u8; 32]>,
+        with_allowances: bool,
+    ) -> Result<(CheckWinnerResult, WinnerChecked)> {
         let inco = self.inco_lightning_program.to_account_info();
+        let signer = self.payer.to_account_info();
 
-        let signer = self.bidder.to_account_info();
-        // Auction must be closed
+        rules::check_winner_allowed((&self.auction.auction_status).into())
+            .map_err(AuctionError::from)?;
+        // Private bids prove ownership with the secret behind their commitment
         require!(
-            auction.auction_status == AuctionStatus::Closed,
-            AuctionError::AuctionNotClosed
+            self.bid
+                .is_owned_by(&self.bidder.key(), bid_secret.as_ref()),
+            AuctionError::Unauthorized
         );
 
-        // encrypted  bid_amount == highest_bid
-
-        let is_highest: Ebool = e_eq(
-            CpiContext::new(
-                inco.clone(),
-                Operation {
-                    signer: signer.clone(),
-                },
-            ),
-            Euint128(bid.bid_amount),
-            Euint128(self.auction.highest_bid),
-            input_type,
-        )?;
-        // encrypted  timestamp == highest_timestamp
-        let is_earliest: Ebool = e_eq(
-            CpiContext::new(
-                inco.clone(),
-                Operation {
-                    signer: signer.clone(),
-                },
-            ),
-            Euint128(bid.time_stamp),
-            Euint128(auction.highest_timestamp),
-            input_type,
-        )?;
-
-        // Convert bool handles to Euint128
-        let is_highest_u = Euint128(is_highest.0);
-        let is_earliest_u = Euint128(is_earliest.0);
-        let reserve_met_u = Euint128(self.auction.reserve_met_handle);
-
-        let highest_and_earliest: Euint128 = e_and(
-            CpiContext::new(
-                inco.clone(),
-                Operation {
-                    signer: signer.clone(),
-                },
-            ),
-            is_highest_u,
-            is_earliest_u,
-            input_type,
-        )?;
-
-        let is_winner = e_and(
-            CpiContext::new(
-                inco.clone(),
-                Operation {
-                    signer: signer.clone(),
-                },
-            ),
-            highest_and_earliest,
-            reserve_met_u,
-            0,
-        )?;
-
-        self.bid.is_winner_handle = is_winner.0;
-
-        if remaining_accounts.len() >= 2 {
-            let allow_ctx = CpiContext::new(
-                inco.clone(),
-                Allow {
-                    allowance_account: remaining_accounts[0].clone(),
-                    signer: self.bidder.to_account_info(),
-                    allowed_address: remaining_accounts[1].clone(),
-                    system_program: self.system_program.to_account_info(),
-                },
-            );
-            allow(allow_ctx, is_winner.0, true, self.bidder.key())?;
+        let mut ops = IncoOps::new(inco.clone(), signer).with_scalar_byte(input_type);
+        let is_winner = resolve_bid(&mut ops, &mut self.auction, &mut self.bid)?;
+
+        if with_allowances {
+            let winner_allowance = self
+                .winner_allowance
+                .as_ref()
+                .ok_or(AuctionError::MissingAllowanceAccount)?;
+            set_allowance(
+                &inco,
+                &self.payer.to_account_info(),
+                &winner_allowance.to_account_info(),
+                &self.bidder.to_account_info(),
+                &self.system_program.to_account_info(),
+                is_winner.0,
+                true,
+            )?;
         }
 
         msg!("Winner check completed");
-        msg!("Winner handle: {}", is_winner.0);
-        Ok(())
+        Ok((
+            CheckWinnerResult {
+                is_winner_handle: is_winner.0,
+            },
+            WinnerChecked {
+                version: EVENT_VERSION,
+                auction: self.auction.key(),
+                bid: self.bid.key(),
+                timestamp: Clock::get()?.unix_timestamp,
+            },
+        ))
+    }
+}
+
+/// Computes the winner handle of `bid` and counts it towards resolution the
+/// first time.
+///
+/// Shared by `check_winner` and `resolve_winners`; callers check the auction
+/// is closed and that `bid` belongs to it.
+pub fn resolve_bid(ops: &mut IncoOps, auction: &mut AuctionState, bid: &mut Bid) -> Result<Ebool> {
+    // A bid exists, so the auction has standings
+    let standings = auction.standings().ok_or(AuctionError::InvalidAuction)?;
+    let is_winner = rules::is_winner(
+        ops,
+        Euint128(bid.bid_amount),
+        Euint128(bid.time_stamp),
+        &standings,
+        Ebool(auction.reserve_met_handle),
+    )?;
+    bid.is_winner_handle = is_winner.0;
+
+    if !bid.resolved {
+        bid.resolved = true;
+        auction.resolved_count = auction
+            .resolved_count
+            .checked_add(1)
+            .ok_or(AuctionError::MathOverflow)?;
     }
+    Ok(is_winner)
 }
@@ -0,0 +1,196 @@
+use anchor_lang::{prelude::*, solana_program::sysvar::instructions as instructions_sysvar};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+use confidential_bid_core as rules;
+use inco_lightning::{Ebool, Euint128, IncoLightning, ID as INCO_LIGHTNING_ID};
+
+use crate::{
+    attestation::verify_ebool,
+    constants::{ATTESTER_SEED, AUCTION_SEED, BID_SEED, EVENT_VERSION, LEGACY_BID_VERSION},
+    error::AuctionError,
+    events::PrizeClaimed,
+    inco::{inco_ata_address, IncoOps, INCO_TOKEN_ID},
+    state::{AttesterConfig, AuctionState, Bid},
+    vault::{transfer_bid, transfer_prize},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(bid_secret: Option<[u8; 32]>)]
+pub struct ClaimPrize<'info> {
+    /// Pays the prize token account and the Inco operations, the bidder
+    /// itself or a relayer for private bids
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub bidder: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            BID_SEED,
+            auction.key().as_ref(),
+            Bid::seed_key(
+                &bidder.key(),
+                bid_secret.map(|secret| Bid::commitment(&bidder.key(), &secret))
+            ).as_ref()
+        ],
+        bump = bid.bid_bump,
+        constraint = bid.auction == auction.key() @ AuctionError::InvalidAuction
+    )]
+    pub bid: Account<'info, Bid>,
+
+    /// - Auction state PDA
+    #[account(
+        mut,
+        seeds=[
+            AUCTION_SEED,
+            auction.organizer.key().as_ref(),
+            auction.auction_id.to_le_bytes().as_ref()
+        ],
+        bump = auction.auction_bump,
+    )]
+    pub auction: Account<'info, AuctionState>,
+
+    #[account(seeds = [ATTESTER_SEED], bump = attester_config.bump)]
+    pub attester_config: Account<'info, AttesterConfig>,
+
+    /// CHECK: instructions sysvar holding the Ed25519 attestation of the winner handle
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    #[account(address = auction.mint @ AuctionError::InvalidAuction)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, address = auction.vault @ AuctionError::InvalidAuction)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = bidder,
+        associated_token::token_program = token_program,
+    )]
+    pub bidder_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Inco mint used for bidding, must match auction state
+    pub bid_mint: AccountInfo<'info>,
+
+    /// CHECK: shared auction escrow Inco token account, must match auction state
+    #[account(mut)]
+    pub bid_vault: AccountInfo<'info>,
+
+    /// CHECK: bidder's Inco token account receiving a Vickrey winner's excess
+    #[account(mut)]
+    pub bidder_bid_ata: AccountInfo<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Inco Token program
+    #[account(address = INCO_TOKEN_ID)]
+    pub inco_token_program: AccountInfo<'info>,
+
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: Program<'info, IncoLightning>,
+}
+
+impl<'info> ClaimPrize<'info> {
+    /// Hands the prize to the winner, proven by an attested decryption of
+    /// their winner handle. Vickrey winners also get back the part of their
+    /// bid above the clearing price.
+    pub fn handler(&mut self, bid_secret: Option<[u8; 32]>) -> Result<PrizeClaimed> {
+        rules::check_claim((&self.auction.auction_status).into()).map_err(AuctionError::from)?;
+        // Private bids prove ownership with the secret behind their commitment
+        require!(
+            self.bid
+                .is_owned_by(&self.bidder.key(), bid_secret.as_ref()),
+            AuctionError::Unauthorized
+        );
+        require!(
+            self.bid.version != LEGACY_BID_VERSION,
+            AuctionError::UnprovenEscrow
+        );
+        require!(self.bid.resolved, AuctionError::BidNotResolved);
+        require!(
+            !self.bid.claimed && !self.auction.prize_claimed,
+            AuctionError::AlreadyClaimed
+        );
+        require!(
+            self.bid_mint.key() == self.auction.bid_token_mint,
+            AuctionError::InvalidBidMint
+        );
+        require!(
+            self.bid_vault.key() == self.auction.bid_vault,
+            AuctionError::InvalidBidVault
+        );
+        require!(
+            self.bidder_bid_ata.key() == inco_ata_address(&self.bidder.key(), &self.bid_mint.key()),
+            AuctionError::InvalidBidVault
+        );
+
+        let won = verify_ebool(
+            &self.instructions,
+            &self.attester_config.attester,
+            Ebool(self.bid.is_winner_handle),
+        )?;
+        require!(won, AuctionError::NotWinner);
+
+        let amount = self.vault.amount;
+        transfer_prize(
+            &self.auction,
+            &self.mint.to_account_info(),
+            &self.vault.to_account_info(),
+            &self.bidder_token_account.to_account_info(),
+            &self.token_program.to_account_info(),
+            amount,
+            self.mint.decimals,
+        )?;
+
+        // The winner bid the highest, so the standings are set
+        let standings = self
+            .auction
+            .standings()
+            .ok_or(AuctionError::InvalidAuction)?;
+        let pricing = self.auction.auction_type.into();
+        let mut ops = IncoOps::new(
+            self.inco_lightning_program.to_account_info(),
+            self.payer.to_account_info(),
+        );
+        let price = rules::clearing_price(
+            &mut ops,
+            pricing,
+            standings.highest,
+            standings.second_highest,
+            self.auction.reserve_price,
+        )?;
+        let excess = rules::winner_excess(&mut ops, pricing, Euint128(self.bid.bid_amount), price)?;
+        if let Some(excess) = excess {
+            transfer_bid(
+                &self.auction,
+                &self.bid_mint,
+                &self.bid_vault,
+                &self.bidder_bid_ata,
+                &self.inco_token_program,
+                &self.inco_lightning_program.to_account_info(),
+                &self.system_program.to_account_info(),
+                excess,
+            )?;
+        }
+
+        self.bid.claimed = true;
+        self.auction.prize_claimed = true;
+
+        Ok(PrizeClaimed {
+            version: EVENT_VERSION,
+            auction: self.auction.key(),
+            bid: self.bid.key(),
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        })
+    }
+}
@@ -1,16 +1,17 @@
 use anchor_lang::prelude::*;
-use inco_lightning::{
-    cpi::{as_euint128, e_ge, Operation},
-    Ebool, Euint128, IncoLightning, ID as INCO_LIGHTNING_ID,
-};
+use confidential_bid_core::{self as rules, Status};
+use inco_lightning::{Euint128, IncoLightning, ID as INCO_LIGHTNING_ID};
 
 use crate::{
-    constants::AUCTION_SEED,
+    constants::{AUCTION_SEED, EVENT_VERSION},
     error::AuctionError,
     events::AuctionClosed,
-    state::{AuctionState, AuctionStatus},
+    inco::IncoOps,
+    returns::CloseAuctionResult,
+    state::AuctionState,
 };
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CloseAuction<'info> {
     #[account(mut)]
@@ -34,53 +35,58 @@ pub struct CloseAuction<'info> {
 }
 
 impl<'info> CloseAuction<'info> {
-    pub fn handler(&mut self) -> Result<()> {
+    pub fn handler(&mut self) -> Result<(CloseAuctionResult, AuctionClosed)> {
         let auction = &mut self.auction;
         let current_time = Clock::get()?.unix_timestamp;
 
-        require!(
-            auction.auction_status == AuctionStatus::Open,
-            AuctionError::AuctionNotOpen
-        );
-        require!(
-            current_time >= auction.end_time,
-            AuctionError::AuctionNotEnded
-        );
+        let closed = rules::close(auction.schedule(), current_time).map_err(AuctionError::from)?;
         require!(
             self.organizer.key() == auction.organizer.key(),
             AuctionError::Unauthorized
         );
+        require!(
+            !auction.deferred_standings || auction.standings_final,
+            AuctionError::StandingsNotReduced
+        );
 
-        let inco_program = self.inco_lightning_program.to_account_info();
-        let signer = self.organizer.to_account_info();
-
-        let enc_reserve = as_euint128(
-            CpiContext::new(
-                inco_program.clone(),
-                Operation {
-                    signer: signer.clone(),
-                },
-            ),
-            auction.reserve_price as u128,
+        let event = close_auction_state(
+            auction,
+            closed,
+            self.inco_lightning_program.to_account_info(),
+            self.organizer.to_account_info(),
+            current_time,
         )?;
 
-        // highest_bid >= reserve_price
-        let reserve_met: Ebool = e_ge(
-            CpiContext::new(inco_program.clone(), Operation { signer }),
-            Euint128(auction.highest_bid),
-            enc_reserve,
-            0u8,
-        )?;
+        let result = CloseAuctionResult {
+            reserve_met_handle: self.auction.reserve_met_handle,
+        };
+        Ok((result, event))
+    }
+}
 
-        auction.reserve_met_handle = reserve_met.0;
-        auction.auction_status = AuctionStatus::Closed;
+/// Seals the encrypted reserve check and moves the auction to `status`.
+///
+/// Shared by `close_auction` and `end_early`; callers get `status` from the
+/// matching core transition and are responsible for the organizer check and
+/// for emitting the returned event.
+pub fn close_auction_state<'info>(
+    auction: &mut Account<'info, AuctionState>,
+    status: Status,
+    inco_program: AccountInfo<'info>,
+    signer: AccountInfo<'info>,
+    current_time: i64,
+) -> Result<AuctionClosed> {
+    let mut ops = IncoOps::new(inco_program, signer);
+    let highest = auction.standings().map(|standings| standings.highest);
+    let reserve_met = rules::reserve_met(&mut ops, highest, auction.reserve_price)?;
 
-        emit!(AuctionClosed {
-            auction_id: auction.auction_id,
-            organizer: self.organizer.key(),
-            timestamp: current_time,
-        });
+    auction.reserve_met_handle = reserve_met.0;
+    auction.auction_status = status.into();
 
-        Ok(())
-    }
+    Ok(AuctionClosed {
+        version: EVENT_VERSION,
+        auction_id: auction.auction_id,
+        organizer: auction.organizer,
+        timestamp: current_time,
+    })
 }
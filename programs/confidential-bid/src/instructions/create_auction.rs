@@ -3,23 +3,40 @@ use anchor_spl::{
     associated_token::AssociatedToken,
     token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
 };
+use confidential_bid_core as rules;
 use inco_lightning::{IncoLightning, ID as INCO_LIGHTNING_ID};
 use inco_token::cpi::{accounts::CreateIdempotent, create_idempotent};
 
 use crate::{
-    constants::AUCTION_SEED,
+    constants::{
+        AUCTION_SEED, AUCTION_STATE_RESERVED, AUCTION_STATE_VERSION, BPS_DENOMINATOR,
+        EVENT_VERSION, MAX_PAYOUT_RECIPIENTS, ORGANIZER_SEED,
+    },
     error::AuctionError,
     events::AuctionCreated,
-    state::{AuctionState, AuctionStatus, AuctionType},
+    inco::{inco_ata_address, INCO_TOKEN_ID},
+    state::{AuctionState, AuctionStatus, AuctionType, OrganizerProfile, PayoutRecipient},
 };
 
+#[event_cpi]
 #[derive(Accounts)]
-#[instruction(auction_id:u64)]
 pub struct CreateAuction<'info> {
     /// The auction creator/organizer who owns the token to be auctioned
     #[account(mut)]
     pub organizer: Signer<'info>,
 
+    /// - Per-organizer counter allocating auction ids
+    /// - Created on the organizer's first auction
+    /// - Derived from [ORGANIZER_SEED, organizer]
+    #[account(
+        init_if_needed,
+        payer = organizer,
+        space = 8 + OrganizerProfile::INIT_SPACE,
+        seeds = [ORGANIZER_SEED, organizer.key().as_ref()],
+        bump
+    )]
+    pub organizer_profile: Account<'info, OrganizerProfile>,
+
     /// The NFT mint being auctioned
     #[account(mint::token_program=token_program)]
     pub mint: InterfaceAccount<'info, Mint>,
@@ -27,7 +44,7 @@ pub struct CreateAuction<'info> {
     /// CHECK: BIDDING TOKEN INCO MINT - The token used for placing bids
     pub bid_token_mint: AccountInfo<'info>,
 
-    /// CHECK: auction bid Inco vault
+    /// CHECK: auction bid Inco vault, must be the auction PDA's Inco ATA for the bid mint
     #[account(mut)]
     pub bid_vault: AccountInfo<'info>,
 
@@ -45,15 +62,15 @@ pub struct CreateAuction<'info> {
 
     /// - Auction state PDA storing metadata.
     /// - Stores all auction metadata
-    /// - Derived from [AUCTION_SEED, organizer, auction_id]
+    /// - Derived from [AUCTION_SEED, organizer, auction_id] with the next id of the organizer profile
     #[account(
         init,
         payer=organizer,
-        space=8 + AuctionState::LEN,
+        space=8 + AuctionState::INIT_SPACE,
         seeds=[
             AUCTION_SEED,
             organizer.key().as_ref(),
-            auction_id.to_le_bytes().as_ref()
+            organizer_profile.auction_count.to_le_bytes().as_ref()
         ]
         ,bump
     )]
@@ -75,6 +92,7 @@ pub struct CreateAuction<'info> {
     pub token_program: Interface<'info, TokenInterface>,
 
     /// CHECK: Inco Token program
+    #[account(address = INCO_TOKEN_ID)]
     pub inco_token_program: AccountInfo<'info>,
 
     /// CHECK: Inco Lightning program
@@ -83,36 +101,80 @@ pub struct CreateAuction<'info> {
 }
 
 impl<'info> CreateAuction<'info> {
+    #[allow(clippy::too_many_arguments)]
     pub fn handler(
         &mut self,
-        auction_id: u64,
         start_time: i64,
         end_time: i64,
         reserve_price: u64,
         auction_type: AuctionType,
         token_amount: u64,
+        payout_recipients: Vec<PayoutRecipient>,
+        auditor: Option<Pubkey>,
+        hide_bid_count: bool,
+        private_bids: bool,
+        deferred_standings: bool,
+        reveal_on_settle: bool,
+        disclose_bids: bool,
         bump: &CreateAuctionBumps,
-    ) -> Result<()> {
+    ) -> Result<AuctionCreated> {
         // validate that bid token mint is from Inco token program
         require!(
             self.bid_token_mint.owner == self.inco_token_program.key,
             AuctionError::InvalidBidMint
         );
+        require!(
+            self.bid_vault.key()
+                == inco_ata_address(&self.auction.key(), &self.bid_token_mint.key()),
+            AuctionError::InvalidBidVault
+        );
 
         // Validate auction timing
-        let current_time = Clock::get()?.unix_timestamp;
-        require!(start_time > current_time, AuctionError::InvalidStartTime);
-        require!(end_time > start_time, AuctionError::InvalidEndTime);
+        rules::validate_schedule(start_time, end_time, Clock::get()?.unix_timestamp)
+            .map_err(AuctionError::from)?;
 
         // Validate token amount
         require!(token_amount > 0, AuctionError::InvalidTokenAmount);
 
+        // Reduction and disclosure walk bids by their public sequence, which a hidden count would leak
+        require!(
+            !((deferred_standings || disclose_bids) && hide_bid_count),
+            AuctionError::BidCountHidden
+        );
+
+        // Validate payout split, whatever is left over goes to the organizer
+        require!(
+            payout_recipients.len() <= MAX_PAYOUT_RECIPIENTS,
+            AuctionError::TooManyPayoutRecipients
+        );
+        let mut total_share_bps: u16 = 0;
+        for payout in payout_recipients.iter() {
+            require!(payout.share_bps > 0, AuctionError::InvalidPayoutShare);
+            total_share_bps = total_share_bps
+                .checked_add(payout.share_bps)
+                .ok_or(AuctionError::MathOverflow)?;
+        }
+        require!(
+            total_share_bps <= BPS_DENOMINATOR,
+            AuctionError::InvalidPayoutShare
+        );
+
         // Check organizer has sufficient balance
         require!(
             self.organizer_token_account.amount >= token_amount,
             AuctionError::InsufficientBalance
         );
 
+        // Allocate the next auction id, it matches the one used in the auction PDA seeds
+        let auction_id = self.organizer_profile.auction_count;
+        self.organizer_profile.set_inner(OrganizerProfile {
+            organizer: self.organizer.key(),
+            auction_count: auction_id
+                .checked_add(1)
+                .ok_or(AuctionError::MathOverflow)?,
+            bump: bump.organizer_profile,
+        });
+
         // Transfer tokens from organizer to vault
         let cpi_ctx = CpiContext::new(
             self.token_program.to_account_info(),
@@ -150,9 +212,13 @@ impl<'info> CreateAuction<'info> {
             highest_timestamp: 0,
             start_time,
             end_time,
+            total_extension: 0,
             reserve_price,
             auction_id,
             bid_count: 0,
+            encrypted_bid_count: 0,
+            hide_bid_count,
+            private_bids,
             auction_status: AuctionStatus::Open,
             auction_type,
             auction_bump: bump.auction,
@@ -160,9 +226,23 @@ impl<'info> CreateAuction<'info> {
             bid_vault: self.bid_vault.key(),
             reserve_met_handle: 0,
             prize_claimed: false,
+            auditor,
+            payout_recipients,
+            version: AUCTION_STATE_VERSION,
+            deferred_standings,
+            standings_final: false,
+            resolved_count: 0,
+            reveal_on_settle,
+            clearing_price: None,
+            winning_bid: None,
+            disclose_bids,
+            clearing_price_handle: 0,
+            disclosed_count: 0,
+            reserved: [0; AUCTION_STATE_RESERVED],
         });
 
-        emit!(AuctionCreated {
+        Ok(AuctionCreated {
+            version: EVENT_VERSION,
             auction_id,
             organizer: self.organizer.key(),
             mint: self.mint.key(),
@@ -172,9 +252,10 @@ impl<'info> CreateAuction<'info> {
             end_time,
             reserve_price,
             auction_type: auction_type,
-            bid_token_mint: self.bid_token_mint.key()
-        });
-
-        Ok(())
+            bid_token_mint: self.bid_token_mint.key(),
+            auditor,
+            hide_bid_count,
+            private_bids,
+        })
     }
 }
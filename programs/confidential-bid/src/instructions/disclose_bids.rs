@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use confidential_bid_core as rules;
+use inco_lightning::{IncoLightning, ID as INCO_LIGHTNING_ID};
+
+use crate::{
+    constants::{AUCTION_SEED, DISCLOSURE_ADDRESS},
+    error::AuctionError,
+    inco::set_allowance,
+    state::{AuctionState, Bid},
+};
+
+#[derive(Accounts)]
+pub struct DiscloseBids<'info> {
+    /// Anyone may crank, paying the rent of the allowances
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// - Auction state PDA
+    #[account(
+        mut,
+        seeds=[
+            AUCTION_SEED,
+            auction.organizer.key().as_ref(),
+            auction.auction_id.to_le_bytes().as_ref()
+        ],
+        bump = auction.auction_bump,
+    )]
+    pub auction: Account<'info, AuctionState>,
+
+    /// CHECK: well-known disclosure key
+    #[account(address = DISCLOSURE_ADDRESS)]
+    pub disclosure: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: Program<'info, IncoLightning>,
+}
+
+impl<'info> DiscloseBids<'info> {
+    /// Lets `DISCLOSURE_ADDRESS` decrypt the amounts of the bids in
+    /// `remaining_accounts` once an auction created with `disclose_bids`
+    /// has closed.
+    ///
+    /// `remaining_accounts` holds one pair per bid: the bid, then the
+    /// disclosure allowance PDA for its amount. Bids must come in placement
+    /// order, continuing where the previous batch left off, so `reveal_bid`
+    /// can tell when every bid is disclosed.
+    pub fn handler(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        rules::check_reveal_bid((&self.auction.auction_status).into())
+            .map_err(AuctionError::from)?;
+        require!(
+            self.auction.disclose_bids,
+            AuctionError::DisclosureNotEnabled
+        );
+        require!(
+            !remaining_accounts.is_empty() && remaining_accounts.len() % 2 == 0,
+            AuctionError::InvalidDisclosureAccounts
+        );
+
+        let inco_lightning_program = self.inco_lightning_program.to_account_info();
+        let payer = self.payer.to_account_info();
+        let disclosure = self.disclosure.to_account_info();
+        let system_program = self.system_program.to_account_info();
+        for pair in remaining_accounts.chunks_exact(2) {
+            let (bid_info, allowance) = (&pair[0], &pair[1]);
+            require_keys_eq!(*bid_info.owner, crate::ID, AuctionError::InvalidAuction);
+            let bid = Bid::try_deserialize(&mut &bid_info.try_borrow_data()?[..])?;
+            require!(
+                bid.auction == self.auction.key(),
+                AuctionError::InvalidAuction
+            );
+            require!(
+                bid.sequence == self.auction.disclosed_count,
+                AuctionError::BidOutOfOrder
+            );
+
+            set_allowance(
+                &inco_lightning_program,
+                &payer,
+                allowance,
+                &disclosure,
+                &system_program,
+                bid.bid_amount,
+                true,
+            )?;
+            self.auction.disclosed_count = self
+                .auction
+                .disclosed_count
+                .checked_add(1)
+                .ok_or(AuctionError::MathOverflow)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use confidential_bid_core as rules;
+use inco_lightning::{IncoLightning, ID as INCO_LIGHTNING_ID};
+
+use crate::{
+    constants::{AUCTION_SEED, EVENT_VERSION, MIN_BIDS_TO_END_EARLY},
+    error::AuctionError,
+    events::{AuctionClosed, AuctionEndedEarly},
+    instructions::close_auction_state,
+    returns::CloseAuctionResult,
+    state::AuctionState,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct EndEarly<'info> {
+    #[account(mut)]
+    pub organizer: Signer<'info>,
+
+    /// - Auction state PDA
+    #[account(
+        mut,
+        seeds=[
+            AUCTION_SEED,
+            organizer.key().as_ref(),
+            auction.auction_id.to_le_bytes().as_ref()
+        ],
+        bump = auction.auction_bump,
+    )]
+    pub auction: Account<'info, AuctionState>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: Program<'info, IncoLightning>,
+}
+
+impl<'info> EndEarly<'info> {
+    /// Returns `AuctionClosed` alongside `AuctionEndedEarly` unless the
+    /// auction defers its standings and is closed by `close_auction` later.
+    pub fn handler(
+        &mut self,
+    ) -> Result<(CloseAuctionResult, AuctionEndedEarly, Option<AuctionClosed>)> {
+        let current_time = Clock::get()?.unix_timestamp;
+
+        require!(
+            self.organizer.key() == self.auction.organizer.key(),
+            AuctionError::Unauthorized
+        );
+        let closed = rules::end_early(
+            self.auction.schedule(),
+            (!self.auction.hide_bid_count).then_some(self.auction.bid_count),
+            MIN_BIDS_TO_END_EARLY,
+            current_time,
+        )
+        .map_err(AuctionError::from)?;
+
+        // Accept the bids received so far, no further bids can land after this
+        let scheduled_end_time = self.auction.end_time;
+        self.auction.end_time = current_time;
+
+        let ended = AuctionEndedEarly {
+            version: EVENT_VERSION,
+            auction_id: self.auction.auction_id,
+            organizer: self.organizer.key(),
+            scheduled_end_time,
+            bid_count: self.auction.bid_count,
+            timestamp: current_time,
+        };
+
+        // Deferred standings are reduced after the new end time, close_auction follows
+        if self.auction.deferred_standings {
+            let result = CloseAuctionResult {
+                reserve_met_handle: 0,
+            };
+            return Ok((result, ended, None));
+        }
+
+        let auction_closed = close_auction_state(
+            &mut self.auction,
+            closed,
+            self.inco_lightning_program.to_account_info(),
+            self.organizer.to_account_info(),
+            current_time,
+        )?;
+
+        let result = CloseAuctionResult {
+            reserve_met_handle: self.auction.reserve_met_handle,
+        };
+        Ok((result, ended, Some(auction_closed)))
+    }
+}
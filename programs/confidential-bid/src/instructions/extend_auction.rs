@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use confidential_bid_core as rules;
+
+use crate::{
+    constants::{AUCTION_SEED, EVENT_VERSION, MAX_TOTAL_EXTENSION},
+    error::AuctionError,
+    events::AuctionExtended,
+    state::AuctionState,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExtendAuction<'info> {
+    pub organizer: Signer<'info>,
+
+    /// - Auction state PDA
+    #[account(
+        mut,
+        seeds=[
+            AUCTION_SEED,
+            organizer.key().as_ref(),
+            auction.auction_id.to_le_bytes().as_ref()
+        ],
+        bump = auction.auction_bump,
+    )]
+    pub auction: Account<'info, AuctionState>,
+}
+
+impl<'info> ExtendAuction<'info> {
+    pub fn handler(&mut self, new_end_time: i64) -> Result<AuctionExtended> {
+        let auction = &mut self.auction;
+        let current_time = Clock::get()?.unix_timestamp;
+
+        require!(
+            self.organizer.key() == auction.organizer.key(),
+            AuctionError::Unauthorized
+        );
+
+        // The deadline only ever moves later, and only up to the cap
+        let total_extension = rules::extend(
+            auction.schedule(),
+            auction.total_extension,
+            new_end_time,
+            MAX_TOTAL_EXTENSION,
+            current_time,
+        )
+        .map_err(AuctionError::from)?;
+
+        let previous_end_time = auction.end_time;
+        auction.end_time = new_end_time;
+        auction.total_extension = total_extension;
+
+        Ok(AuctionExtended {
+            version: EVENT_VERSION,
+            auction_id: auction.auction_id,
+            organizer: self.organizer.key(),
+            previous_end_time,
+            new_end_time,
+        })
+    }
+}
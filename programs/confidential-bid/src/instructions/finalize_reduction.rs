@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use confidential_bid_core as rules;
+
+use crate::{
+    constants::{AUCTION_SEED, REDUCTION_SEED},
+    error::AuctionError,
+    state::{AuctionState, BidReduction},
+};
+
+#[derive(Accounts)]
+pub struct FinalizeReduction<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// - Auction state PDA
+    #[account(
+        mut,
+        seeds=[
+            AUCTION_SEED,
+            auction.organizer.key().as_ref(),
+            auction.auction_id.to_le_bytes().as_ref()
+        ],
+        bump = auction.auction_bump,
+    )]
+    pub auction: Account<'info, AuctionState>,
+
+    /// Reduction over every bid, always the one starting at sequence 0
+    #[account(
+        mut,
+        close = reduction_payer,
+        seeds = [REDUCTION_SEED, auction.key().as_ref(), 0u32.to_le_bytes().as_ref()],
+        bump = reduction.bump,
+    )]
+    pub reduction: Account<'info, BidReduction>,
+
+    #[account(mut, address = reduction.payer)]
+    pub reduction_payer: SystemAccount<'info>,
+}
+
+impl<'info> FinalizeReduction<'info> {
+    /// Writes the standings of a fully reduced auction, after which it can be closed.
+    ///
+    /// The winner is only identified by the encrypted placement key in
+    /// `highest_timestamp`, as on auctions that track their standings. A
+    /// plaintext winner id would make the winner public before settlement.
+    pub fn handler(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        rules::check_reduce(self.auction.schedule(), now).map_err(AuctionError::from)?;
+        require!(
+            self.auction.deferred_standings,
+            AuctionError::StandingsNotDeferred
+        );
+        require!(!self.auction.standings_final, AuctionError::StandingsFinal);
+        require!(
+            self.reduction.reduced == self.auction.bid_count,
+            AuctionError::ReductionIncomplete
+        );
+
+        // An auction without bids keeps the unset standings
+        if let Some(standings) = self.reduction.standings() {
+            self.auction.set_standings(&standings);
+        }
+        self.auction.standings_final = true;
+        Ok(())
+    }
+}
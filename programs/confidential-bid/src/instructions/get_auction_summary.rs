@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{AUCTION_SEED, BID_SEED, VIEW_VERSION},
+    returns::AuctionSummary,
+    state::{AuctionState, Bid},
+};
+
+#[derive(Accounts)]
+#[instruction(bid_secret: Option<[u8; 32]>)]
+pub struct GetAuctionSummary<'info> {
+    /// CHECK: address the summary is computed for, never written and need not sign
+    pub caller: UncheckedAccount<'info>,
+
+    /// - Auction state PDA
+    #[account(
+        seeds=[
+            AUCTION_SEED,
+            auction.organizer.key().as_ref(),
+            auction.auction_id.to_le_bytes().as_ref()
+        ],
+        bump = auction.auction_bump,
+    )]
+    pub auction: Account<'info, AuctionState>,
+
+    /// CHECK: caller's bid PDA, may not have been created yet
+    #[account(
+        seeds = [
+            BID_SEED,
+            auction.key().as_ref(),
+            Bid::seed_key(
+                &caller.key(),
+                bid_secret.map(|secret| Bid::commitment(&caller.key(), &secret))
+            ).as_ref()
+        ],
+        bump,
+    )]
+    pub bid: UncheckedAccount<'info>,
+}
+
+impl<'info> GetAuctionSummary<'info> {
+    /// Read-only, meant to be simulated. Never send a `bid_secret` in a
+    /// landed transaction, it would reveal the private bid's owner.
+    pub fn handler(&self, bid_secret: Option<[u8; 32]>) -> Result<AuctionSummary> {
+        let auction = &self.auction;
+        let caller = self.caller.key();
+        let now = Clock::get()?.unix_timestamp;
+
+        let bid = if self.bid.owner == &crate::ID && !self.bid.data_is_empty() {
+            Some(Bid::try_deserialize(&mut &self.bid.try_borrow_data()?[..])?)
+        } else {
+            None
+        }
+        .filter(|bid| bid.is_owned_by(&caller, bid_secret.as_ref()));
+
+        let mut grantable_handles = Vec::new();
+        if caller == auction.organizer || auction.auditor == Some(caller) {
+            grantable_handles.extend(auction.handles());
+        }
+        if let Some(bid) = &bid {
+            grantable_handles.extend(bid.handles());
+        }
+
+        Ok(AuctionSummary {
+            version: VIEW_VERSION,
+            auction: auction.key(),
+            auction_id: auction.auction_id,
+            organizer: auction.organizer,
+            auction_type: auction.auction_type,
+            status: auction.auction_status.clone(),
+            phase: auction.phase(now),
+            start_time: auction.start_time,
+            end_time: auction.end_time,
+            time_remaining: auction.time_remaining(now),
+            bid_count: (!auction.hide_bid_count).then_some(auction.bid_count),
+            has_bid: bid.is_some(),
+            grantable_handles,
+        })
+    }
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{AUCTION_SEED, VIEW_VERSION},
+    error::AuctionError,
+    returns::BidStatus,
+    state::{AuctionState, Bid},
+};
+
+#[derive(Accounts)]
+pub struct GetBidStatus<'info> {
+    /// CHECK: address the status is computed for, never written and need not sign
+    pub caller: UncheckedAccount<'info>,
+
+    /// - Auction state PDA
+    #[account(
+        seeds=[
+            AUCTION_SEED,
+            auction.organizer.key().as_ref(),
+            auction.auction_id.to_le_bytes().as_ref()
+        ],
+        bump = auction.auction_bump,
+    )]
+    pub auction: Account<'info, AuctionState>,
+
+    #[account(constraint = bid.auction == auction.key() @ AuctionError::InvalidAuction)]
+    pub bid: Account<'info, Bid>,
+}
+
+impl<'info> GetBidStatus<'info> {
+    /// Read-only, meant to be simulated. Never send a `bid_secret` in a
+    /// landed transaction, it would reveal the private bid's owner.
+    pub fn handler(&self, bid_secret: Option<[u8; 32]>) -> Result<BidStatus> {
+        let auction = &self.auction;
+        let bid = &self.bid;
+        let caller = self.caller.key();
+        let now = Clock::get()?.unix_timestamp;
+
+        let owned_by_caller = bid.is_owned_by(&caller, bid_secret.as_ref());
+        let grantable_handles = if owned_by_caller || auction.auditor == Some(caller) {
+            bid.handles()
+        } else {
+            Vec::new()
+        };
+
+        Ok(BidStatus {
+            version: VIEW_VERSION,
+            bid: bid.key(),
+            auction: auction.key(),
+            phase: auction.phase(now),
+            owned_by_caller,
+            winner_checked: bid.is_winner_handle != 0,
+            claimed: bid.claimed,
+            bid_amount_handle: bid.bid_amount,
+            time_stamp_handle: bid.time_stamp,
+            is_winner_handle: bid.is_winner_handle,
+            grantable_handles,
+        })
+    }
+}
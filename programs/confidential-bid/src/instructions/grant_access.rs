@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use inco_lightning::{IncoLightning, ID as INCO_LIGHTNING_ID};
+
+use crate::{
+    constants::AUCTION_SEED,
+    error::AuctionError,
+    inco::set_allowance,
+    state::{AuctionState, Bid},
+};
+
+#[derive(Accounts)]
+pub struct GrantAccess<'info> {
+    /// Pays the allowance, the owner itself or a relayer for private bids
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Owner of the handle: the bidder for `Bid` handles, the organizer for auction handles
+    pub owner: Signer<'info>,
+
+    /// - Auction state PDA
+    #[account(
+        seeds=[
+            AUCTION_SEED,
+            auction.organizer.key().as_ref(),
+            auction.auction_id.to_le_bytes().as_ref()
+        ],
+        bump = auction.auction_bump,
+    )]
+    pub auction: Account<'info, AuctionState>,
+
+    /// Bid holding the handle, required when granting access to a bid handle
+    #[account(constraint = bid.auction == auction.key() @ AuctionError::InvalidAuction)]
+    pub bid: Option<Account<'info, Bid>>,
+
+    /// CHECK: Inco allowance PDA for (handle, allowed_address), derivation checked on-chain
+    #[account(mut)]
+    pub allowance_account: UncheckedAccount<'info>,
+
+    /// CHECK: address gaining or losing decryption rights
+    pub allowed_address: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Inco Lightning program
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: Program<'info, IncoLightning>,
+}
+
+impl<'info> GrantAccess<'info> {
+    pub fn handler(
+        &mut self,
+        handle: u128,
+        value: bool,
+        bid_secret: Option<[u8; 32]>,
+    ) -> Result<()> {
+        let auction = &self.auction;
+        require!(handle != 0, AuctionError::UnknownHandle);
+        // The auditor must keep seeing every handle of the auction
+        require!(
+            value || auction.auditor != Some(self.allowed_address.key()),
+            AuctionError::AuditorAccessLocked
+        );
+
+        let is_bid_handle = self
+            .bid
+            .as_ref()
+            .is_some_and(|bid| bid.handles().contains(&handle));
+        let is_auction_handle = auction.handles().contains(&handle);
+
+        if is_bid_handle {
+            let bid = self.bid.as_ref().ok_or(AuctionError::UnknownHandle)?;
+            require!(
+                bid.is_owned_by(&self.owner.key(), bid_secret.as_ref()),
+                AuctionError::Unauthorized
+            );
+        } else if is_auction_handle {
+            require!(
+                self.owner.key() == auction.organizer,
+                AuctionError::Unauthorized
+            );
+        } else {
+            return err!(AuctionError::UnknownHandle);
+        }
+
+        set_allowance(
+            &self.inco_lightning_program.to_account_info(),
+            &self.payer.to_account_info(),
+            &self.allowance_account.to_account_info(),
+            &self.allowed_address.to_account_info(),
+            &self.system_program.to_account_info(),
+            handle,
+            value,
+        )
+    }
+}
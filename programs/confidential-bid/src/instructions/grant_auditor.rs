@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use inco_lightning::{IncoLightning, ID as INCO_LIGHTNING_ID};
+
+use crate::{
+    constants::AUCTION_SEED,
+    error::AuctionError,
+    inco::set_allowance,
+    state::{AuctionState, Bid},
+};
+
+#[derive(Accounts)]
+pub struct GrantAuditor<'info> {
+    /// Anyone, pays the allowances
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// - Auction state PDA
+    #[account(
+        seeds=[
+            AUCTION_SEED,
+            auction.organizer.key().as_ref(),
+            auction.auction_id.to_le_bytes().as_ref()
+        ],
+        bump = auction.auction_bump,
+    )]
+    pub auction: Account<'info, AuctionState>,
+
+    /// Bid whose handles are granted, the auction's own handles without one
+    #[account(constraint = bid.auction == auction.key() @ AuctionError::InvalidAuction)]
+    pub bid: Option<Account<'info, Bid>>,
+
+    /// CHECK: auction auditor, must match auction state
+    pub auditor: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Inco Lightning program
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: Program<'info, IncoLightning>,
+}
+
+impl<'info> GrantAuditor<'info> {
+    /// Grants the auditor every current handle of the bid, or of the auction
+    /// when no bid is given.
+    ///
+    /// `remaining_accounts` holds the auditor's allowance PDA of each handle,
+    /// in the order of `Bid::handles` or `AuctionState::handles`. Handles
+    /// only exist once the instruction creating them has run, so grants follow
+    /// as a separate step; granting again after the handles move is harmless.
+    pub fn handler(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        let auditor = self.auction.auditor.ok_or(AuctionError::InvalidAuditor)?;
+        require_keys_eq!(self.auditor.key(), auditor, AuctionError::InvalidAuditor);
+
+        let handles = match &self.bid {
+            Some(bid) => bid.handles(),
+            None => self.auction.handles(),
+        };
+        require!(
+            remaining_accounts.len() == handles.len(),
+            AuctionError::MissingAuditorAccounts
+        );
+
+        for (allowance_account, handle) in remaining_accounts.iter().zip(handles) {
+            set_allowance(
+                &self.inco_lightning_program.to_account_info(),
+                &self.payer.to_account_info(),
+                allowance_account,
+                &self.auditor.to_account_info(),
+                &self.system_program.to_account_info(),
+                handle,
+                true,
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use confidential_bid_core as rules;
+use inco_lightning::{IncoLightning, ID as INCO_LIGHTNING_ID};
+
+use crate::{
+    constants::{AUCTION_SEED, REDUCTION_SEED},
+    error::AuctionError,
+    inco::IncoOps,
+    state::{AuctionState, BidReduction},
+};
+
+#[derive(Accounts)]
+pub struct MergeReductions<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// - Auction state PDA
+    #[account(
+        seeds=[
+            AUCTION_SEED,
+            auction.organizer.key().as_ref(),
+            auction.auction_id.to_le_bytes().as_ref()
+        ],
+        bump = auction.auction_bump,
+    )]
+    pub auction: Account<'info, AuctionState>,
+
+    #[account(
+        mut,
+        seeds = [REDUCTION_SEED, auction.key().as_ref(), earlier.start.to_le_bytes().as_ref()],
+        bump = earlier.bump,
+    )]
+    pub earlier: Account<'info, BidReduction>,
+
+    /// Closed into `earlier`, its rent goes back to whoever opened it
+    #[account(
+        mut,
+        close = later_payer,
+        seeds = [REDUCTION_SEED, auction.key().as_ref(), later.start.to_le_bytes().as_ref()],
+        bump = later.bump,
+    )]
+    pub later: Account<'info, BidReduction>,
+
+    #[account(mut, address = later.payer)]
+    pub later_payer: SystemAccount<'info>,
+
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: Program<'info, IncoLightning>,
+}
+
+impl<'info> MergeReductions<'info> {
+    /// Extends `earlier` with the bids of `later`, which must pick up at the
+    /// sequence where `earlier` stops.
+    pub fn handler(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        rules::check_reduce(self.auction.schedule(), now).map_err(AuctionError::from)?;
+        require!(
+            self.auction.deferred_standings,
+            AuctionError::StandingsNotDeferred
+        );
+        require_keys_neq!(
+            self.earlier.key(),
+            self.later.key(),
+            AuctionError::ReductionGap
+        );
+        require!(
+            self.later.start == self.earlier.end(),
+            AuctionError::ReductionGap
+        );
+
+        let mut ops = IncoOps::new(
+            self.inco_lightning_program.to_account_info(),
+            self.payer.to_account_info(),
+        );
+        let merged =
+            rules::merge_standings(&mut ops, self.earlier.standings(), self.later.standings())?;
+        if let Some(standings) = merged {
+            self.earlier.set_standings(&standings);
+        }
+        self.earlier.reduced = self
+            .earlier
+            .reduced
+            .checked_add(self.later.reduced)
+            .ok_or(AuctionError::MathOverflow)?;
+
+        Ok(())
+    }
+}
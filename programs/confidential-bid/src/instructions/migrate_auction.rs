@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    legacy::{grow_account, read_legacy, AuctionStateV0, AuctionStateV1},
+    state::AuctionState,
+};
+
+#[derive(Accounts)]
+pub struct MigrateAuction<'info> {
+    /// Pays for the extra rent, migration is permissionless since it only fills defaults
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: legacy auction state, owner, discriminator and size checked in the handler
+    #[account(mut)]
+    pub auction: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateAuction<'info> {
+    pub fn handler(&mut self) -> Result<()> {
+        let auction_info = self.auction.to_account_info();
+        let new_len = 8 + AuctionState::INIT_SPACE;
+
+        // Version 1 accounts have a size of their own, anything smaller is from the first layout
+        let auction = if auction_info.data_len() == 8 + AuctionStateV1::INIT_SPACE {
+            read_legacy::<AuctionStateV1>(&auction_info, AuctionState::DISCRIMINATOR, new_len)?
+                .migrate()
+        } else {
+            read_legacy::<AuctionStateV0>(&auction_info, AuctionState::DISCRIMINATOR, new_len)?
+                .migrate()
+        };
+
+        grow_account(
+            &auction_info,
+            &self.payer.to_account_info(),
+            &self.system_program.to_account_info(),
+            new_len,
+        )?;
+        auction.try_serialize(&mut &mut auction_info.try_borrow_mut_data()?[..])?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    legacy::{grow_account, read_legacy, BidV0},
+    state::Bid,
+};
+
+#[derive(Accounts)]
+pub struct MigrateBid<'info> {
+    /// Pays for the extra rent, migration is permissionless since it only fills defaults
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: legacy bid, owner, discriminator and size checked in the handler
+    #[account(mut)]
+    pub bid: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateBid<'info> {
+    pub fn handler(&mut self) -> Result<()> {
+        let bid_info = self.bid.to_account_info();
+        let new_len = 8 + Bid::INIT_SPACE;
+
+        let legacy: BidV0 = read_legacy(&bid_info, Bid::DISCRIMINATOR, new_len)?;
+        let bid = legacy.migrate();
+
+        grow_account(
+            &bid_info,
+            &self.payer.to_account_info(),
+            &self.system_program.to_account_info(),
+            new_len,
+        )?;
+        bid.try_serialize(&mut &mut bid_info.try_borrow_mut_data()?[..])?;
+
+        Ok(())
+    }
+}
@@ -6,3 +6,45 @@ pub mod close_auction;
 pub use close_auction::*;
 pub mod check_winner;
 pub use check_winner::*;
+pub mod settle_auction;
+pub use settle_auction::*;
+pub mod update_auction;
+pub use update_auction::*;
+pub mod extend_auction;
+pub use extend_auction::*;
+pub mod end_early;
+pub use end_early::*;
+pub mod grant_access;
+pub use grant_access::*;
+pub mod grant_auditor;
+pub use grant_auditor::*;
+pub mod get_auction_summary;
+pub use get_auction_summary::*;
+pub mod get_bid_status;
+pub use get_bid_status::*;
+pub mod migrate_auction;
+pub use migrate_auction::*;
+pub mod migrate_bid;
+pub use migrate_bid::*;
+pub mod reduce_bids;
+pub use reduce_bids::*;
+pub mod merge_reductions;
+pub use merge_reductions::*;
+pub mod finalize_reduction;
+pub use finalize_reduction::*;
+pub mod resolve_winners;
+pub use resolve_winners::*;
+pub mod set_attester;
+pub use set_attester::*;
+pub mod claim_prize;
+pub use claim_prize::*;
+pub mod refund_bid;
+pub use refund_bid::*;
+pub mod cancel_auction;
+pub use cancel_auction::*;
+pub mod reveal_result;
+pub use reveal_result::*;
+pub mod disclose_bids;
+pub use disclose_bids::*;
+pub mod reveal_bid;
+pub use reveal_bid::*;
@@ -1,19 +1,28 @@
 use anchor_lang::prelude::*;
-use inco_lightning::{
-    cpi::{allow, as_euint128, e_gt, e_select, new_euint128, Allow, Operation},
-    Ebool, Euint128, IncoLightning, ID as INCO_LIGHTNING_ID,
-};
+use confidential_bid_core::{self as rules, EncryptedOps};
+use inco_lightning::{Euint128, IncoLightning, ID as INCO_LIGHTNING_ID};
 use inco_token::cpi::{accounts::TransferChecked, transfer_checked};
 
 use crate::{
-    constants::{AUCTION_SEED, BID_SEED},
+    constants::{AUCTION_SEED, BID_RESERVED, BID_SEED, BID_VERSION, EVENT_VERSION},
     error::AuctionError,
-    state::{AuctionState, AuctionStatus, Bid},
+    events::BidPlaced,
+    inco::{set_allowance, IncoOps, INCO_TOKEN_ID},
+    returns::PlaceBidResult,
+    state::{AuctionState, Bid},
 };
 
+#[event_cpi]
 #[derive(Accounts)]
+#[instruction(bid_amount: Vec<u8>, input_type: u8, bid_commitment: Option<[u8; 32]>)]
 pub struct PlaceBid<'info> {
+    /// Pays the bid account and the Inco operations, the bidder itself or a
+    /// relayer for private bids
     #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Owner of the token account the bid is drawn from, a one-off bid key
+    /// on private auctions
     pub bidder: Signer<'info>,
 
     /// CHECK: used only for PDA seed verification
@@ -32,12 +41,12 @@ pub struct PlaceBid<'info> {
 
     #[account(
             init,
-            payer = bidder,
+            payer = payer,
             space = 8 + Bid::INIT_SPACE,
             seeds = [
                 BID_SEED,
                 auction.key().as_ref(),
-                bidder.key().as_ref()
+                Bid::seed_key(&bidder.key(), bid_commitment).as_ref()
             ],
             bump
         )]
@@ -55,9 +64,20 @@ pub struct PlaceBid<'info> {
     )]
     pub auction: Account<'info, AuctionState>,
 
+    /// CHECK: Inco allowance PDA for the bidder's new balance handle, derivation checked
+    /// on-chain, required with `with_allowances`
+    #[account(mut)]
+    pub balance_allowance: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Inco allowance PDA for the bidder's encrypted bid amount, derivation checked
+    /// on-chain, required with `with_allowances`
+    #[account(mut)]
+    pub bid_allowance: Option<UncheckedAccount<'info>>,
+
     pub system_program: Program<'info, System>,
 
     /// CHECK: Inco Token program
+    #[account(address = INCO_TOKEN_ID)]
     pub inco_token_program: AccountInfo<'info>,
 
     /// CHECK: Inco Lightning program
@@ -66,24 +86,21 @@ pub struct PlaceBid<'info> {
 }
 
 impl<'info> PlaceBid<'info> {
+    /// `with_allowances` grants the bidder its new balance and bid amount,
+    /// which needs both allowance accounts; simulations that only learn the
+    /// handles leave it unset.
     pub fn handler(
         &mut self,
         bid_amount: Vec<u8>,
         bump: &PlaceBidBumps,
         input_type: u8,
-        remaining_accounts: &[AccountInfo<'info>],
-    ) -> Result<()> {
-        let now = Clock::get()?.unix_timestamp;
-        require!(
-            now >= self.auction.start_time,
-            AuctionError::AuctionNotStarted
-        );
-        require!(now < self.auction.end_time, AuctionError::AuctionEnded);
+        bid_commitment: Option<[u8; 32]>,
+        with_allowances: bool,
+    ) -> Result<(PlaceBidResult, BidPlaced)> {
+        let clock = Clock::get()?;
+        let now = clock.unix_timestamp;
+        rules::check_bid(self.auction.schedule(), now).map_err(AuctionError::from)?;
 
-        require!(
-            self.auction.auction_status == AuctionStatus::Open,
-            AuctionError::AuctionNotOpen
-        );
         require!(
             self.bid_mint.key() == self.auction.bid_token_mint.key(),
             AuctionError::InvalidBidMint
@@ -94,6 +111,12 @@ impl<'info> PlaceBid<'info> {
         );
         require!(!bid_amount.is_empty(), AuctionError::InvalidBidAmount);
 
+        // Private auctions key every bid by a commitment, public ones never do
+        require!(
+            self.auction.private_bids == bid_commitment.is_some(),
+            AuctionError::InvalidBidCommitment
+        );
+
         // Verify bid_mint is owned by Inco program
         require!(
             *self.bid_mint.owner == self.inco_token_program.key(),
@@ -101,26 +124,16 @@ impl<'info> PlaceBid<'info> {
         );
 
         let inco_program = self.inco_lightning_program.to_account_info();
+        // Every operation below is between encrypted values, `input_type` only
+        // describes the submitted ciphertext
+        let mut ops = IncoOps::new(inco_program.clone(), self.payer.to_account_info());
 
-        let current_time = Clock::get()?.unix_timestamp;
-        let cpi_ctx = CpiContext::new(
-            inco_program.clone(),
-            Operation {
-                signer: self.bidder.to_account_info(),
-            },
-        );
-        let enc_time_stamp = as_euint128(cpi_ctx, current_time as u128)?;
-
-        let enc_bid_amount = new_euint128(
-            CpiContext::new(
-                inco_program.clone(),
-                Operation {
-                    signer: self.bidder.to_account_info(),
-                },
-            ),
-            bid_amount.clone(),
-            input_type,
-        )?;
+        // check_winner matches the leader by this key, unique even within a second
+        let sequence = self.auction.bid_count;
+        let hidden_sequence = (self.auction.hide_bid_count
+            && self.auction.encrypted_bid_count != 0)
+            .then_some(Euint128(self.auction.encrypted_bid_count));
+        let enc_time_stamp = rules::placement_key(&mut ops, now, sequence, hidden_sequence)?;
 
         let cpi_transfer = CpiContext::new(
             self.inco_token_program.to_account_info().clone(),
@@ -136,148 +149,109 @@ impl<'info> PlaceBid<'info> {
         let bid_mint_decimals =
             inco_token::IncoMint::try_deserialize(&mut &self.bid_mint.try_borrow_data()?[..])?;
 
+        // An insufficient balance transfers nothing instead of failing, so the
+        // bid is what reached the vault rather than the submitted ciphertext
+        let vault_before = self.vault_balance()?;
         transfer_checked(
             cpi_transfer,
             bid_amount,
             input_type,
             bid_mint_decimals.decimals,
         )?;
-
-        self.auction.bid_count = self
-            .auction
-            .bid_count
-            .checked_add(1)
-            .ok_or(AuctionError::MathOverflow)?;
+        let escrowed = ops.sub(self.vault_balance()?, vault_before)?;
+
+        if self.auction.hide_bid_count {
+            // Keep participation private, only the encrypted count moves
+            let enc_bid_count = rules::count_bid(&mut ops, hidden_sequence)?;
+            self.auction.encrypted_bid_count = enc_bid_count.0;
+        } else {
+            self.auction.bid_count = self
+                .auction
+                .bid_count
+                .checked_add(1)
+                .ok_or(AuctionError::MathOverflow)?;
+        }
 
         // Initialize bid account
         self.bid.set_inner(Bid {
-            bidder: self.bidder.key(),
+            bidder: if bid_commitment.is_some() {
+                Pubkey::default()
+            } else {
+                self.bidder.key()
+            },
+            commitment: bid_commitment.unwrap_or_default(),
             auction: self.auction.key(),
-            bid_amount: enc_bid_amount.0,
+            bid_amount: escrowed.0,
             time_stamp: enc_time_stamp.0,
             bid_bump: bump.bid,
             is_winner_handle: 0,
             claimed: false,
+            version: BID_VERSION,
+            sequence,
+            resolved: false,
+            revealed_amount: None,
+            reserved: [0; BID_RESERVED],
         });
 
-        let previous_highest_bid = Euint128(self.auction.highest_bid);
-
-        let previous_second = Euint128(
-            self.auction
-                .second_highest_bid
-                .unwrap_or(self.auction.highest_bid),
-        );
-
-        // Check if new bid is greater than current highest
-        // does new bid higher than current highest
-        let is_gt_highest: Ebool = e_gt(
-            CpiContext::new(
-                inco_program.clone(),
-                Operation {
-                    signer: self.bidder.to_account_info(),
-                },
-            ),
-            enc_bid_amount,
-            previous_highest_bid,
-            input_type,
-        )?;
-
-        let cpi_ctx = CpiContext::new(
-            inco_program.clone(),
-            Operation {
-                signer: self.bidder.to_account_info(),
-            },
-        );
-
-        // If new bid is greater -> highest becomes new bid
-        // Otherwise ->  keep previous highest
-        let e_new_highest: Euint128 = e_select(
-            cpi_ctx,
-            is_gt_highest,
-            enc_bid_amount,
-            previous_highest_bid,
-            input_type,
-        )?;
-
-        //  Check if new bid is greater than previous second
-        let is_gt_second: Ebool = e_gt(
-            CpiContext::new(
-                inco_program.clone(),
-                Operation {
-                    signer: self.bidder.to_account_info(),
-                },
-            ),
-            enc_bid_amount,
-            previous_second,
-            input_type,
-        )?;
-
-        let temp_second: Euint128 = e_select(
-            CpiContext::new(
-                inco_program.clone(),
-                Operation {
-                    signer: self.bidder.to_account_info(),
-                },
-            ),
-            is_gt_second,
-            enc_bid_amount,
-            previous_second,
-            input_type,
-        )?;
-
-        let cpi_ctx = CpiContext::new(
-            inco_program.clone(),
-            Operation {
-                signer: self.bidder.to_account_info(),
-            },
-        );
-
-        // If new bid is greater than second -> use new bid
-        // Otherwise -> keep previous second
-        let new_second = e_select(
-            cpi_ctx,
-            is_gt_highest,
-            previous_highest_bid, // prev highest becomes second
-            temp_second,
-            input_type,
-        )?;
-
-        let new_highest_timestamp = e_select(
-            CpiContext::new(
-                inco_program.clone(),
-                Operation {
-                    signer: self.bidder.to_account_info(),
-                },
-            ),
-            is_gt_highest,
-            enc_time_stamp,
-            Euint128(self.auction.highest_timestamp),
-            input_type,
-        )?;
-
-        self.auction.highest_bid = e_new_highest.0;
-        self.auction.second_highest_bid = Some(new_second.0);
-        self.auction.highest_timestamp = new_highest_timestamp.0;
-
-        if remaining_accounts.len() >= 2 {
-            // Allow bidder to decrypt bidder ATA balance handle
+        // Deferred auctions leave the standings to reduce_bids after end_time
+        let standings = if self.auction.deferred_standings {
+            None
+        } else {
+            let standings =
+                rules::place_bid(&mut ops, self.auction.standings(), escrowed, enc_time_stamp)?;
+            self.auction.set_standings(&standings);
+            Some(standings)
+        };
+
+        if with_allowances {
             let bidder_acc = inco_token::IncoAccount::try_deserialize(
                 &mut &self.bidder_token_ata.try_borrow_data()?[..],
             )?;
-            let bidder_amount_handle = bidder_acc.amount.0;
-
-            let cpi_ctx = CpiContext::new(
-                inco_program.clone(),
-                Allow {
-                    allowance_account: remaining_accounts[0].clone(),
-                    signer: self.bidder.to_account_info(),
-                    allowed_address: remaining_accounts[1].clone(),
-                    system_program: self.system_program.to_account_info(),
-                },
-            );
-            allow(cpi_ctx, bidder_amount_handle, true, self.bidder.key())?;
+            // Allow bidder to decrypt bidder ATA balance handle and their own bid amount
+            let grants = [
+                (&self.balance_allowance, bidder_acc.amount.0),
+                (&self.bid_allowance, escrowed.0),
+            ];
+            for (allowance, handle) in grants {
+                let allowance = allowance
+                    .as_ref()
+                    .ok_or(AuctionError::MissingAllowanceAccount)?;
+                set_allowance(
+                    &inco_program,
+                    &self.payer.to_account_info(),
+                    &allowance.to_account_info(),
+                    &self.bidder.to_account_info(),
+                    &self.system_program.to_account_info(),
+                    handle,
+                    true,
+                )?;
+            }
         }
 
-        Ok(())
+        let event = BidPlaced {
+            version: EVENT_VERSION,
+            auction: self.auction.key(),
+            bid: self.bid.key(),
+            bid_amount_handle: escrowed.0,
+            bid_count: (!self.auction.hide_bid_count).then_some(self.auction.bid_count),
+            slot: clock.slot,
+        };
+        let result = PlaceBidResult {
+            bid: self.bid.key(),
+            bid_amount_handle: escrowed.0,
+            time_stamp_handle: enc_time_stamp.0,
+            highest_bid_handle: standings.map_or(0, |standings| standings.highest.0),
+            second_highest_bid_handle: standings
+                .and_then(|standings| standings.second_highest)
+                .map_or(0, |second| second.0),
+        };
+        Ok((result, event))
+    }
+
+    /// Balance handle of the shared bid vault
+    fn vault_balance(&self) -> Result<Euint128> {
+        let vault =
+            inco_token::IncoAccount::try_deserialize(&mut &self.bid_vault.try_borrow_data()?[..])?;
+        Ok(Euint128(vault.amount.0))
     }
 }
@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use confidential_bid_core as rules;
+use inco_lightning::{Euint128, IncoLightning, ID as INCO_LIGHTNING_ID};
+
+use crate::{
+    constants::{AUCTION_SEED, REDUCTION_SEED},
+    error::AuctionError,
+    inco::IncoOps,
+    state::{AuctionState, Bid, BidReduction},
+};
+
+#[derive(Accounts)]
+#[instruction(start: u32)]
+pub struct ReduceBids<'info> {
+    /// Anyone may crank, the reduction rent is refunded to them once it is merged
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// - Auction state PDA
+    #[account(
+        seeds=[
+            AUCTION_SEED,
+            auction.organizer.key().as_ref(),
+            auction.auction_id.to_le_bytes().as_ref()
+        ],
+        bump = auction.auction_bump,
+    )]
+    pub auction: Account<'info, AuctionState>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + BidReduction::INIT_SPACE,
+        seeds = [REDUCTION_SEED, auction.key().as_ref(), start.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub reduction: Account<'info, BidReduction>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: Program<'info, IncoLightning>,
+}
+
+impl<'info> ReduceBids<'info> {
+    /// Folds the bids in `remaining_accounts` into the reduction starting at
+    /// sequence `start`.
+    ///
+    /// Bids must come in placement order, continuing where the reduction left
+    /// off, so ties still go to the earliest bid.
+    pub fn handler(
+        &mut self,
+        start: u32,
+        remaining_accounts: &[AccountInfo<'info>],
+        bumps: &ReduceBidsBumps,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        rules::check_reduce(self.auction.schedule(), now).map_err(AuctionError::from)?;
+        require!(
+            self.auction.deferred_standings,
+            AuctionError::StandingsNotDeferred
+        );
+        require!(!self.auction.standings_final, AuctionError::StandingsFinal);
+
+        if self.reduction.auction == Pubkey::default() {
+            self.reduction.set_inner(BidReduction {
+                auction: self.auction.key(),
+                payer: self.payer.key(),
+                start,
+                reduced: 0,
+                highest: 0,
+                second_highest: None,
+                highest_timestamp: 0,
+                bump: bumps.reduction,
+            });
+        }
+
+        let mut ops = IncoOps::new(
+            self.inco_lightning_program.to_account_info(),
+            self.payer.to_account_info(),
+        );
+        for bid_info in remaining_accounts {
+            require_keys_eq!(*bid_info.owner, crate::ID, AuctionError::InvalidAuction);
+            let bid = Bid::try_deserialize(&mut &bid_info.try_borrow_data()?[..])?;
+            require!(
+                bid.auction == self.auction.key(),
+                AuctionError::InvalidAuction
+            );
+            require!(
+                bid.sequence == self.reduction.end(),
+                AuctionError::BidOutOfOrder
+            );
+
+            let standings = rules::place_bid(
+                &mut ops,
+                self.reduction.standings(),
+                Euint128(bid.bid_amount),
+                Euint128(bid.time_stamp),
+            )?;
+            self.reduction.set_standings(&standings);
+            self.reduction.reduced = self
+                .reduction
+                .reduced
+                .checked_add(1)
+                .ok_or(AuctionError::MathOverflow)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,135 @@
+use anchor_lang::{prelude::*, solana_program::sysvar::instructions as instructions_sysvar};
+use confidential_bid_core as rules;
+use inco_lightning::{Ebool, Euint128, IncoLightning, ID as INCO_LIGHTNING_ID};
+
+use crate::{
+    attestation::verify_ebool,
+    constants::{ATTESTER_SEED, AUCTION_SEED, BID_SEED, EVENT_VERSION, LEGACY_BID_VERSION},
+    error::AuctionError,
+    events::BidRefunded,
+    inco::{inco_ata_address, INCO_TOKEN_ID},
+    state::{AttesterConfig, AuctionState, AuctionStatus, Bid},
+    vault::transfer_bid,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(bid_secret: Option<[u8; 32]>)]
+pub struct RefundBid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            BID_SEED,
+            auction.key().as_ref(),
+            Bid::seed_key(
+                &bidder.key(),
+                bid_secret.map(|secret| Bid::commitment(&bidder.key(), &secret))
+            ).as_ref()
+        ],
+        bump = bid.bid_bump,
+        constraint = bid.auction == auction.key() @ AuctionError::InvalidAuction
+    )]
+    pub bid: Account<'info, Bid>,
+
+    /// - Auction state PDA
+    #[account(
+        seeds=[
+            AUCTION_SEED,
+            auction.organizer.key().as_ref(),
+            auction.auction_id.to_le_bytes().as_ref()
+        ],
+        bump = auction.auction_bump,
+    )]
+    pub auction: Account<'info, AuctionState>,
+
+    #[account(seeds = [ATTESTER_SEED], bump = attester_config.bump)]
+    pub attester_config: Account<'info, AttesterConfig>,
+
+    /// CHECK: instructions sysvar holding the Ed25519 attestation of the winner handle
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    /// CHECK: Inco mint used for bidding, must match auction state
+    pub bid_mint: AccountInfo<'info>,
+
+    /// CHECK: shared auction escrow Inco token account, must match auction state
+    #[account(mut)]
+    pub bid_vault: AccountInfo<'info>,
+
+    /// CHECK: bidder's Inco token account receiving the refund
+    #[account(mut)]
+    pub bidder_bid_ata: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Inco Token program
+    #[account(address = INCO_TOKEN_ID)]
+    pub inco_token_program: AccountInfo<'info>,
+
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: Program<'info, IncoLightning>,
+}
+
+impl<'info> RefundBid<'info> {
+    /// Returns a bid to its bidder. Every bid of a cancelled auction is
+    /// refunded, otherwise an attested decryption of the winner handle must
+    /// show the bid lost.
+    pub fn handler(&mut self, bid_secret: Option<[u8; 32]>) -> Result<BidRefunded> {
+        rules::check_refund((&self.auction.auction_status).into()).map_err(AuctionError::from)?;
+        // Private bids prove ownership with the secret behind their commitment
+        require!(
+            self.bid
+                .is_owned_by(&self.bidder.key(), bid_secret.as_ref()),
+            AuctionError::Unauthorized
+        );
+        require!(
+            self.bid.version != LEGACY_BID_VERSION,
+            AuctionError::UnprovenEscrow
+        );
+        require!(!self.bid.claimed, AuctionError::AlreadyClaimed);
+        require!(
+            self.bid_mint.key() == self.auction.bid_token_mint,
+            AuctionError::InvalidBidMint
+        );
+        require!(
+            self.bid_vault.key() == self.auction.bid_vault,
+            AuctionError::InvalidBidVault
+        );
+        require!(
+            self.bidder_bid_ata.key() == inco_ata_address(&self.bidder.key(), &self.bid_mint.key()),
+            AuctionError::InvalidBidVault
+        );
+
+        if self.auction.auction_status != AuctionStatus::Cancelled {
+            require!(self.bid.resolved, AuctionError::BidNotResolved);
+            let won = verify_ebool(
+                &self.instructions,
+                &self.attester_config.attester,
+                Ebool(self.bid.is_winner_handle),
+            )?;
+            require!(!won, AuctionError::WinningBidNotRefundable);
+        }
+
+        transfer_bid(
+            &self.auction,
+            &self.bid_mint,
+            &self.bid_vault,
+            &self.bidder_bid_ata,
+            &self.inco_token_program,
+            &self.inco_lightning_program.to_account_info(),
+            &self.system_program.to_account_info(),
+            Euint128(self.bid.bid_amount),
+        )?;
+        self.bid.claimed = true;
+
+        Ok(BidRefunded {
+            version: EVENT_VERSION,
+            auction: self.auction.key(),
+            bid: self.bid.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        })
+    }
+}
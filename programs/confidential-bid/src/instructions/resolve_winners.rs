@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+use confidential_bid_core as rules;
+use inco_lightning::{IncoLightning, ID as INCO_LIGHTNING_ID};
+
+use crate::{
+    constants::{AUCTION_SEED, EVENT_VERSION},
+    error::AuctionError,
+    events::WinnerChecked,
+    inco::{set_allowance, IncoOps},
+    instructions::resolve_bid,
+    state::{AuctionState, Bid},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ResolveWinners<'info> {
+    /// The organizer or any crank, signs the Inco operations and pays the allowances
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// - Auction state PDA
+    #[account(
+        mut,
+        seeds=[
+            AUCTION_SEED,
+            auction.organizer.key().as_ref(),
+            auction.auction_id.to_le_bytes().as_ref()
+        ],
+        bump = auction.auction_bump,
+    )]
+    pub auction: Account<'info, AuctionState>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: Program<'info, IncoLightning>,
+}
+
+impl<'info> ResolveWinners<'info> {
+    /// Computes the winner handle of a batch of bids without their bidders.
+    ///
+    /// `remaining_accounts` holds one group per bid: the bid, then with
+    /// `with_allowances` on public auctions the bidder's allowance PDA for the
+    /// winner handle and the bidder. Private bids do not reveal their bidder,
+    /// who grants themselves access through `grant_access`. Bids already
+    /// resolved keep their handle, grant nothing and emit no event.
+    pub fn handler(
+        &mut self,
+        with_allowances: bool,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<Vec<WinnerChecked>> {
+        rules::check_winner_allowed((&self.auction.auction_status).into())
+            .map_err(AuctionError::from)?;
+
+        let group_len = if with_allowances && !self.auction.private_bids {
+            3
+        } else {
+            1
+        };
+        require!(
+            !remaining_accounts.is_empty() && remaining_accounts.len() % group_len == 0,
+            AuctionError::InvalidResolveAccounts
+        );
+
+        let inco = self.inco_lightning_program.to_account_info();
+        let payer = self.payer.to_account_info();
+        let system_program = self.system_program.to_account_info();
+        let mut ops = IncoOps::new(inco.clone(), payer.clone());
+        let now = Clock::get()?.unix_timestamp;
+        let mut events = Vec::new();
+
+        for group in remaining_accounts.chunks_exact(group_len) {
+            let (bid_info, grants) = (&group[0], &group[1..]);
+            require_keys_eq!(*bid_info.owner, crate::ID, AuctionError::InvalidAuction);
+            let mut bid = Bid::try_deserialize(&mut &bid_info.try_borrow_data()?[..])?;
+            require!(
+                bid.auction == self.auction.key(),
+                AuctionError::InvalidAuction
+            );
+            if bid.resolved {
+                continue;
+            }
+
+            let is_winner = resolve_bid(&mut ops, &mut self.auction, &mut bid)?;
+            bid.try_serialize(&mut &mut bid_info.try_borrow_mut_data()?[..])?;
+
+            if let [winner_allowance, bidder] = grants {
+                require_keys_eq!(
+                    bidder.key(),
+                    bid.bidder,
+                    AuctionError::InvalidResolveAccounts
+                );
+                set_allowance(
+                    &inco,
+                    &payer,
+                    winner_allowance,
+                    bidder,
+                    &system_program,
+                    is_winner.0,
+                    true,
+                )?;
+            }
+
+            events.push(WinnerChecked {
+                version: EVENT_VERSION,
+                auction: self.auction.key(),
+                bid: bid_info.key(),
+                timestamp: now,
+            });
+        }
+
+        Ok(events)
+    }
+}
@@ -0,0 +1,84 @@
+use anchor_lang::{prelude::*, solana_program::sysvar::instructions as instructions_sysvar};
+use confidential_bid_core as rules;
+use inco_lightning::Euint128;
+
+use crate::{
+    attestation::verify_euint128,
+    constants::{ATTESTER_SEED, AUCTION_SEED, EVENT_VERSION},
+    error::AuctionError,
+    events::BidRevealed,
+    state::{AttesterConfig, AuctionState, Bid},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RevealBid<'info> {
+    /// Anyone may record a disclosed bid, the attestation is the proof
+    pub payer: Signer<'info>,
+
+    /// - Auction state PDA
+    #[account(
+        seeds=[
+            AUCTION_SEED,
+            auction.organizer.key().as_ref(),
+            auction.auction_id.to_le_bytes().as_ref()
+        ],
+        bump = auction.auction_bump,
+    )]
+    pub auction: Account<'info, AuctionState>,
+
+    #[account(
+        mut,
+        constraint = bid.auction == auction.key() @ AuctionError::InvalidAuction
+    )]
+    pub bid: Account<'info, Bid>,
+
+    #[account(seeds = [ATTESTER_SEED], bump = attester_config.bump)]
+    pub attester_config: Account<'info, AttesterConfig>,
+
+    /// CHECK: instructions sysvar holding the Ed25519 attestation of the bid amount
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+impl<'info> RevealBid<'info> {
+    /// Records the plaintext amount of a bid on an auction created with
+    /// `disclose_bids`, proven by an attested decryption of `bid_amount`.
+    /// Waits until the `disclose_bids` crank has covered every bid, so no
+    /// amount is published while others are still undisclosed.
+    /// With every bid recorded, the bid book and its sequence order let
+    /// anyone recompute the winner.
+    pub fn handler(&mut self) -> Result<BidRevealed> {
+        rules::check_reveal_bid((&self.auction.auction_status).into())
+            .map_err(AuctionError::from)?;
+        require!(
+            self.auction.disclose_bids,
+            AuctionError::DisclosureNotEnabled
+        );
+        require!(
+            self.auction.disclosed_count == self.auction.bid_count,
+            AuctionError::DisclosureIncomplete
+        );
+        require!(
+            self.bid.revealed_amount.is_none(),
+            AuctionError::BidAlreadyRevealed
+        );
+
+        let amount = verify_euint128(
+            &self.instructions,
+            &self.attester_config.attester,
+            Euint128(self.bid.bid_amount),
+        )?;
+        let amount = u64::try_from(amount).map_err(|_| AuctionError::MathOverflow)?;
+        self.bid.revealed_amount = Some(amount);
+
+        Ok(BidRevealed {
+            version: EVENT_VERSION,
+            auction: self.auction.key(),
+            bid: self.bid.key(),
+            sequence: self.bid.sequence,
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        })
+    }
+}
@@ -0,0 +1,91 @@
+use anchor_lang::{prelude::*, solana_program::sysvar::instructions as instructions_sysvar};
+use confidential_bid_core as rules;
+use inco_lightning::{Ebool, Euint128};
+
+use crate::{
+    attestation::{verify_ebool, verify_euint128},
+    constants::{ATTESTER_SEED, AUCTION_SEED, EVENT_VERSION},
+    error::AuctionError,
+    events::AuctionResultRevealed,
+    state::{AttesterConfig, AuctionState, Bid},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RevealResult<'info> {
+    /// Anyone may publish the result, the attestations are the proof
+    pub payer: Signer<'info>,
+
+    /// - Auction state PDA
+    #[account(
+        mut,
+        seeds=[
+            AUCTION_SEED,
+            auction.organizer.key().as_ref(),
+            auction.auction_id.to_le_bytes().as_ref()
+        ],
+        bump = auction.auction_bump,
+    )]
+    pub auction: Account<'info, AuctionState>,
+
+    #[account(constraint = winning_bid.auction == auction.key() @ AuctionError::InvalidAuction)]
+    pub winning_bid: Account<'info, Bid>,
+
+    #[account(seeds = [ATTESTER_SEED], bump = attester_config.bump)]
+    pub attester_config: Account<'info, AttesterConfig>,
+
+    /// CHECK: instructions sysvar holding the Ed25519 attestations
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+impl<'info> RevealResult<'info> {
+    /// Publishes the plaintext clearing price and the winning bid of a
+    /// settled auction created with `reveal_on_settle`.
+    ///
+    /// The preceding Ed25519 instruction attests the winner handle of
+    /// `winning_bid` and the clearing price handle computed at settlement.
+    /// No bid amount is attested, so losing bids stay confidential beyond
+    /// what the price itself shows.
+    pub fn handler(&mut self) -> Result<AuctionResultRevealed> {
+        rules::check_reveal((&self.auction.auction_status).into()).map_err(AuctionError::from)?;
+        require!(
+            self.auction.reveal_on_settle,
+            AuctionError::RevealNotEnabled
+        );
+        require!(
+            self.auction.winning_bid.is_none(),
+            AuctionError::ResultAlreadyRevealed
+        );
+        require!(self.winning_bid.resolved, AuctionError::BidNotResolved);
+
+        let instructions = self.instructions.to_account_info();
+        let attester = self.attester_config.attester;
+        let won = verify_ebool(
+            &instructions,
+            &attester,
+            Ebool(self.winning_bid.is_winner_handle),
+        )?;
+        require!(won, AuctionError::NotWinner);
+
+        let price = verify_euint128(
+            &instructions,
+            &attester,
+            Euint128(self.auction.clearing_price_handle),
+        )?;
+        let clearing_price = u64::try_from(price).map_err(|_| AuctionError::MathOverflow)?;
+
+        self.auction.clearing_price = Some(clearing_price);
+        self.auction.winning_bid = Some(self.winning_bid.key());
+
+        Ok(AuctionResultRevealed {
+            version: EVENT_VERSION,
+            auction_id: self.auction.auction_id,
+            organizer: self.auction.organizer,
+            winning_bid: self.winning_bid.key(),
+            winner: self.winning_bid.bidder,
+            clearing_price,
+            timestamp: Clock::get()?.unix_timestamp,
+        })
+    }
+}
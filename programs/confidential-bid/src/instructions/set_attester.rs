@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::ATTESTER_SEED, error::AuctionError, program::ConfidentialBid, state::AttesterConfig,
+};
+
+#[derive(Accounts)]
+pub struct SetAttester<'info> {
+    /// Upgrade authority of this program
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + AttesterConfig::INIT_SPACE,
+        seeds = [ATTESTER_SEED],
+        bump
+    )]
+    pub attester_config: Account<'info, AttesterConfig>,
+
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key())
+            @ AuctionError::Unauthorized
+    )]
+    pub program: Program<'info, ConfidentialBid>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(authority.key())
+            @ AuctionError::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetAttester<'info> {
+    /// Sets the key trusted to attest decryptions, replacing any previous one
+    pub fn handler(&mut self, attester: Pubkey, bumps: &SetAttesterBumps) -> Result<()> {
+        self.attester_config.set_inner(AttesterConfig {
+            attester,
+            bump: bumps.attester_config,
+        });
+        Ok(())
+    }
+}
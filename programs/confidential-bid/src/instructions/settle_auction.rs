@@ -0,0 +1,196 @@
+use anchor_lang::prelude::*;
+use confidential_bid_core::{self as rules, EncryptedOps};
+use inco_lightning::{Ebool, Euint128, IncoLightning, ID as INCO_LIGHTNING_ID};
+
+use crate::{
+    constants::{AUCTION_SEED, BPS_DENOMINATOR, EVENT_VERSION},
+    error::AuctionError,
+    events::AuctionSettled,
+    inco::{inco_ata_address, IncoOps, INCO_TOKEN_ID},
+    metadata::{metadata_address, prize_royalty},
+    state::AuctionState,
+    vault::transfer_bid,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SettleAuction<'info> {
+    #[account(mut)]
+    pub organizer: Signer<'info>,
+
+    /// - Auction state PDA
+    #[account(
+        mut,
+        seeds=[
+            AUCTION_SEED,
+            organizer.key().as_ref(),
+            auction.auction_id.to_le_bytes().as_ref()
+        ],
+        bump = auction.auction_bump,
+    )]
+    pub auction: Account<'info, AuctionState>,
+
+    /// CHECK: Inco mint used for bidding, must match auction state
+    pub bid_mint: AccountInfo<'info>,
+
+    /// CHECK: shared auction escrow Inco token account, must match auction state
+    #[account(mut)]
+    pub bid_vault: AccountInfo<'info>,
+
+    /// CHECK: organizer's Inco token account receiving the remainder of the proceeds
+    #[account(mut)]
+    pub organizer_bid_ata: AccountInfo<'info>,
+
+    /// CHECK: Metaplex metadata PDA of the prize mint, royalties are only skipped while it does not exist
+    #[account(address = metadata_address(&auction.mint) @ AuctionError::InvalidPrizeMetadata)]
+    pub prize_metadata: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Inco Token program
+    #[account(address = INCO_TOKEN_ID)]
+    pub inco_token_program: AccountInfo<'info>,
+
+    /// CHECK: Inco Lightning program
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: Program<'info, IncoLightning>,
+}
+
+impl<'info> SettleAuction<'info> {
+    /// Pays the clearing price out of the bid vault.
+    ///
+    /// `remaining_accounts` holds the Inco token account of every payout
+    /// recipient, in the order they are stored on the auction, followed by the
+    /// Inco token account of every verified creator in metadata order when
+    /// the prize has Metaplex metadata.
+    pub fn handler(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<AuctionSettled> {
+        let settled =
+            rules::settle((&self.auction.auction_status).into()).map_err(AuctionError::from)?;
+        rules::check_resolved(
+            (!self.auction.hide_bid_count).then_some(self.auction.bid_count),
+            self.auction.resolved_count,
+        )
+        .map_err(AuctionError::from)?;
+        require!(
+            self.organizer.key() == self.auction.organizer.key(),
+            AuctionError::Unauthorized
+        );
+        require!(
+            self.bid_mint.key() == self.auction.bid_token_mint.key(),
+            AuctionError::InvalidBidMint
+        );
+        require!(
+            self.bid_vault.key() == self.auction.bid_vault,
+            AuctionError::InvalidBidVault
+        );
+        require!(
+            self.organizer_bid_ata.key()
+                == inco_ata_address(&self.organizer.key(), &self.bid_mint.key()),
+            AuctionError::InvalidPayoutRecipient
+        );
+        require!(
+            remaining_accounts.len() >= self.auction.payout_recipients.len(),
+            AuctionError::InvalidPayoutRecipient
+        );
+        let (recipient_atas, creator_atas) =
+            remaining_accounts.split_at(self.auction.payout_recipients.len());
+
+        // Creator royalties are owed whenever the prize has metadata
+        let mut royalty_rates = Vec::new();
+        if let Some(royalty) =
+            prize_royalty(&self.prize_metadata.to_account_info(), &self.auction.mint)?
+        {
+            let creators: Vec<_> = royalty.verified_creators().collect();
+            require!(
+                creator_atas.len() == creators.len(),
+                AuctionError::InvalidRoyaltyCreator
+            );
+            for (creator, creator_ata) in creators.iter().zip(creator_atas.iter()) {
+                require!(
+                    creator_ata.key() == inco_ata_address(&creator.address, &self.bid_mint.key()),
+                    AuctionError::InvalidRoyaltyCreator
+                );
+                // seller_fee_basis_points is in bps and each creator share in percent
+                royalty_rates.push(royalty.seller_fee_basis_points as u128 * creator.share as u128);
+            }
+        } else {
+            require!(creator_atas.is_empty(), AuctionError::InvalidRoyaltyCreator);
+        }
+
+        let payout_recipients = self.auction.payout_recipients.clone();
+        for (payout, recipient_ata) in payout_recipients.iter().zip(recipient_atas.iter()) {
+            require!(
+                recipient_ata.key() == inco_ata_address(&payout.recipient, &self.bid_mint.key()),
+                AuctionError::InvalidPayoutRecipient
+            );
+        }
+        let share_bps: Vec<u16> = payout_recipients
+            .iter()
+            .map(|payout| payout.share_bps)
+            .collect();
+
+        let mut ops = IncoOps::new(
+            self.inco_lightning_program.to_account_info(),
+            self.organizer.to_account_info(),
+        );
+        // Without bids there is no highest handle, the unmet reserve zeroes the price anyway
+        let highest = match self.auction.standings() {
+            Some(standings) => standings.highest,
+            None => ops.constant(0)?,
+        };
+        let price = rules::clearing_price(
+            &mut ops,
+            self.auction.auction_type.into(),
+            highest,
+            self.auction.second_highest_bid.map(Euint128),
+            self.auction.reserve_price,
+        )?;
+        // reveal_result attests this handle, which only ever decrypts to the price
+        self.auction.clearing_price_handle = price.0;
+        let proceeds = rules::proceeds(&mut ops, price, Ebool(self.auction.reserve_met_handle))?;
+        let payouts = rules::split_proceeds(
+            &mut ops,
+            proceeds,
+            &royalty_rates,
+            &share_bps,
+            BPS_DENOMINATOR,
+        )?;
+
+        for (creator_ata, royalty) in creator_atas.iter().zip(payouts.royalties) {
+            self.transfer_from_vault(creator_ata, royalty)?;
+        }
+        for (recipient_ata, share) in recipient_atas.iter().zip(payouts.shares) {
+            self.transfer_from_vault(recipient_ata, share)?;
+        }
+        let organizer_bid_ata = self.organizer_bid_ata.to_account_info();
+        self.transfer_from_vault(&organizer_bid_ata, payouts.remainder)?;
+
+        self.auction.auction_status = settled.into();
+
+        Ok(AuctionSettled {
+            version: EVENT_VERSION,
+            auction_id: self.auction.auction_id,
+            organizer: self.organizer.key(),
+            recipient_count: payout_recipients.len() as u8,
+            creator_count: royalty_rates.len() as u8,
+            timestamp: Clock::get()?.unix_timestamp,
+        })
+    }
+
+    fn transfer_from_vault(
+        &self,
+        destination: &AccountInfo<'info>,
+        amount: Euint128,
+    ) -> Result<()> {
+        transfer_bid(
+            &self.auction,
+            &self.bid_mint,
+            &self.bid_vault,
+            destination,
+            &self.inco_token_program,
+            &self.inco_lightning_program.to_account_info(),
+            &self.system_program.to_account_info(),
+            amount,
+        )
+    }
+}
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use confidential_bid_core as rules;
+
+use crate::{
+    constants::{AUCTION_SEED, EVENT_VERSION},
+    error::AuctionError,
+    events::AuctionUpdated,
+    state::{AuctionState, AuctionType},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateAuction<'info> {
+    pub organizer: Signer<'info>,
+
+    /// - Auction state PDA
+    #[account(
+        mut,
+        seeds=[
+            AUCTION_SEED,
+            organizer.key().as_ref(),
+            auction.auction_id.to_le_bytes().as_ref()
+        ],
+        bump = auction.auction_bump,
+    )]
+    pub auction: Account<'info, AuctionState>,
+}
+
+impl<'info> UpdateAuction<'info> {
+    pub fn handler(
+        &mut self,
+        start_time: i64,
+        end_time: i64,
+        reserve_price: u64,
+        auction_type: AuctionType,
+    ) -> Result<AuctionUpdated> {
+        let auction = &mut self.auction;
+        let current_time = Clock::get()?.unix_timestamp;
+
+        require!(
+            self.organizer.key() == auction.organizer.key(),
+            AuctionError::Unauthorized
+        );
+        // Parameters are frozen once the auction starts or receives a bid
+        rules::check_update(auction.schedule(), auction.bid_count, current_time)
+            .map_err(AuctionError::from)?;
+        rules::validate_schedule(start_time, end_time, current_time).map_err(AuctionError::from)?;
+
+        auction.start_time = start_time;
+        auction.end_time = end_time;
+        auction.reserve_price = reserve_price;
+        auction.auction_type = auction_type;
+
+        Ok(AuctionUpdated {
+            version: EVENT_VERSION,
+            auction_id: auction.auction_id,
+            organizer: self.organizer.key(),
+            start_time,
+            end_time,
+            reserve_price,
+            auction_type,
+        })
+    }
+}
@@ -0,0 +1,256 @@
+//! Account layouts from before `version` was added to `AuctionState` and `Bid`,
+//! and `AuctionState` at version 1, before `clearing_price_handle` outgrew its
+//! reserved space.
+//!
+//! Only the migrate instructions read them. Legacy accounts are told apart by
+//! their size, which is always smaller than the current `INIT_SPACE`.
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+use crate::{
+    constants::{
+        AUCTION_STATE_RESERVED, AUCTION_STATE_VERSION, BID_RESERVED, LEGACY_BID_VERSION,
+        MAX_PAYOUT_RECIPIENTS,
+    },
+    error::AuctionError,
+    state::{AuctionState, AuctionStatus, AuctionType, Bid, PayoutRecipient},
+};
+
+/// Reserved bytes of `AuctionState` at version 1
+const AUCTION_STATE_V1_RESERVED: usize = 14;
+
+/// `AuctionState` as first deployed, field for field
+#[derive(AnchorDeserialize)]
+pub struct AuctionStateV0 {
+    pub organizer: Pubkey,
+    pub mint: Pubkey,
+    pub highest_bidder: Option<Pubkey>,
+    pub vault: Pubkey,
+    pub bid_token_mint: Pubkey,
+    pub bid_vault: Pubkey,
+    pub highest_bid: u128,
+    pub second_highest_bid: Option<u128>,
+    pub highest_timestamp: u128,
+    pub reserve_met_handle: u128,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub reserve_price: u64,
+    pub auction_id: u64,
+    pub bid_count: u32,
+    pub auction_status: AuctionStatus,
+    pub auction_type: AuctionType,
+    pub prize_claimed: bool,
+    pub auction_bump: u8,
+}
+
+impl AuctionStateV0 {
+    pub fn migrate(self) -> AuctionState {
+        AuctionState {
+            organizer: self.organizer,
+            mint: self.mint,
+            highest_bidder: self.highest_bidder,
+            vault: self.vault,
+            bid_token_mint: self.bid_token_mint,
+            bid_vault: self.bid_vault,
+            highest_bid: self.highest_bid,
+            second_highest_bid: self.second_highest_bid,
+            highest_timestamp: self.highest_timestamp,
+            reserve_met_handle: self.reserve_met_handle,
+            start_time: self.start_time,
+            end_time: self.end_time,
+            total_extension: 0,
+            reserve_price: self.reserve_price,
+            auction_id: self.auction_id,
+            bid_count: self.bid_count,
+            encrypted_bid_count: 0,
+            hide_bid_count: false,
+            private_bids: false,
+            auction_status: self.auction_status,
+            auction_type: self.auction_type,
+            prize_claimed: self.prize_claimed,
+            auditor: None,
+            payout_recipients: Vec::new(),
+            auction_bump: self.auction_bump,
+            version: AUCTION_STATE_VERSION,
+            deferred_standings: false,
+            standings_final: false,
+            resolved_count: 0,
+            reveal_on_settle: false,
+            clearing_price: None,
+            winning_bid: None,
+            disclose_bids: false,
+            clearing_price_handle: 0,
+            disclosed_count: 0,
+            reserved: [0; AUCTION_STATE_RESERVED],
+        }
+    }
+}
+
+/// `AuctionState` at version 1, field for field
+#[derive(AnchorDeserialize, InitSpace)]
+pub struct AuctionStateV1 {
+    pub organizer: Pubkey,
+    pub mint: Pubkey,
+    pub highest_bidder: Option<Pubkey>,
+    pub vault: Pubkey,
+    pub bid_token_mint: Pubkey,
+    pub bid_vault: Pubkey,
+    pub highest_bid: u128,
+    pub second_highest_bid: Option<u128>,
+    pub highest_timestamp: u128,
+    pub reserve_met_handle: u128,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub total_extension: i64,
+    pub reserve_price: u64,
+    pub auction_id: u64,
+    pub bid_count: u32,
+    pub encrypted_bid_count: u128,
+    pub hide_bid_count: bool,
+    pub private_bids: bool,
+    pub auction_status: AuctionStatus,
+    pub auction_type: AuctionType,
+    pub prize_claimed: bool,
+    pub auditor: Option<Pubkey>,
+    #[max_len(MAX_PAYOUT_RECIPIENTS)]
+    pub payout_recipients: Vec<PayoutRecipient>,
+    pub auction_bump: u8,
+    pub version: u8,
+    pub deferred_standings: bool,
+    pub standings_final: bool,
+    pub resolved_count: u32,
+    pub reveal_on_settle: bool,
+    pub clearing_price: Option<u64>,
+    pub winning_bid: Option<Pubkey>,
+    pub disclose_bids: bool,
+    pub reserved: [u8; AUCTION_STATE_V1_RESERVED],
+}
+
+impl AuctionStateV1 {
+    /// Version 1 settlement stored no price handle, so `reveal_result` can't
+    /// attest one, and kept no disclosure count, so `reveal_bid` waits for
+    /// `disclose_bids` to run over every bid.
+    pub fn migrate(self) -> AuctionState {
+        AuctionState {
+            organizer: self.organizer,
+            mint: self.mint,
+            highest_bidder: self.highest_bidder,
+            vault: self.vault,
+            bid_token_mint: self.bid_token_mint,
+            bid_vault: self.bid_vault,
+            highest_bid: self.highest_bid,
+            second_highest_bid: self.second_highest_bid,
+            highest_timestamp: self.highest_timestamp,
+            reserve_met_handle: self.reserve_met_handle,
+            start_time: self.start_time,
+            end_time: self.end_time,
+            total_extension: self.total_extension,
+            reserve_price: self.reserve_price,
+            auction_id: self.auction_id,
+            bid_count: self.bid_count,
+            encrypted_bid_count: self.encrypted_bid_count,
+            hide_bid_count: self.hide_bid_count,
+            private_bids: self.private_bids,
+            auction_status: self.auction_status,
+            auction_type: self.auction_type,
+            prize_claimed: self.prize_claimed,
+            auditor: self.auditor,
+            payout_recipients: self.payout_recipients,
+            auction_bump: self.auction_bump,
+            version: AUCTION_STATE_VERSION,
+            deferred_standings: self.deferred_standings,
+            standings_final: self.standings_final,
+            resolved_count: self.resolved_count,
+            reveal_on_settle: self.reveal_on_settle,
+            clearing_price: self.clearing_price,
+            winning_bid: self.winning_bid,
+            disclose_bids: self.disclose_bids,
+            clearing_price_handle: 0,
+            disclosed_count: 0,
+            reserved: [0; AUCTION_STATE_RESERVED],
+        }
+    }
+}
+
+/// `Bid` as first deployed, field for field
+#[derive(AnchorDeserialize)]
+pub struct BidV0 {
+    pub bidder: Pubkey,
+    pub auction: Pubkey,
+    pub bid_amount: u128,
+    pub time_stamp: u128,
+    pub is_winner_handle: u128,
+    pub claimed: bool,
+    pub bid_bump: u8,
+}
+
+impl BidV0 {
+    pub fn migrate(self) -> Bid {
+        Bid {
+            bidder: self.bidder,
+            auction: self.auction,
+            bid_amount: self.bid_amount,
+            time_stamp: self.time_stamp,
+            is_winner_handle: self.is_winner_handle,
+            commitment: [0; 32],
+            claimed: self.claimed,
+            bid_bump: self.bid_bump,
+            version: LEGACY_BID_VERSION,
+            sequence: 0,
+            resolved: false,
+            revealed_amount: None,
+            reserved: [0; BID_RESERVED],
+        }
+    }
+}
+
+/// Decodes a legacy account of this program, rejecting accounts already at `current_len`
+pub fn read_legacy<T: AnchorDeserialize>(
+    account: &AccountInfo,
+    discriminator: &[u8],
+    current_len: usize,
+) -> Result<T> {
+    require_keys_eq!(
+        *account.owner,
+        crate::ID,
+        AuctionError::InvalidLegacyAccount
+    );
+    let data = account.try_borrow_data()?;
+    require!(
+        data.starts_with(discriminator),
+        AuctionError::InvalidLegacyAccount
+    );
+    require!(
+        data.len() < current_len,
+        AuctionError::AccountAlreadyMigrated
+    );
+    T::deserialize(&mut &data[discriminator.len()..])
+        .map_err(|_| error!(AuctionError::InvalidLegacyAccount))
+}
+
+/// Grows `account` to `new_len`, with `payer` covering the extra rent
+pub fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    new_len: usize,
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(new_len);
+    let top_up = rent.saturating_sub(account.lamports());
+    if top_up > 0 {
+        transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+    account.resize(new_len)?;
+    Ok(())
+}
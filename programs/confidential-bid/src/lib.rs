@@ -1,36 +1,73 @@
 use anchor_lang::prelude::*;
 
 declare_id!("Ek9MekGDy6g1CAwoE8AbSXkhBXnkJDMQxTLHFKLpTFii");
-mod constants;
-mod error;
-mod events;
+pub mod attestation;
+pub mod constants;
+pub mod error;
+pub mod events;
+pub mod inco;
 mod instructions;
-mod state;
-use crate::state::AuctionType;
+mod legacy;
+pub mod metadata;
+pub mod returns;
+pub mod state;
+mod vault;
+use crate::{
+    returns::{AuctionSummary, BidStatus, CheckWinnerResult, CloseAuctionResult, PlaceBidResult},
+    state::{AuctionType, PayoutRecipient},
+};
 use instructions::*;
 #[program]
 pub mod confidential_bid {
 
     use super::*;
 
+    #[allow(clippy::too_many_arguments)]
     pub fn create_auction(
         ctx: Context<CreateAuction>,
-        auction_id: u64,
         start_time: i64,
         end_time: i64,
         reserve_price: u64,
         auction_type: AuctionType,
         token_amount: u64,
+        payout_recipients: Vec<PayoutRecipient>,
+        auditor: Option<Pubkey>,
+        hide_bid_count: bool,
+        private_bids: bool,
+        deferred_standings: bool,
+        reveal_on_settle: bool,
+        disclose_bids: bool,
     ) -> Result<()> {
-        ctx.accounts.handler(
-            auction_id,
+        let event = ctx.accounts.handler(
             start_time,
             end_time,
             reserve_price,
             auction_type,
             token_amount,
+            payout_recipients,
+            auditor,
+            hide_bid_count,
+            private_bids,
+            deferred_standings,
+            reveal_on_settle,
+            disclose_bids,
             &ctx.bumps,
         )?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    pub fn update_auction(
+        ctx: Context<UpdateAuction>,
+        start_time: i64,
+        end_time: i64,
+        reserve_price: u64,
+        auction_type: AuctionType,
+    ) -> Result<()> {
+        let event = ctx
+            .accounts
+            .handler(start_time, end_time, reserve_price, auction_type)?;
+        emit_cpi!(event);
         Ok(())
     }
 
@@ -38,28 +75,172 @@ pub mod confidential_bid {
         ctx: Context<'_, '_, '_, 'info, PlaceBid<'info>>,
         token_amount: Vec<u8>,
         input_type: u8,
-    ) -> Result<()> {
-        ctx.accounts.handler(
+        bid_commitment: Option<[u8; 32]>,
+        with_allowances: bool,
+    ) -> Result<PlaceBidResult> {
+        let (result, event) = ctx.accounts.handler(
             token_amount,
             &ctx.bumps,
             input_type,
-            &ctx.remaining_accounts,
+            bid_commitment,
+            with_allowances,
         )?;
-        Ok(())
+        emit_cpi!(event);
+        Ok(result)
     }
 
     pub fn close_auction<'info>(
         ctx: Context<'_, '_, '_, 'info, CloseAuction<'info>>,
-    ) -> Result<()> {
-        ctx.accounts.handler()?;
+    ) -> Result<CloseAuctionResult> {
+        let (result, event) = ctx.accounts.handler()?;
+        emit_cpi!(event);
+        Ok(result)
+    }
+
+    pub fn extend_auction(ctx: Context<ExtendAuction>, new_end_time: i64) -> Result<()> {
+        let event = ctx.accounts.handler(new_end_time)?;
+        emit_cpi!(event);
         Ok(())
     }
 
+    pub fn end_early<'info>(
+        ctx: Context<'_, '_, '_, 'info, EndEarly<'info>>,
+    ) -> Result<CloseAuctionResult> {
+        let (result, ended, closed) = ctx.accounts.handler()?;
+        emit_cpi!(ended);
+        if let Some(closed) = closed {
+            emit_cpi!(closed);
+        }
+        Ok(result)
+    }
+
+    pub fn reduce_bids<'info>(
+        ctx: Context<'_, '_, '_, 'info, ReduceBids<'info>>,
+        start: u32,
+    ) -> Result<()> {
+        ctx.accounts
+            .handler(start, ctx.remaining_accounts, &ctx.bumps)
+    }
+
+    pub fn merge_reductions(ctx: Context<MergeReductions>) -> Result<()> {
+        ctx.accounts.handler()
+    }
+
+    pub fn finalize_reduction<'info>(
+        ctx: Context<'_, '_, '_, 'info, FinalizeReduction<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.handler()
+    }
+
     pub fn check_winner<'info>(
         ctx: Context<'_, '_, '_, 'info, CheckWinner<'info>>,
         input_type: u8,
+        bid_secret: Option<[u8; 32]>,
+        with_allowances: bool,
+    ) -> Result<CheckWinnerResult> {
+        let (result, event) = ctx
+            .accounts
+            .handler(input_type, bid_secret, with_allowances)?;
+        emit_cpi!(event);
+        Ok(result)
+    }
+
+    pub fn resolve_winners<'info>(
+        ctx: Context<'_, '_, '_, 'info, ResolveWinners<'info>>,
+        with_allowances: bool,
+    ) -> Result<()> {
+        let events = ctx
+            .accounts
+            .handler(with_allowances, ctx.remaining_accounts)?;
+        for event in events {
+            emit_cpi!(event);
+        }
+        Ok(())
+    }
+
+    pub fn settle_auction<'info>(
+        ctx: Context<'_, '_, '_, 'info, SettleAuction<'info>>,
+    ) -> Result<()> {
+        let event = ctx.accounts.handler(ctx.remaining_accounts)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    pub fn set_attester(ctx: Context<SetAttester>, attester: Pubkey) -> Result<()> {
+        ctx.accounts.handler(attester, &ctx.bumps)
+    }
+
+    pub fn claim_prize(ctx: Context<ClaimPrize>, bid_secret: Option<[u8; 32]>) -> Result<()> {
+        let event = ctx.accounts.handler(bid_secret)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    pub fn refund_bid(ctx: Context<RefundBid>, bid_secret: Option<[u8; 32]>) -> Result<()> {
+        let event = ctx.accounts.handler(bid_secret)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    pub fn cancel_auction(ctx: Context<CancelAuction>) -> Result<()> {
+        let event = ctx.accounts.handler()?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    pub fn reveal_result(ctx: Context<RevealResult>) -> Result<()> {
+        let event = ctx.accounts.handler()?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    pub fn disclose_bids<'info>(
+        ctx: Context<'_, '_, '_, 'info, DiscloseBids<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.handler(ctx.remaining_accounts)
+    }
+
+    pub fn reveal_bid(ctx: Context<RevealBid>) -> Result<()> {
+        let event = ctx.accounts.handler()?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    pub fn grant_access<'info>(
+        ctx: Context<'_, '_, '_, 'info, GrantAccess<'info>>,
+        handle: u128,
+        value: bool,
+        bid_secret: Option<[u8; 32]>,
     ) -> Result<()> {
-        ctx.accounts.handler(input_type, ctx.remaining_accounts)?;
+        ctx.accounts.handler(handle, value, bid_secret)?;
         Ok(())
     }
+
+    pub fn grant_auditor<'info>(
+        ctx: Context<'_, '_, '_, 'info, GrantAuditor<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.handler(ctx.remaining_accounts)
+    }
+
+    pub fn get_auction_summary(
+        ctx: Context<GetAuctionSummary>,
+        bid_secret: Option<[u8; 32]>,
+    ) -> Result<AuctionSummary> {
+        ctx.accounts.handler(bid_secret)
+    }
+
+    pub fn get_bid_status(
+        ctx: Context<GetBidStatus>,
+        bid_secret: Option<[u8; 32]>,
+    ) -> Result<BidStatus> {
+        ctx.accounts.handler(bid_secret)
+    }
+
+    pub fn migrate_auction(ctx: Context<MigrateAuction>) -> Result<()> {
+        ctx.accounts.handler()
+    }
+
+    pub fn migrate_bid(ctx: Context<MigrateBid>) -> Result<()> {
+        ctx.accounts.handler()
+    }
 }
@@ -0,0 +1,151 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{BPS_DENOMINATOR, METADATA_SEED, TOKEN_METADATA_PROGRAM_ID},
+    error::AuctionError,
+};
+
+/// `Key::MetadataV1` discriminator of a Metaplex Token Metadata account
+const METADATA_V1_KEY: u8 = 4;
+/// Metaplex caps the creators array at five entries
+pub const MAX_CREATORS: usize = 5;
+
+/// Royalty terms read from the prize's Metaplex metadata
+pub struct PrizeRoyalty {
+    pub seller_fee_basis_points: u16,
+    pub creators: Vec<RoyaltyCreator>,
+}
+
+pub struct RoyaltyCreator {
+    pub address: Pubkey,
+    pub verified: bool,
+    pub share: u8, // Percentage of the royalty, creators sum to 100
+}
+
+impl PrizeRoyalty {
+    /// Creators entitled to a payout, unverified creators are never paid
+    pub fn verified_creators(&self) -> impl Iterator<Item = &RoyaltyCreator> {
+        self.creators
+            .iter()
+            .filter(|creator| creator.verified && creator.share > 0)
+    }
+}
+
+/// Metadata PDA of `mint` under the Token Metadata program
+pub fn metadata_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            METADATA_SEED,
+            TOKEN_METADATA_PROGRAM_ID.as_ref(),
+            mint.as_ref(),
+        ],
+        &TOKEN_METADATA_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Royalty terms of `mint`, `None` when its metadata PDA was never created by
+/// the Token Metadata program, which is the only case without royalties
+pub fn prize_royalty(metadata: &AccountInfo, mint: &Pubkey) -> Result<Option<PrizeRoyalty>> {
+    if metadata.data_is_empty() || *metadata.owner != TOKEN_METADATA_PROGRAM_ID {
+        return Ok(None);
+    }
+    read_prize_royalty(metadata, mint).map(Some)
+}
+
+/// Reads the royalty terms of `mint` straight from the metadata account data.
+///
+/// Only the prefix of the account up to the creators array is decoded, so
+/// later Metaplex layout additions do not affect parsing.
+pub fn read_prize_royalty(metadata: &AccountInfo, mint: &Pubkey) -> Result<PrizeRoyalty> {
+    require_keys_eq!(
+        metadata.key(),
+        metadata_address(mint),
+        AuctionError::InvalidPrizeMetadata
+    );
+    require_keys_eq!(
+        *metadata.owner,
+        TOKEN_METADATA_PROGRAM_ID,
+        AuctionError::InvalidPrizeMetadata
+    );
+
+    let data = metadata.try_borrow_data()?;
+    let mut reader = MetadataReader { data: &data[..] };
+
+    require!(
+        reader.read_u8()? == METADATA_V1_KEY,
+        AuctionError::InvalidPrizeMetadata
+    );
+    let _update_authority = reader.read_pubkey()?;
+    require_keys_eq!(
+        reader.read_pubkey()?,
+        *mint,
+        AuctionError::InvalidPrizeMetadata
+    );
+    reader.skip_string()?; // name
+    reader.skip_string()?; // symbol
+    reader.skip_string()?; // uri
+
+    let seller_fee_basis_points = reader.read_u16()?;
+    require!(
+        seller_fee_basis_points <= BPS_DENOMINATOR,
+        AuctionError::InvalidPrizeMetadata
+    );
+
+    let mut creators = Vec::new();
+    if reader.read_u8()? == 1 {
+        let len = reader.read_u32()? as usize;
+        require!(len <= MAX_CREATORS, AuctionError::InvalidPrizeMetadata);
+        for _ in 0..len {
+            creators.push(RoyaltyCreator {
+                address: reader.read_pubkey()?,
+                verified: reader.read_u8()? == 1,
+                share: reader.read_u8()?,
+            });
+        }
+    }
+
+    Ok(PrizeRoyalty {
+        seller_fee_basis_points,
+        creators,
+    })
+}
+
+/// Minimal Borsh cursor over raw metadata account bytes
+struct MetadataReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> MetadataReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        require!(self.data.len() >= len, AuctionError::InvalidPrizeMetadata);
+        let (head, tail) = self.data.split_at(len);
+        self.data = tail;
+        Ok(head)
+    }
+
+    fn read_u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn read_u16(&mut self) -> Result<u16> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn read_u32(&mut self) -> Result<u32> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn read_pubkey(&mut self) -> Result<Pubkey> {
+        let bytes = self.take(32)?;
+        Pubkey::try_from(bytes).map_err(|_| error!(AuctionError::InvalidPrizeMetadata))
+    }
+
+    fn skip_string(&mut self) -> Result<()> {
+        let len = self.read_u32()? as usize;
+        self.take(len)?;
+        Ok(())
+    }
+}
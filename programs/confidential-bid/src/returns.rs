@@ -0,0 +1,62 @@
+//! Borsh-encoded results handed back to clients through return data.
+//!
+//! They carry the handles an instruction produced, so callers can derive the
+//! matching Inco allowance PDAs without decoding account layouts. Return data
+//! is printed in the public `Program return:` log of every sent transaction,
+//! so it only holds handles already public in account data.
+use anchor_lang::prelude::*;
+
+use crate::state::{AuctionPhase, AuctionStatus, AuctionType};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct PlaceBidResult {
+    pub bid: Pubkey,
+    pub bid_amount_handle: u128,
+    pub time_stamp_handle: u128,
+    pub highest_bid_handle: u128, // 0 when the auction defers its standings
+    pub second_highest_bid_handle: u128, // 0 until the auction has a second bid, or when deferred
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct CheckWinnerResult {
+    pub is_winner_handle: u128,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct CloseAuctionResult {
+    pub reserve_met_handle: u128,
+}
+
+/// Public view of an auction as seen by `caller`, versioned by `VIEW_VERSION`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct AuctionSummary {
+    pub version: u8,
+    pub auction: Pubkey,
+    pub auction_id: u64,
+    pub organizer: Pubkey,
+    pub auction_type: AuctionType,
+    pub status: AuctionStatus,
+    pub phase: AuctionPhase,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub time_remaining: i64,
+    pub bid_count: Option<u32>, // None when the auction hides its bid count
+    pub has_bid: bool,          // Whether the caller's bid PDA exists
+    pub grantable_handles: Vec<u128>, // Handles the caller's role can be granted, granted yet or not
+}
+
+/// Public view of a single bid as seen by `caller`, versioned by `VIEW_VERSION`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct BidStatus {
+    pub version: u8,
+    pub bid: Pubkey,
+    pub auction: Pubkey,
+    pub phase: AuctionPhase,
+    pub owned_by_caller: bool,
+    pub winner_checked: bool,
+    pub claimed: bool,
+    pub bid_amount_handle: u128,
+    pub time_stamp_handle: u128,
+    pub is_winner_handle: u128,
+    pub grantable_handles: Vec<u128>, // Handles the caller's role can be granted, granted yet or not
+}
@@ -1,6 +1,17 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, solana_program::hash::hashv};
+use confidential_bid_core::{Pricing, Schedule, Standings, Status};
+use inco_lightning::Euint128;
 
+use crate::constants::{AUCTION_STATE_RESERVED, BID_RESERVED, MAX_PAYOUT_RECIPIENTS};
+
+/// Auction account data is public.
+///
+/// The `u128` handle fields (`highest_bid`, `second_highest_bid`,
+/// `highest_timestamp`, `reserve_met_handle`, `encrypted_bid_count`) reference
+/// Inco ciphertexts that only allowed addresses can decrypt. Every other field
+/// is plaintext readable by anyone.
 #[account]
+#[derive(InitSpace)]
 pub struct AuctionState {
     pub organizer: Pubkey,
     pub mint: Pubkey, // Token being auctioned
@@ -10,44 +21,267 @@ pub struct AuctionState {
     pub bid_vault: Pubkey,      // Inco bid  vault
     pub highest_bid: u128,
     pub second_highest_bid: Option<u128>,
-    pub highest_timestamp: u128, // Encrypted timestamp of earliest highest bid
+    pub highest_timestamp: u128, // Encrypted placement key of earliest highest bid
     pub reserve_met_handle: u128,
     pub start_time: i64,
     pub end_time: i64,
-    pub reserve_price: u64, // Minimum bid in bid_token_mint
+    pub total_extension: i64, // Seconds end_time was pushed back by the organizer
+    pub reserve_price: u64,   // Minimum bid in bid_token_mint
     pub auction_id: u64,
-    pub bid_count: u32,
+    pub bid_count: u32, // Public participation count, stays 0 when hide_bid_count is set
+    pub encrypted_bid_count: u128, // Participation count tracked instead when hide_bid_count is set
+    /// Hides the counter only. Every bid still emits `BidPlaced` and creates a
+    /// `Bid` account holding `auction`, so anyone can count the bids from
+    /// events or by filtering program accounts on that field. Without a public
+    /// count, settlement cannot wait for every winner handle and `end_early`
+    /// is refused.
+    pub hide_bid_count: bool,
+    pub private_bids: bool, // Bids are keyed by a commitment instead of the bidder key
     pub auction_status: AuctionStatus,
     pub auction_type: AuctionType,
     pub prize_claimed: bool,
+    pub auditor: Option<Pubkey>, // Allowed to decrypt every bid and auction handle
+    #[max_len(MAX_PAYOUT_RECIPIENTS)]
+    pub payout_recipients: Vec<PayoutRecipient>, // Co-owners paid out of the proceeds at settlement
     pub auction_bump: u8,
+    pub version: u8,                 // Layout version, see AUCTION_STATE_VERSION
+    pub deferred_standings: bool, // Bids are only recorded, reduce_bids computes the standings after end_time
+    pub standings_final: bool,    // Set once every bid of a deferred auction has been reduced
+    pub resolved_count: u32, // Bids whose winner handle is computed, settlement waits for all of them
+    pub reveal_on_settle: bool, // reveal_result may publish the clearing price and winning bid once settled
+    pub clearing_price: Option<u64>, // Plaintext price, set by reveal_result
+    pub winning_bid: Option<Pubkey>, // Winning bid account, set by reveal_result
+    pub disclose_bids: bool, // disclose_bids lets DISCLOSURE_ADDRESS decrypt every bid amount once closed
+    pub clearing_price_handle: u128, // Encrypted price computed at settlement, what reveal_result attests
+    pub disclosed_count: u32,        // Bids disclosed so far, reveal_bid waits for all of them
+    pub reserved: [u8; AUCTION_STATE_RESERVED], // Zeroed space for fields added by later versions
 }
 impl AuctionState {
-    pub const LEN: usize =
-        32 + 32 + 1 + 32 + 32 + 32 + 16 + 1 + 16 + 16 + 16 + 8 + 8 + 8 + 8 + 4 + 1 + 1 + 1 + 1;
+    /// Public fields the core timing rules read
+    pub fn schedule(&self) -> Schedule {
+        Schedule {
+            status: (&self.auction_status).into(),
+            start_time: self.start_time,
+            end_time: self.end_time,
+        }
+    }
+
+    /// `None` before the first bid, which is the only time `highest_timestamp` is unset
+    pub fn standings(&self) -> Option<Standings<Euint128>> {
+        (self.highest_timestamp != 0).then(|| Standings {
+            highest: Euint128(self.highest_bid),
+            second_highest: self.second_highest_bid.map(Euint128),
+            highest_timestamp: Euint128(self.highest_timestamp),
+        })
+    }
+
+    pub fn set_standings(&mut self, standings: &Standings<Euint128>) {
+        self.highest_bid = standings.highest.0;
+        self.second_highest_bid = standings.second_highest.map(|handle| handle.0);
+        self.highest_timestamp = standings.highest_timestamp.0;
+    }
+
+    /// Non-zero Inco handles held by the auction, readable by the organizer and auditor
+    pub fn handles(&self) -> Vec<u128> {
+        [
+            Some(self.highest_bid),
+            self.second_highest_bid,
+            Some(self.highest_timestamp),
+            Some(self.reserve_met_handle),
+            Some(self.encrypted_bid_count),
+            Some(self.clearing_price_handle),
+        ]
+        .into_iter()
+        .flatten()
+        .filter(|handle| *handle != 0)
+        .collect()
+    }
+
+    /// Lifecycle phase at `now`, an open auction past its end time counts as ended
+    pub fn phase(&self, now: i64) -> AuctionPhase {
+        match self.auction_status {
+            AuctionStatus::Open if now < self.start_time => AuctionPhase::Upcoming,
+            AuctionStatus::Open if now < self.end_time => AuctionPhase::Live,
+            AuctionStatus::Open | AuctionStatus::Closed => AuctionPhase::Ended,
+            AuctionStatus::Settled => AuctionPhase::Settled,
+            AuctionStatus::Cancelled => AuctionPhase::Cancelled,
+        }
+    }
+
+    /// Seconds until the auction starts when upcoming, until it ends when live, 0 otherwise
+    pub fn time_remaining(&self, now: i64) -> i64 {
+        match self.phase(now) {
+            AuctionPhase::Upcoming => self.start_time - now,
+            AuctionPhase::Live => self.end_time - now,
+            AuctionPhase::Ended | AuctionPhase::Settled | AuctionPhase::Cancelled => 0,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct PayoutRecipient {
+    pub recipient: Pubkey,
+    pub share_bps: u16, // Share of the proceeds in basis points
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct OrganizerProfile {
+    pub organizer: Pubkey,
+    pub auction_count: u64, // Next auction id, ids 0..auction_count have all been used
+    pub bump: u8,
 }
 
+/// Bid account data is public: `bidder`, `auction`, `commitment` and `claimed`
+/// are plaintext, `bid_amount`, `time_stamp` and `is_winner_handle` are Inco
+/// handles. Private bids leave `bidder` as the default key and are placed by
+/// a one-off bid key, a relayer paying for them, so no account of the bid
+/// names the bidder's wallet.
 #[account]
 #[derive(InitSpace)]
 pub struct Bid {
     pub bidder: Pubkey,
     pub auction: Pubkey,
-    pub bid_amount: u128,
-    pub time_stamp: u128,
+    pub bid_amount: u128, // Amount escrowed in the bid vault, zero when the balance fell short
+    pub time_stamp: u128, // Encrypted placement key, unix time << 32 | position
     pub is_winner_handle: u128, // encrypted bool
-    pub claimed: bool,          // prevent double withdraw
+    pub commitment: [u8; 32], // hash(bidder, secret) for private bids, zeroed otherwise
+    pub claimed: bool,    // prevent double withdraw
     pub bid_bump: u8,
+    pub version: u8,    // Layout version, see BID_VERSION and LEGACY_BID_VERSION
+    pub sequence: u32,  // Placement order, public like the bid count and stays 0 when it is hidden
+    pub resolved: bool, // Winner handle computed, by check_winner or resolve_winners
+    pub revealed_amount: Option<u64>, // Plaintext amount recorded by reveal_bid on disclosed auctions
+    pub reserved: [u8; BID_RESERVED], // Zeroed space for fields added by later versions
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+impl Bid {
+    /// Commitment keying a private bid, binds it to `bidder` without storing the key
+    pub fn commitment(bidder: &Pubkey, secret: &[u8; 32]) -> [u8; 32] {
+        hashv(&[bidder.as_ref(), secret]).to_bytes()
+    }
+
+    /// Last PDA seed of a bid: the commitment for private bids, the bidder key otherwise
+    pub fn seed_key(bidder: &Pubkey, commitment: Option<[u8; 32]>) -> [u8; 32] {
+        commitment.unwrap_or(bidder.to_bytes())
+    }
+
+    /// Whether `signer` placed this bid, proven by the secret for private bids
+    pub fn is_owned_by(&self, signer: &Pubkey, secret: Option<&[u8; 32]>) -> bool {
+        match secret {
+            Some(secret) => {
+                self.bidder == Pubkey::default()
+                    && self.commitment == Self::commitment(signer, secret)
+            }
+            None => self.bidder == *signer,
+        }
+    }
+
+    /// Non-zero Inco handles held by the bid, readable by the bidder and auditor
+    pub fn handles(&self) -> Vec<u128> {
+        [self.bid_amount, self.time_stamp, self.is_winner_handle]
+            .into_iter()
+            .filter(|handle| *handle != 0)
+            .collect()
+    }
+}
+
+/// Program-wide key whose Ed25519 signatures attest Inco decryptions, set
+/// by the program's upgrade authority
+#[account]
+#[derive(InitSpace)]
+pub struct AttesterConfig {
+    pub attester: Pubkey,
+    pub bump: u8,
+}
+
+/// Partial standings of a deferred auction over the bids with sequence
+/// `start..start + reduced`, handles as in `AuctionState`.
+#[account]
+#[derive(InitSpace)]
+pub struct BidReduction {
+    pub auction: Pubkey,
+    pub payer: Pubkey, // Refunded when the reduction is merged or finalized
+    pub start: u32,
+    pub reduced: u32,
+    pub highest: u128,
+    pub second_highest: Option<u128>,
+    pub highest_timestamp: u128,
+    pub bump: u8,
+}
+
+impl BidReduction {
+    /// `None` until a bid is reduced
+    pub fn standings(&self) -> Option<Standings<Euint128>> {
+        (self.reduced > 0).then(|| Standings {
+            highest: Euint128(self.highest),
+            second_highest: self.second_highest.map(Euint128),
+            highest_timestamp: Euint128(self.highest_timestamp),
+        })
+    }
+
+    pub fn set_standings(&mut self, standings: &Standings<Euint128>) {
+        self.highest = standings.highest.0;
+        self.second_highest = standings.second_highest.map(|handle| handle.0);
+        self.highest_timestamp = standings.highest_timestamp.0;
+    }
+
+    /// Sequence of the next bid to reduce
+    pub fn end(&self) -> u32 {
+        self.start.saturating_add(self.reduced)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
 pub enum AuctionType {
     Normal,  // First-price: winner pays their own bid
     Vickrey, // Second-price: winner pays second-highest bid
 }
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+impl From<AuctionType> for Pricing {
+    fn from(auction_type: AuctionType) -> Self {
+        match auction_type {
+            AuctionType::Normal => Pricing::FirstPrice,
+            AuctionType::Vickrey => Pricing::SecondPrice,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
 pub enum AuctionStatus {
     Open,
     Closed,
     Settled,
     Cancelled,
 }
+
+impl From<&AuctionStatus> for Status {
+    fn from(status: &AuctionStatus) -> Self {
+        match status {
+            AuctionStatus::Open => Status::Open,
+            AuctionStatus::Closed => Status::Closed,
+            AuctionStatus::Settled => Status::Settled,
+            AuctionStatus::Cancelled => Status::Cancelled,
+        }
+    }
+}
+
+impl From<Status> for AuctionStatus {
+    fn from(status: Status) -> Self {
+        match status {
+            Status::Open => AuctionStatus::Open,
+            Status::Closed => AuctionStatus::Closed,
+            Status::Settled => AuctionStatus::Settled,
+            Status::Cancelled => AuctionStatus::Cancelled,
+        }
+    }
+}
+
+/// Phase derived from the status and the clock
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum AuctionPhase {
+    Upcoming,
+    Live,
+    Ended,
+    Settled,
+    Cancelled,
+}
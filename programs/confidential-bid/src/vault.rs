@@ -0,0 +1,85 @@
+//! Transfers out of the auction's escrows, signed by the auction PDA
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{transfer_checked, TransferChecked};
+use inco_lightning::Euint128;
+
+use crate::{
+    constants::{AUCTION_SEED, HANDLE_INPUT_TYPE},
+    state::AuctionState,
+};
+
+/// Moves an encrypted amount out of the Inco bid vault
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_bid<'info>(
+    auction: &Account<'info, AuctionState>,
+    bid_mint: &AccountInfo<'info>,
+    bid_vault: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+    inco_token_program: &AccountInfo<'info>,
+    inco_lightning_program: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    amount: Euint128,
+) -> Result<()> {
+    let organizer = auction.organizer;
+    let auction_id = auction.auction_id.to_le_bytes();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        AUCTION_SEED,
+        organizer.as_ref(),
+        auction_id.as_ref(),
+        &[auction.auction_bump],
+    ]];
+
+    let decimals =
+        inco_token::IncoMint::try_deserialize(&mut &bid_mint.try_borrow_data()?[..])?.decimals;
+
+    let cpi_transfer = CpiContext::new_with_signer(
+        inco_token_program.clone(),
+        inco_token::cpi::accounts::TransferChecked {
+            source: bid_vault.clone(),
+            mint: bid_mint.clone(),
+            destination: destination.clone(),
+            authority: auction.to_account_info(),
+            inco_lightning_program: inco_lightning_program.clone(),
+            system_program: system_program.clone(),
+        },
+        signer_seeds,
+    );
+    inco_token::cpi::transfer_checked(
+        cpi_transfer,
+        amount.0.to_le_bytes().to_vec(),
+        HANDLE_INPUT_TYPE,
+        decimals,
+    )
+}
+
+/// Moves `amount` prize tokens out of the prize vault
+pub fn transfer_prize<'info>(
+    auction: &Account<'info, AuctionState>,
+    mint: &AccountInfo<'info>,
+    vault: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    amount: u64,
+    decimals: u8,
+) -> Result<()> {
+    let organizer = auction.organizer;
+    let auction_id = auction.auction_id.to_le_bytes();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        AUCTION_SEED,
+        organizer.as_ref(),
+        auction_id.as_ref(),
+        &[auction.auction_bump],
+    ]];
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        token_program.clone(),
+        TransferChecked {
+            from: vault.clone(),
+            mint: mint.clone(),
+            to: destination.clone(),
+            authority: auction.to_account_info(),
+        },
+        signer_seeds,
+    );
+    transfer_checked(cpi_ctx, amount, decimals)
+}
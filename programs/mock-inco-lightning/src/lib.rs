@@ -0,0 +1,310 @@
+//! Plaintext stand-in for the Inco Lightning program, for local tests only.
+//!
+//! It is declared at the live program id and mirrors the instruction names,
+//! arguments and accounts the `inco-lightning` CPI client uses, so
+//! confidential-bid runs unmodified against it. Nothing is encrypted, but
+//! handles are opaque: [`seal`] masks the value behind a tag derived from the
+//! operation and operands that issued it, and [`reveal`] is the only way back.
+//! The operations only receive the signer, so there is no account to keep
+//! values in and each handle carries its own, checked on every use. Handle 0
+//! and handles the mock never issued are rejected, so an unset field cannot
+//! pass for an encrypted zero and two handles holding the same value stay
+//! distinct whenever they were computed differently. Booleans hold 1 and 0.
+//!
+//! Ciphertexts passed to `new_euint128` are the little-endian bytes of the
+//! value, at most 16 of them, whatever the `input_type`.
+//!
+//! The `zero-handle` feature reads handle 0 as an encrypted zero instead, for
+//! measuring the first deployed confidential-bid, which relies on it.
+use anchor_lang::{prelude::*, solana_program::hash::hashv};
+
+declare_id!("5sjEbPiqgZrYwR31ahR6Uk9wf5awoX61YGg7jExQSwaj");
+
+/// Width of the values a handle holds, arithmetic wraps at it
+pub const VALUE_BITS: u32 = 80;
+const VALUE_MASK: u128 = (1 << VALUE_BITS) - 1;
+/// Bits of the tag checking a handle was issued for its value
+const CHECK_BITS: u32 = 16;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct Euint128(pub u128);
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct Ebool(pub u128);
+
+fn hash_u128(parts: &[&[u8]]) -> u128 {
+    let hash = hashv(parts).to_bytes();
+    u128::from_le_bytes(hash[..16].try_into().unwrap())
+}
+
+/// Mask hiding the value of handles tagged `id`
+fn pad(id: u32) -> u128 {
+    hash_u128(&[b"mock-inco:pad", &id.to_le_bytes()]) & VALUE_MASK
+}
+
+/// Check binding `value` to the tag `id`
+fn check(id: u32, value: u128) -> u128 {
+    hash_u128(&[b"mock-inco:check", &id.to_le_bytes(), &value.to_le_bytes()])
+        & ((1 << CHECK_BITS) - 1)
+}
+
+/// New handle holding `value` modulo 2^[`VALUE_BITS`], its tag derived from
+/// `lineage`: the operation and operands issuing it
+pub fn seal(value: u128, lineage: &[&[u8]]) -> u128 {
+    let value = value & VALUE_MASK;
+    let mut id = hash_u128(lineage) as u32;
+    loop {
+        let handle = (u128::from(id) << (VALUE_BITS + CHECK_BITS))
+            | (check(id, value) << VALUE_BITS)
+            | (value ^ pad(id));
+        if handle != 0 {
+            return handle;
+        }
+        id = id.wrapping_add(1);
+    }
+}
+
+/// Value held by `handle`, failing for 0 and handles [`seal`] never issued
+pub fn reveal(handle: u128) -> Result<u128> {
+    if cfg!(feature = "zero-handle") && handle == 0 {
+        return Ok(0);
+    }
+    require!(handle != 0, MockIncoError::UnknownHandle);
+    let id = (handle >> (VALUE_BITS + CHECK_BITS)) as u32;
+    let value = (handle & VALUE_MASK) ^ pad(id);
+    require!(
+        (handle >> VALUE_BITS) & ((1 << CHECK_BITS) - 1) == check(id, value),
+        MockIncoError::UnknownHandle
+    );
+    Ok(value)
+}
+
+/// Decodes a mock ciphertext, the little-endian bytes of the plaintext
+pub fn decode_ciphertext(ciphertext: &[u8]) -> Result<u128> {
+    require!(ciphertext.len() <= 16, MockIncoError::InvalidCiphertext);
+    let mut bytes = [0u8; 16];
+    bytes[..ciphertext.len()].copy_from_slice(ciphertext);
+    Ok(u128::from_le_bytes(bytes))
+}
+
+/// Result of `op` over `operands`, sealed with them as its lineage
+fn issue(op: &str, operands: &[u128], value: u128) -> u128 {
+    let operands: Vec<[u8; 16]> = operands.iter().map(|handle| handle.to_le_bytes()).collect();
+    let mut lineage: Vec<&[u8]> = vec![op.as_bytes()];
+    lineage.extend(operands.iter().map(|bytes| bytes.as_slice()));
+    seal(value, &lineage)
+}
+
+/// Values of two operand handles
+fn reveal_pair(lhs: u128, rhs: u128) -> Result<(u128, u128)> {
+    Ok((reveal(lhs)?, reveal(rhs)?))
+}
+
+fn boolean(op: &str, lhs: u128, rhs: u128, value: bool) -> Ebool {
+    Ebool(issue(op, &[lhs, rhs], value as u128))
+}
+
+#[program]
+pub mod mock_inco_lightning {
+    use super::*;
+
+    /// Inputs are sealed per signer and slot, so equal ciphertexts from
+    /// different bidders get different handles
+    pub fn new_euint128(
+        ctx: Context<Operation>,
+        ciphertext: Vec<u8>,
+        _input_type: u8,
+    ) -> Result<Euint128> {
+        let value = decode_ciphertext(&ciphertext)?;
+        let slot = Clock::get()?.slot.to_le_bytes();
+        Ok(Euint128(seal(
+            value,
+            &[
+                b"new_euint128",
+                ctx.accounts.signer.key.as_ref(),
+                &slot,
+                &ciphertext,
+            ],
+        )))
+    }
+
+    pub fn as_euint128(_ctx: Context<Operation>, value: u128) -> Result<Euint128> {
+        Ok(Euint128(seal(
+            value,
+            &[b"as_euint128", &value.to_le_bytes()],
+        )))
+    }
+
+    pub fn e_gt(
+        _ctx: Context<Operation>,
+        lhs: Euint128,
+        rhs: Euint128,
+        _scalar_byte: u8,
+    ) -> Result<Ebool> {
+        let (l, r) = reveal_pair(lhs.0, rhs.0)?;
+        Ok(boolean("e_gt", lhs.0, rhs.0, l > r))
+    }
+
+    pub fn e_ge(
+        _ctx: Context<Operation>,
+        lhs: Euint128,
+        rhs: Euint128,
+        _scalar_byte: u8,
+    ) -> Result<Ebool> {
+        let (l, r) = reveal_pair(lhs.0, rhs.0)?;
+        Ok(boolean("e_ge", lhs.0, rhs.0, l >= r))
+    }
+
+    pub fn e_eq(
+        _ctx: Context<Operation>,
+        lhs: Euint128,
+        rhs: Euint128,
+        _scalar_byte: u8,
+    ) -> Result<Ebool> {
+        let (l, r) = reveal_pair(lhs.0, rhs.0)?;
+        Ok(boolean("e_eq", lhs.0, rhs.0, l == r))
+    }
+
+    pub fn e_and(
+        _ctx: Context<Operation>,
+        lhs: Euint128,
+        rhs: Euint128,
+        _scalar_byte: u8,
+    ) -> Result<Euint128> {
+        let (l, r) = reveal_pair(lhs.0, rhs.0)?;
+        Ok(Euint128(issue("e_and", &[lhs.0, rhs.0], l & r)))
+    }
+
+    /// Issues a new handle rather than passing the chosen one through
+    pub fn e_select(
+        _ctx: Context<Operation>,
+        control: Ebool,
+        if_true: Euint128,
+        if_false: Euint128,
+        _scalar_byte: u8,
+    ) -> Result<Euint128> {
+        let chosen = if reveal(control.0)? != 0 {
+            reveal(if_true.0)?
+        } else {
+            reveal(if_false.0)?
+        };
+        Ok(Euint128(issue(
+            "e_select",
+            &[control.0, if_true.0, if_false.0],
+            chosen,
+        )))
+    }
+
+    /// Arithmetic wraps like the encrypted integers it stands in for, at [`VALUE_BITS`]
+    pub fn e_add(
+        _ctx: Context<Operation>,
+        lhs: Euint128,
+        rhs: Euint128,
+        _scalar_byte: u8,
+    ) -> Result<Euint128> {
+        let (l, r) = reveal_pair(lhs.0, rhs.0)?;
+        Ok(Euint128(issue("e_add", &[lhs.0, rhs.0], l.wrapping_add(r))))
+    }
+
+    pub fn e_sub(
+        _ctx: Context<Operation>,
+        lhs: Euint128,
+        rhs: Euint128,
+        _scalar_byte: u8,
+    ) -> Result<Euint128> {
+        let (l, r) = reveal_pair(lhs.0, rhs.0)?;
+        Ok(Euint128(issue("e_sub", &[lhs.0, rhs.0], l.wrapping_sub(r))))
+    }
+
+    pub fn e_mul(
+        _ctx: Context<Operation>,
+        lhs: Euint128,
+        rhs: Euint128,
+        _scalar_byte: u8,
+    ) -> Result<Euint128> {
+        let (l, r) = reveal_pair(lhs.0, rhs.0)?;
+        Ok(Euint128(issue("e_mul", &[lhs.0, rhs.0], l.wrapping_mul(r))))
+    }
+
+    /// Division by zero yields zero instead of failing, as it cannot be detected on ciphertexts
+    pub fn e_div(
+        _ctx: Context<Operation>,
+        lhs: Euint128,
+        rhs: Euint128,
+        _scalar_byte: u8,
+    ) -> Result<Euint128> {
+        let (l, r) = reveal_pair(lhs.0, rhs.0)?;
+        Ok(Euint128(issue(
+            "e_div",
+            &[lhs.0, rhs.0],
+            l.checked_div(r).unwrap_or(0),
+        )))
+    }
+
+    /// Records whether `allowed_address` may decrypt `handle`, which must be one the mocks issued
+    pub fn allow(
+        ctx: Context<Allow>,
+        handle: u128,
+        value: bool,
+        allowed_address: Pubkey,
+    ) -> Result<()> {
+        reveal(handle)?;
+        require_keys_eq!(
+            ctx.accounts.allowed_address.key(),
+            allowed_address,
+            MockIncoError::AllowedAddressMismatch
+        );
+        ctx.accounts.allowance_account.set_inner(Allowance {
+            handle,
+            allowed_address,
+            value,
+        });
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Operation<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(handle: u128)]
+pub struct Allow<'info> {
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + Allowance::INIT_SPACE,
+        seeds = [handle.to_le_bytes().as_ref(), allowed_address.key().as_ref()],
+        bump
+    )]
+    pub allowance_account: Account<'info, Allowance>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// CHECK: address gaining or losing decryption rights
+    pub allowed_address: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Decryption right of `allowed_address` over `handle`, read by local tests
+#[account]
+#[derive(InitSpace)]
+pub struct Allowance {
+    pub handle: u128,
+    pub allowed_address: Pubkey,
+    pub value: bool,
+}
+
+#[error_code]
+pub enum MockIncoError {
+    #[msg("Mock ciphertexts are at most 16 little-endian bytes")]
+    InvalidCiphertext,
+    #[msg("Allowed address does not match the instruction argument")]
+    AllowedAddressMismatch,
+    #[msg("Handle was never issued by the mock")]
+    UnknownHandle,
+}
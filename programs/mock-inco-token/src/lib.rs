@@ -0,0 +1,275 @@
+//! Plaintext stand-in for the Inco Token program, for local tests only.
+//!
+//! Declared at the live program id with the live account layouts, so
+//! confidential-bid deserializes `IncoMint` and `IncoAccount` from it
+//! unmodified. Balances are handles sealed the way the mock Inco Lightning
+//! program seals them, every update issuing a new one. Only the instructions
+//! confidential-bid and its tests use are implemented.
+use anchor_lang::prelude::*;
+use mock_inco_lightning::{reveal, seal};
+
+declare_id!("EZvVX4SvMZDSDi9zSoXWriHPzZjFjDGosJuGjUZyzfPA");
+
+/// `input_type` of a ciphertext that is the little-endian bytes of an existing handle
+pub const HANDLE_INPUT_TYPE: u8 = 1;
+
+/// Amount of a ciphertext: the handle it names for [`HANDLE_INPUT_TYPE`],
+/// the little-endian bytes of the plaintext otherwise
+fn decode_amount(ciphertext: &[u8], input_type: u8) -> Result<u128> {
+    require!(ciphertext.len() <= 16, MockTokenError::InvalidCiphertext);
+    let mut bytes = [0u8; 16];
+    bytes[..ciphertext.len()].copy_from_slice(ciphertext);
+    let value = u128::from_le_bytes(bytes);
+    if input_type == HANDLE_INPUT_TYPE {
+        reveal(value)
+    } else {
+        Ok(value)
+    }
+}
+
+/// New balance handle of `account`, replacing `previous`
+fn balance(account: &Pubkey, previous: Euint128, amount: u128) -> Euint128 {
+    Euint128(seal(
+        amount,
+        &[b"balance", account.as_ref(), &previous.0.to_le_bytes()],
+    ))
+}
+
+#[program]
+pub mod mock_inco_token {
+    use super::*;
+
+    pub fn initialize_mint(
+        ctx: Context<InitializeMint>,
+        decimals: u8,
+        mint_authority: Pubkey,
+        freeze_authority: Option<Pubkey>,
+    ) -> Result<()> {
+        let supply = balance(&ctx.accounts.mint.key(), Euint128(0), 0);
+        ctx.accounts.mint.set_inner(IncoMint {
+            mint_authority: COption::Some(mint_authority),
+            supply,
+            decimals,
+            is_initialized: true,
+            freeze_authority: freeze_authority.map_or(COption::None, COption::Some),
+        });
+        Ok(())
+    }
+
+    pub fn create_idempotent(ctx: Context<CreateIdempotent>) -> Result<()> {
+        let account = &mut ctx.accounts.associated_token;
+        if account.state == AccountState::Uninitialized {
+            let amount = balance(&account.key(), Euint128(0), 0);
+            account.set_inner(IncoAccount {
+                mint: ctx.accounts.mint.key(),
+                owner: ctx.accounts.wallet.key(),
+                amount,
+                delegate: COption::None,
+                state: AccountState::Initialized,
+                is_native: COption::None,
+                delegated_amount: Euint128(0),
+                close_authority: COption::None,
+            });
+        }
+        Ok(())
+    }
+
+    pub fn mint_to(ctx: Context<MintTo>, ciphertext: Vec<u8>, input_type: u8) -> Result<()> {
+        let amount = decode_amount(&ciphertext, input_type)?;
+        let mint = &mut ctx.accounts.mint;
+        require!(
+            mint.mint_authority == COption::Some(ctx.accounts.mint_authority.key()),
+            MockTokenError::OwnerMismatch
+        );
+        require_keys_eq!(
+            ctx.accounts.account.mint,
+            mint.key(),
+            MockTokenError::MintMismatch
+        );
+
+        mint.supply = balance(
+            &mint.key(),
+            mint.supply,
+            reveal(mint.supply.0)?.wrapping_add(amount),
+        );
+        let account = &mut ctx.accounts.account;
+        account.amount = balance(
+            &account.key(),
+            account.amount,
+            reveal(account.amount.0)?.wrapping_add(amount),
+        );
+        Ok(())
+    }
+
+    /// Moves nothing when the balance is short, as the encrypted transfer would
+    pub fn transfer_checked(
+        ctx: Context<TransferChecked>,
+        ciphertext: Vec<u8>,
+        input_type: u8,
+        decimals: u8,
+    ) -> Result<()> {
+        require!(
+            decimals == ctx.accounts.mint.decimals,
+            MockTokenError::MintDecimalsMismatch
+        );
+        let source = &mut ctx.accounts.source;
+        let destination = &mut ctx.accounts.destination;
+        require_keys_eq!(
+            source.owner,
+            ctx.accounts.authority.key(),
+            MockTokenError::OwnerMismatch
+        );
+        require_keys_eq!(
+            source.mint,
+            ctx.accounts.mint.key(),
+            MockTokenError::MintMismatch
+        );
+        require_keys_eq!(
+            destination.mint,
+            ctx.accounts.mint.key(),
+            MockTokenError::MintMismatch
+        );
+
+        let requested = decode_amount(&ciphertext, input_type)?;
+        let source_amount = reveal(source.amount.0)?;
+        let amount = if source_amount >= requested {
+            requested
+        } else {
+            0
+        };
+        source.amount = balance(&source.key(), source.amount, source_amount - amount);
+        let destination_amount = reveal(destination.amount.0)?.wrapping_add(amount);
+        destination.amount = balance(&destination.key(), destination.amount, destination_amount);
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct Euint128(pub u128);
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum COption<T> {
+    None,
+    Some(T),
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum AccountState {
+    Uninitialized,
+    Initialized,
+    Frozen,
+}
+
+#[account]
+pub struct IncoMint {
+    pub mint_authority: COption<Pubkey>,
+    pub supply: Euint128,
+    pub decimals: u8,
+    pub is_initialized: bool,
+    pub freeze_authority: COption<Pubkey>,
+}
+impl IncoMint {
+    pub const LEN: usize = 33 + 16 + 1 + 1 + 33;
+}
+
+#[account]
+pub struct IncoAccount {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub amount: Euint128,
+    pub delegate: COption<Pubkey>,
+    pub state: AccountState,
+    pub is_native: COption<u64>,
+    pub delegated_amount: Euint128,
+    pub close_authority: COption<Pubkey>,
+}
+impl IncoAccount {
+    pub const LEN: usize = 32 + 32 + 16 + 33 + 1 + 9 + 16 + 33;
+}
+
+#[derive(Accounts)]
+pub struct InitializeMint<'info> {
+    #[account(init, payer = payer, space = 8 + IncoMint::LEN)]
+    pub mint: Account<'info, IncoMint>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: unused, kept for account order parity with the live program
+    pub inco_lightning_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CreateIdempotent<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + IncoAccount::LEN,
+        seeds = [wallet.key().as_ref(), crate::ID.as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub associated_token: Account<'info, IncoAccount>,
+
+    /// CHECK: owner of the new token account
+    pub wallet: UncheckedAccount<'info>,
+
+    pub mint: Account<'info, IncoMint>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: unused, kept for account order parity with the live program
+    pub inco_lightning_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MintTo<'info> {
+    #[account(mut)]
+    pub mint: Account<'info, IncoMint>,
+
+    #[account(mut)]
+    pub account: Account<'info, IncoAccount>,
+
+    #[account(mut)]
+    pub mint_authority: Signer<'info>,
+
+    /// CHECK: unused, kept for account order parity with the live program
+    pub inco_lightning_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct TransferChecked<'info> {
+    #[account(mut)]
+    pub source: Account<'info, IncoAccount>,
+
+    pub mint: Account<'info, IncoMint>,
+
+    #[account(mut)]
+    pub destination: Account<'info, IncoAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: unused, kept for account order parity with the live program
+    pub inco_lightning_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[error_code]
+pub enum MockTokenError {
+    #[msg("Mock ciphertexts are at most 16 little-endian bytes")]
+    InvalidCiphertext,
+    #[msg("Authority does not own the account")]
+    OwnerMismatch,
+    #[msg("Account mint does not match")]
+    MintMismatch,
+    #[msg("Decimals do not match the mint")]
+    MintDecimalsMismatch,
+}
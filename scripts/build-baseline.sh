@@ -0,0 +1,26 @@
+#!/usr/bin/env bash
+# Builds what the compute unit comparison in
+# integration-tests/tests/compute_units.rs loads on top of `anchor build`:
+# confidential-bid as first deployed, and the Inco Lightning mock built with
+# `zero-handle`, which that program needs.
+set -euo pipefail
+
+BASELINE_COMMIT=e8f6cad
+root=$(git rev-parse --show-toplevel)
+worktree=$root/target/baseline
+deploy=$root/target/deploy
+
+rm -rf "$worktree"
+git -C "$root" worktree prune
+git -C "$root" worktree add --detach "$worktree" "$BASELINE_COMMIT"
+trap 'git -C "$root" worktree remove --force "$worktree"' EXIT
+
+mkdir -p "$deploy"
+(cd "$worktree" && anchor build)
+cp "$worktree/target/deploy/confidential_bid.so" "$deploy/confidential_bid_baseline.so"
+
+cargo build-sbf \
+    --manifest-path "$root/programs/mock-inco-lightning/Cargo.toml" \
+    --features zero-handle \
+    --sbf-out-dir "$worktree/mock"
+cp "$worktree/mock/mock_inco_lightning.so" "$deploy/mock_inco_lightning_zero_handle.so"
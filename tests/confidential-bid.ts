@@ -20,15 +20,19 @@ import {
   extractHandleFromAnchor,
   formatBalance,
   getAllowancePda,
+  getBidPda,
   getIncoAta,
+  getOrganizerProfilePda,
+  getPrizeMetadataPda,
 } from "./helpers/pda";
 import { encryptValue, hexToBuffer } from "@inco/solana-sdk";
 import {
-  simulateAndGetHandle,
-  simulateAuctionHandles,
-  simulateCheckWinnerHandles,
+  simulate,
+  simulateAccount,
+  simulateReturnData,
 } from "./helpers/simulate";
 import { decryptHandleWithSigner } from "./helpers/decrypt";
+import { getCpiEvents } from "./helpers/events";
 import { expect } from "chai";
 import fs from "fs";
 const SYSTEM_PROGRAM_ID = SystemProgram.programId;
@@ -61,6 +65,7 @@ describe("confidential-bid", () => {
   const bidder1 = Keypair.generate(); // Bidder
   const bidder2 = Keypair.generate();
   const bidder3 = Keypair.generate();
+  const auditor = Keypair.generate();
 
   let auctionPda: anchor.web3.PublicKey;
   let vault: anchor.web3.PublicKey;
@@ -68,10 +73,22 @@ describe("confidential-bid", () => {
   const program = anchor.workspace.confidentialBid as Program<ConfidentialBid>;
   const incoTokenProgram = new anchor.Program(incoIdl as anchor.Idl, provider);
 
-  const auctionId = new anchor.BN(Date.now());
+  let auctionId: anchor.BN;
   let startTime: anchor.BN;
   let endTime: anchor.BN;
 
+  // Next auction id the program will allocate from the organizer profile counter
+  async function nextAuctionId(): Promise<anchor.BN> {
+    const [organizerProfile] = getOrganizerProfilePda(
+      program.programId,
+      organizer.publicKey
+    );
+    const profile = await program.account.organizerProfile.fetchNullable(
+      organizerProfile
+    );
+    return profile ? profile.auctionCount : new anchor.BN(0);
+  }
+
   // Creates a confidential mint using Inco token program
   async function createConfidentialMint(payer: Keypair, mintKeypair: Keypair) {
     // Build the instruction using the IDL
@@ -145,17 +162,16 @@ describe("confidential-bid", () => {
 
     await new Promise((resolve) => setTimeout(resolve, 3000));
 
-    const newHandle = await simulateAndGetHandle(
+    const simulated = await simulateAccount(
       provider.connection,
       txForSim,
-      ata,
-      payer
+      payer,
+      incoTokenProgram,
+      "incoAccount",
+      ata
     );
-
-    if (!newHandle) {
-      throw new Error("Failed to extract handle from simulation");
-    }
-    const [allowancePda] = getAllowancePda(newHandle!, owner.publicKey);
+    const newHandle = extractHandleFromAnchor(simulated.amount);
+    const [allowancePda] = getAllowancePda(newHandle, owner.publicKey);
 
     const tx = await incoTokenProgram.methods
       .mintTo(hexToBuffer(encryptedHex), INPUT_TYPE)
@@ -220,6 +236,7 @@ describe("confidential-bid", () => {
     );
 
     // Derive PDAs
+    auctionId = await nextAuctionId();
     auctionPda = anchor.web3.PublicKey.findProgramAddressSync(
       [
         Buffer.from("auction"),
@@ -263,12 +280,18 @@ describe("confidential-bid", () => {
 
     const tx = await program.methods
       .createAuction(
-        auctionId,
         startTime,
         endTime,
         reservePrice,
         { normal: {} }, // First-price auction type
-        tokenAmount
+        tokenAmount,
+        [], // No co-owners, organizer receives all proceeds
+        null, // No auditor
+        false, // Bid count stays public
+        false, // Bids keyed by bidder
+        false, // Standings tracked on every bid
+        false, // Result stays confidential
+        false // Bids stay confidential after close
       )
       .accounts({
         organizer: organizer.publicKey,
@@ -330,7 +353,7 @@ describe("confidential-bid", () => {
     const tokenAmount = new anchor.BN(1);
 
     // Generate new auction ID for Vickrey auction
-    const vickreyAuctionId = new anchor.BN(Math.floor(Math.random() * 1000));
+    const vickreyAuctionId = await nextAuctionId();
 
     // Derive Vickrey auction PDA
     const vickreyAuctionPda = anchor.web3.PublicKey.findProgramAddressSync(
@@ -355,12 +378,18 @@ describe("confidential-bid", () => {
     );
     const tx = await program.methods
       .createAuction(
-        vickreyAuctionId,
         startTime,
         endTime,
         reservePrice,
         { vickrey: {} }, // Second-price auction type
-        tokenAmount
+        tokenAmount,
+        [],
+        auditor.publicKey, // Independent auditor can decrypt every bid
+        true, // Hide participation count
+        true, // Bids keyed by commitment, unlinkable to bidders
+        false, // Standings tracked on every bid
+        false, // Result stays confidential
+        false // Bids stay confidential after close
       )
       .accounts({
         organizer: organizer.publicKey,
@@ -419,8 +448,10 @@ describe("confidential-bid", () => {
       reservePrice.toNumber()
     );
 
-    // Verify bid count is 0
+    // Verify bid count is 0 and hidden from observers
     expect(vickreyAuctionStateAccount.bidCount).to.equal(0);
+    expect(vickreyAuctionStateAccount.hideBidCount).to.equal(true);
+    expect(vickreyAuctionStateAccount.privateBids).to.equal(true);
 
     // Verify highest bid is 0
     expect(vickreyAuctionStateAccount.highestBid.toNumber()).to.equal(0);
@@ -433,6 +464,11 @@ describe("confidential-bid", () => {
       open: {},
     });
 
+    // Verify the auditor was recorded
+    expect(vickreyAuctionStateAccount.auditor.toBase58()).to.equal(
+      auditor.publicKey.toBase58()
+    );
+
     // Verify auction type is Vickrey (second-price)
     expect(vickreyAuctionStateAccount.auctionType).to.deep.include({
       vickrey: {},
@@ -451,6 +487,249 @@ describe("confidential-bid", () => {
     console.log("\nVickrey auction created successfully!\n");
   });
 
+  it("should refuse to move end time earlier", async () => {
+    try {
+      await program.methods
+        .extendAuction(endTime.sub(new anchor.BN(1)))
+        .accounts({
+          organizer: organizer.publicKey,
+          auction: auctionPda,
+        })
+        .rpc();
+
+      expect.fail("extendAuction should only push the deadline later");
+    } catch (err: any) {
+      expect(err?.error?.errorCode?.code).to.equal("InvalidEndTime");
+    }
+  });
+
+  it("should refuse to migrate an auction already at the current version", async () => {
+    const auctionState = await program.account.auctionState.fetch(auctionPda);
+    expect(auctionState.version).to.equal(2);
+
+    try {
+      await program.methods
+        .migrateAuction()
+        .accounts({
+          payer: organizer.publicKey,
+          auction: auctionPda,
+        })
+        .rpc();
+
+      expect.fail("migrateAuction should reject a current layout");
+    } catch (err: any) {
+      expect(err?.error?.errorCode?.code).to.equal("AccountAlreadyMigrated");
+    }
+  });
+
+  it("should reject payout shares above 100%", async () => {
+    const splitAuctionId = await nextAuctionId();
+    const splitAuctionPda = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("auction"),
+        organizer.publicKey.toBuffer(),
+        splitAuctionId.toBuffer("le", 8),
+      ],
+      program.programId
+    )[0];
+
+    try {
+      await program.methods
+        .createAuction(
+          startTime,
+          endTime,
+          new anchor.BN(1),
+          { normal: {} },
+          new anchor.BN(1),
+          [
+            { recipient: bidder1.publicKey, shareBps: 6000 },
+            { recipient: bidder2.publicKey, shareBps: 5000 },
+          ],
+          null,
+          false,
+          false,
+          false,
+          false,
+          false
+        )
+        .accounts({
+          organizer: organizer.publicKey,
+          mint: auctionMint,
+          bidTokenMint: bidTokenMint.publicKey,
+          bidVault: getIncoAta(
+            incoTokenProgram,
+            splitAuctionPda,
+            bidTokenMint.publicKey
+          ),
+          vault: getAssociatedTokenAddressSync(
+            auctionMint,
+            splitAuctionPda,
+            true
+          ),
+          auction: splitAuctionPda,
+          organizerTokenAccount: organizerAta,
+          systemProgram: SYSTEM_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          incoTokenProgram: incoTokenProgram.programId,
+          incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
+        })
+        .rpc();
+
+      expect.fail("createAuction should fail when shares exceed 10000 bps");
+    } catch (err: any) {
+      expect(err?.error?.errorCode?.code).to.equal("InvalidPayoutShare");
+    }
+  });
+
+  it("should reject a spoofed Inco token program on create", async () => {
+    const spoofAuctionId = await nextAuctionId();
+    const spoofAuctionPda = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("auction"),
+        organizer.publicKey.toBuffer(),
+        spoofAuctionId.toBuffer("le", 8),
+      ],
+      program.programId
+    )[0];
+
+    try {
+      await program.methods
+        .createAuction(
+          startTime,
+          endTime,
+          new anchor.BN(1),
+          { normal: {} },
+          new anchor.BN(1),
+          [],
+          null,
+          false,
+          false,
+          false,
+          false,
+          false
+        )
+        .accounts({
+          organizer: organizer.publicKey,
+          mint: auctionMint,
+          bidTokenMint: bidTokenMint.publicKey,
+          bidVault: getIncoAta(
+            incoTokenProgram,
+            spoofAuctionPda,
+            bidTokenMint.publicKey
+          ),
+          vault: getAssociatedTokenAddressSync(
+            auctionMint,
+            spoofAuctionPda,
+            true
+          ),
+          auction: spoofAuctionPda,
+          organizerTokenAccount: organizerAta,
+          systemProgram: SYSTEM_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          incoTokenProgram: SYSTEM_PROGRAM_ID, // not the Inco Token program
+          incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
+        })
+        .rpc();
+
+      expect.fail("createAuction should reject a fake Inco token program");
+    } catch (err: any) {
+      expect(err?.error?.errorCode?.code).to.equal("ConstraintAddress");
+    }
+  });
+
+  it("should reject a bid vault that is not the auction's Inco ATA", async () => {
+    const spoofAuctionId = await nextAuctionId();
+    const spoofAuctionPda = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("auction"),
+        organizer.publicKey.toBuffer(),
+        spoofAuctionId.toBuffer("le", 8),
+      ],
+      program.programId
+    )[0];
+
+    try {
+      await program.methods
+        .createAuction(
+          startTime,
+          endTime,
+          new anchor.BN(1),
+          { normal: {} },
+          new anchor.BN(1),
+          [],
+          null,
+          false,
+          false,
+          false,
+          false,
+          false
+        )
+        .accounts({
+          organizer: organizer.publicKey,
+          mint: auctionMint,
+          bidTokenMint: bidTokenMint.publicKey,
+          bidVault: bidderAta, // bidder-owned account instead of the auction vault
+          vault: getAssociatedTokenAddressSync(
+            auctionMint,
+            spoofAuctionPda,
+            true
+          ),
+          auction: spoofAuctionPda,
+          organizerTokenAccount: organizerAta,
+          systemProgram: SYSTEM_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          incoTokenProgram: incoTokenProgram.programId,
+          incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
+        })
+        .rpc();
+
+      expect.fail("createAuction should reject a foreign bid vault");
+    } catch (err: any) {
+      expect(err?.error?.errorCode?.code).to.equal("InvalidBidVault");
+    }
+  });
+
+  it("should reject a spoofed Inco token program on bid", async () => {
+    const encryptedBid = await encryptValue(BigInt(1) * TOKEN_MULTIPLIER);
+    const [bidPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("bid"), auctionPda.toBuffer(), bidder1.publicKey.toBuffer()],
+      program.programId
+    );
+
+    try {
+      await program.methods
+        .placeBid(hexToBuffer(encryptedBid), INPUT_TYPE, null, false)
+        .accounts({
+          payer: bidder1.publicKey,
+          bidder: bidder1.publicKey,
+          organizer: organizer.publicKey,
+          bidderTokenAta: bidderAta,
+          bidMint: bidTokenMint.publicKey,
+          bidVault: getIncoAta(
+            incoTokenProgram,
+            auctionPda,
+            bidTokenMint.publicKey
+          ),
+          bid: bidPda,
+          auction: auctionPda,
+          balanceAllowance: null,
+          bidAllowance: null,
+          systemProgram: SYSTEM_PROGRAM_ID,
+          incoTokenProgram: SYSTEM_PROGRAM_ID, // not the Inco Token program
+          incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
+        })
+        .signers([bidder1])
+        .rpc();
+
+      expect.fail("placeBid should reject a fake Inco token program");
+    } catch (err: any) {
+      expect(err?.error?.errorCode?.code).to.equal("ConstraintAddress");
+    }
+  });
+
   it("should place bids from all bidders", async () => {
     bidVault = getIncoAta(incoTokenProgram, auctionPda, bidTokenMint.publicKey);
 
@@ -475,8 +754,9 @@ describe("confidential-bid", () => {
         program.programId
       );
       const txForSim = await program.methods
-        .placeBid(bidBuffer, 0)
+        .placeBid(bidBuffer, 0, null, false) // Simulation only learns the handles
         .accounts({
+          payer: bid.kp.publicKey,
           bidder: bid.kp.publicKey,
           organizer: organizer.publicKey,
           bidderTokenAta: bid.ata,
@@ -484,51 +764,42 @@ describe("confidential-bid", () => {
           bidVault,
           bid: bidPda,
           auction: auctionPda,
+          balanceAllowance: null,
+          bidAllowance: null,
           systemProgram: SYSTEM_PROGRAM_ID,
           incoTokenProgram: incoTokenProgram.programId,
           incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
         })
         .transaction();
 
-      const bidderAtahandle = await simulateAndGetHandle(
-        provider.connection,
-        txForSim,
+      // The balance handle stays out of the public return data, read it
+      // from the simulated token account instead
+      const simulated = await simulate(provider.connection, txForSim, bid.kp, [
         bid.ata,
-        bid.kp
+      ]);
+      const result = program.coder.types.decode(
+        "placeBidResult",
+        simulated.returnData!.data
       );
-
-      const bidVaulthandle = await simulateAndGetHandle(
-        provider.connection,
-        txForSim,
-        bidVault,
-        organizer.payer
+      const balance = incoTokenProgram.coder.accounts.decode(
+        "incoAccount",
+        simulated.accounts[0]!
       );
 
       const [allowancePda] = getAllowancePda(
-        bidderAtahandle!,
+        extractHandleFromAnchor(balance.amount),
         bid.kp.publicKey
       );
-      const [bidVaultallowancePda] = getAllowancePda(
-        bidVaulthandle!,
-        organizer.publicKey
-      );
-      const highesthandle = await simulateAuctionHandles(
-        provider.connection,
-        txForSim,
-        auctionPda,
-        organizer.payer,
-        program
-      );
-
-      const [highestallowancePda] = getAllowancePda(
-        highesthandle.highestBid!,
-        organizer.publicKey
+      const [bidAllowancePda] = getAllowancePda(
+        BigInt(result.bidAmountHandle.toString()),
+        bid.kp.publicKey
       );
 
       const tx = await program.methods
-        .placeBid(bidBuffer, 0)
+        .placeBid(bidBuffer, 0, null, true)
         .preInstructions([modifyComputeUnits])
         .accounts({
+          payer: bid.kp.publicKey,
           bidder: bid.kp.publicKey,
           organizer: organizer.publicKey,
           bidderTokenAta: bid.ata,
@@ -536,47 +807,23 @@ describe("confidential-bid", () => {
           bidVault,
           bid: bidPda,
           auction: auctionPda,
+          balanceAllowance: allowancePda,
+          bidAllowance: bidAllowancePda,
           systemProgram: SYSTEM_PROGRAM_ID,
           incoTokenProgram: incoTokenProgram.programId,
           incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
         })
-        .remainingAccounts([
-          {
-            pubkey: allowancePda,
-            isSigner: false,
-            isWritable: true,
-          },
-          {
-            pubkey: bid.kp.publicKey,
-            isSigner: false,
-            isWritable: false,
-          },
-          {
-            pubkey: bidVaultallowancePda,
-            isSigner: false,
-            isWritable: true,
-          },
-          {
-            pubkey: organizer.payer.publicKey,
-            isSigner: false,
-            isWritable: false,
-          },
-          {
-            pubkey: highestallowancePda,
-            isSigner: false,
-            isWritable: true,
-          },
-          {
-            pubkey: organizer.payer.publicKey,
-            isSigner: false,
-            isWritable: false,
-          },
-        ])
         .signers([bid.kp])
         .rpc();
 
       logTransactionResult(`Bid placed by ${bid.kp.publicKey.toBase58()}`, tx);
 
+      const events = await getCpiEvents(provider.connection, tx, program);
+      const bidPlaced = events.find((e) => e.name === "bidPlaced");
+      expect(bidPlaced, "BidPlaced event").to.not.be.undefined;
+      expect(bidPlaced!.data.version).to.equal(1);
+      expect(bidPlaced!.data.bid.toBase58()).to.equal(bidPda.toBase58());
+
       await new Promise((r) => setTimeout(r, 1000));
     }
 
@@ -609,6 +856,91 @@ describe("confidential-bid", () => {
     );
   });
 
+  it("should summarize a live auction for a bidder", async () => {
+    const [bidPda] = getBidPda(
+      program.programId,
+      auctionPda,
+      bidder1.publicKey
+    );
+    const txForSim = await program.methods
+      .getAuctionSummary(null)
+      .accounts({
+        caller: bidder1.publicKey,
+        auction: auctionPda,
+        bid: bidPda,
+      })
+      .transaction();
+
+    const summary = await simulateReturnData(
+      provider.connection,
+      txForSim,
+      bidder1,
+      program,
+      "auctionSummary"
+    );
+
+    expect(summary.version).to.equal(2);
+    expect(summary.phase).to.deep.equal({ live: {} });
+    expect(summary.bidCount).to.equal(3);
+    expect(summary.hasBid).to.equal(true);
+    expect(summary.timeRemaining.toNumber()).to.be.greaterThan(0);
+
+    const bid = await program.account.bid.fetch(bidPda);
+    const grantable = summary.grantableHandles.map((h: anchor.BN) =>
+      h.toString()
+    );
+    expect(grantable).to.include(bid.bidAmount.toString());
+  });
+
+  it("should report bid status without ownership for another caller", async () => {
+    const [bidPda] = getBidPda(
+      program.programId,
+      auctionPda,
+      bidder1.publicKey
+    );
+    const txForSim = await program.methods
+      .getBidStatus(null)
+      .accounts({
+        caller: bidder2.publicKey,
+        auction: auctionPda,
+        bid: bidPda,
+      })
+      .transaction();
+
+    const status = await simulateReturnData(
+      provider.connection,
+      txForSim,
+      bidder2,
+      program,
+      "bidStatus"
+    );
+
+    expect(status.ownedByCaller).to.equal(false);
+    expect(status.winnerChecked).to.equal(false);
+    expect(status.grantableHandles).to.be.empty;
+  });
+
+  it("should fail to update auction once it has started", async () => {
+    try {
+      await program.methods
+        .updateAuction(
+          startTime,
+          endTime.add(new anchor.BN(60)),
+          new anchor.BN(1),
+          { vickrey: {} }
+        )
+        .accounts({
+          organizer: organizer.publicKey,
+          auction: auctionPda,
+        })
+        .rpc();
+
+      expect.fail("updateAuction should fail after start_time");
+    } catch (err: any) {
+      expect(err?.error?.errorCode?.code).to.equal("AuctionAlreadyStarted");
+    }
+  });
+
   it("should close auction after end time", async () => {
     // Wait for auction to end
     await new Promise((r) => setTimeout(r, 30000));
@@ -658,6 +990,44 @@ describe("confidential-bid", () => {
     }
   });
 
+  it("should reject a bid from another auction in check winner", async () => {
+    const vickreyAuctionPda = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("auction"),
+        organizer.publicKey.toBuffer(),
+        auctionId.add(new anchor.BN(1)).toBuffer("le", 8),
+      ],
+      program.programId
+    )[0];
+    const [bidPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("bid"), auctionPda.toBuffer(), bidder1.publicKey.toBuffer()],
+      program.programId
+    );
+
+    try {
+      await program.methods
+        .checkWinner(INPUT_TYPE, null, false)
+        .accounts({
+          payer: bidder1.publicKey,
+          bidder: bidder1.publicKey,
+          bid: bidPda,
+          auction: vickreyAuctionPda,
+          winnerAllowance: null,
+          incoTokenProgram: incoTokenProgram.programId,
+          systemProgram: SYSTEM_PROGRAM_ID,
+          incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
+        })
+        .signers([bidder1])
+        .rpc();
+
+      expect.fail("checkWinner should reject a bid of a different auction");
+    } catch (err: any) {
+      expect(["ConstraintSeeds", "InvalidAuction"]).to.include(
+        err?.error?.errorCode?.code
+      );
+    }
+  });
+
   it("should check winner for all bidders", async () => {
     const modifyComputeUnits = ComputeBudgetProgram.setComputeUnitLimit({
       units: 400_000,
@@ -680,45 +1050,46 @@ describe("confidential-bid", () => {
       );
 
       const txForSim = await program.methods
-        .checkWinner(INPUT_TYPE)
+        .checkWinner(INPUT_TYPE, null, false)
         .accounts({
+          payer: bid.kp.publicKey,
           bidder: bid.kp.publicKey,
           bid: bidPda,
           auction: auctionPda,
+          winnerAllowance: null,
           incoTokenProgram: incoTokenProgram.programId,
           systemProgram: SYSTEM_PROGRAM_ID,
           incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
         })
         .transaction();
 
-      const handles = await simulateCheckWinnerHandles(
+      const simulatedBid = await simulateAccount(
         provider.connection,
         txForSim,
-        bidPda,
         bid.kp,
-        program
+        program,
+        "bid",
+        bidPda
       );
 
       const [winnerAllowance] = getAllowancePda(
-        handles.isWinner!,
+        BigInt(simulatedBid.isWinnerHandle.toString()),
         bid.kp.publicKey
       );
 
       const tx = await program.methods
-        .checkWinner(0)
+        .checkWinner(0, null, true)
         .preInstructions([modifyComputeUnits])
         .accounts({
+          payer: bid.kp.publicKey,
           bidder: bid.kp.publicKey,
           bid: bidPda,
           auction: auctionPda,
+          winnerAllowance: winnerAllowance,
           incoTokenProgram: incoTokenProgram.programId,
           systemProgram: SYSTEM_PROGRAM_ID,
           incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
         })
-        .remainingAccounts([
-          { pubkey: winnerAllowance, isSigner: false, isWritable: true },
-          { pubkey: bid.kp.publicKey, isSigner: false, isWritable: false },
-        ])
         .signers([bid.kp])
         .rpc();
 
@@ -747,4 +1118,101 @@ describe("confidential-bid", () => {
       );
     }
   });
+
+  it("should let a bidder grant the organizer access to their bid", async () => {
+    const [bidPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("bid"), auctionPda.toBuffer(), bidder1.publicKey.toBuffer()],
+      program.programId
+    );
+    const bidAccount = await program.account.bid.fetch(bidPda);
+    const bidHandle = BigInt(bidAccount.bidAmount.toString());
+    const [allowanceAccount] = getAllowancePda(bidHandle, organizer.publicKey);
+
+    // Only the bidder owns bid handles
+    try {
+      await program.methods
+        .grantAccess(new anchor.BN(bidHandle.toString()), true, null)
+        .accounts({
+          owner: bidder2.publicKey,
+          auction: auctionPda,
+          bid: bidPda,
+          allowanceAccount,
+          allowedAddress: organizer.publicKey,
+          systemProgram: SYSTEM_PROGRAM_ID,
+          incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
+        })
+        .signers([bidder2])
+        .rpc();
+
+      expect.fail("grantAccess should fail for a non-owner");
+    } catch (err: any) {
+      expect(err?.error?.errorCode?.code).to.equal("Unauthorized");
+    }
+
+    const tx = await program.methods
+      .grantAccess(new anchor.BN(bidHandle.toString()), true, null)
+      .accounts({
+        owner: bidder1.publicKey,
+        auction: auctionPda,
+        bid: bidPda,
+        allowanceAccount,
+        allowedAddress: organizer.publicKey,
+        systemProgram: SYSTEM_PROGRAM_ID,
+        incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
+      })
+      .signers([bidder1])
+      .rpc();
+    logTransactionResult("Grant access transaction", tx);
+
+    await new Promise((r) => setTimeout(r, 2000));
+    const result = await decryptHandleWithSigner(
+      bidHandle.toString(),
+      organizer.payer
+    );
+    expect(result.success).to.equal(true);
+  });
+
+  it("should settle auction and pay proceeds to the organizer", async () => {
+    const modifyComputeUnits = ComputeBudgetProgram.setComputeUnitLimit({
+      units: 400_000,
+    });
+    bidVault = getIncoAta(incoTokenProgram, auctionPda, bidTokenMint.publicKey);
+    const organizerBidAta = getIncoAta(
+      incoTokenProgram,
+      organizer.publicKey,
+      bidTokenMint.publicKey
+    );
+
+    await incoTokenProgram.methods
+      .createIdempotent()
+      .accounts({
+        payer: organizer.publicKey,
+        associatedToken: organizerBidAta,
+        wallet: organizer.publicKey,
+        mint: bidTokenMint.publicKey,
+        systemProgram: SYSTEM_PROGRAM_ID,
+        incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
+      })
+      .rpc();
+
+    const tx = await program.methods
+      .settleAuction()
+      .preInstructions([modifyComputeUnits])
+      .accounts({
+        organizer: organizer.publicKey,
+        auction: auctionPda,
+        bidMint: bidTokenMint.publicKey,
+        bidVault,
+        organizerBidAta,
+        prizeMetadata: getPrizeMetadataPda(auctionMint), // Never created, no royalties owed
+        systemProgram: SYSTEM_PROGRAM_ID,
+        incoTokenProgram: incoTokenProgram.programId,
+        incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
+      })
+      .rpc();
+    logTransactionResult("Settle auction transaction", tx);
+
+    const auctionState = await program.account.auctionState.fetch(auctionPda);
+    expect(auctionState.auctionStatus).to.deep.include({ settled: {} });
+  });
 });
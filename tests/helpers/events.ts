@@ -0,0 +1,32 @@
+import { Program, utils } from "@coral-xyz/anchor";
+import { Connection } from "@solana/web3.js";
+
+// Helper: decode the events a transaction emitted through emit_cpi self-CPIs
+export async function getCpiEvents(
+  connection: Connection,
+  signature: string,
+  program: Program<any>
+): Promise<{ name: string; data: any }[]> {
+  const tx = await connection.getTransaction(signature, {
+    commitment: "confirmed",
+    maxSupportedTransactionVersion: 0,
+  });
+  if (!tx?.meta?.innerInstructions) return [];
+
+  const accountKeys = tx.transaction.message.getAccountKeys();
+  const events: { name: string; data: any }[] = [];
+  for (const inner of tx.meta.innerInstructions) {
+    for (const ix of inner.instructions) {
+      if (!accountKeys.get(ix.programIdIndex)?.equals(program.programId)) {
+        continue;
+      }
+      // Skip the 8 byte event instruction tag, the rest is a regular event
+      const data = utils.bytes.bs58.decode(ix.data).subarray(8);
+      const event = program.coder.events.decode(
+        utils.bytes.base64.encode(Buffer.from(data))
+      );
+      if (event) events.push(event);
+    }
+  }
+  return events;
+}
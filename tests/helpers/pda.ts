@@ -1,4 +1,5 @@
 import { PublicKey } from "@solana/web3.js";
+import { createHash } from "crypto";
 import { INCO_LIGHTNING_PROGRAM_ID } from "../confidential-bid";
 import { Program } from "@coral-xyz/anchor";
 
@@ -19,6 +20,54 @@ export function getAllowancePda(
   );
 }
 
+// Helper: derive the organizer profile PDA holding the auction id counter
+export function getOrganizerProfilePda(
+  programId: PublicKey,
+  organizer: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("organizer"), organizer.toBuffer()],
+    programId
+  );
+}
+
+const TOKEN_METADATA_PROGRAM_ID = new PublicKey(
+  "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
+);
+
+// Helper: derive the Metaplex metadata PDA of a prize mint, settlement
+// requires it even when the prize has no metadata
+export function getPrizeMetadataPda(mint: PublicKey): PublicKey {
+  const [metadata] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("metadata"),
+      TOKEN_METADATA_PROGRAM_ID.toBuffer(),
+      mint.toBuffer(),
+    ],
+    TOKEN_METADATA_PROGRAM_ID
+  );
+  return metadata;
+}
+
+// Helper: commitment keying a private bid, sha256(bidder || secret)
+export function getBidCommitment(bidder: PublicKey, secret: Buffer): Buffer {
+  return createHash("sha256").update(bidder.toBuffer()).update(secret).digest();
+}
+
+// Helper: derive a bid PDA, keyed by the commitment for private auctions
+export function getBidPda(
+  programId: PublicKey,
+  auction: PublicKey,
+  bidder: PublicKey,
+  secret?: Buffer
+): [PublicKey, number] {
+  const key = secret ? getBidCommitment(bidder, secret) : bidder.toBuffer();
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("bid"), auction.toBuffer(), key],
+    programId
+  );
+}
+
 export function extractHandleFromAnchor(anchorHandle: any): bigint {
   if (anchorHandle && anchorHandle._bn) {
     return BigInt(anchorHandle._bn.toString(10));
@@ -1,100 +1,79 @@
 import { Program } from "@coral-xyz/anchor";
 import { Connection, Keypair, PublicKey, Transaction } from "@solana/web3.js";
 
-// Helper: simulate transaction and extract handle from account data
-export async function simulateAndGetHandle(
-  connection: Connection,
-  tx: Transaction,
-  accountPubkey: PublicKey,
-  walletKeypair: Keypair
-): Promise<bigint | null> {
-  const { blockhash } = await connection.getLatestBlockhash();
-  tx.recentBlockhash = blockhash;
-  tx.feePayer = walletKeypair.publicKey;
-  tx.sign(walletKeypair);
-
-  const simulation = await connection.simulateTransaction(tx, undefined, [
-    accountPubkey,
-  ]);
-
-  if (simulation.value.err) {
-    console.log("Simulation error:", simulation.value.err);
-    return null;
-  }
-
-  if (simulation.value.accounts?.[0]?.data) {
-    const data = Buffer.from(simulation.value.accounts[0].data[0], "base64");
-    // Extract handle from account data (offset depends on your account struct)
-    const amountBytes = data.slice(72, 88); // Adjust offset as needed
-    let handle = BigInt(0);
-    for (let i = 15; i >= 0; i--) {
-      handle = handle * BigInt(256) + BigInt(amountBytes[i]);
-    }
-    return handle;
-  }
-  return null;
+export interface Simulation {
+  returnData: { programId: string; data: Buffer } | null;
+  // Data of the requested accounts as the simulation left them, in order
+  accounts: (Buffer | null)[];
 }
 
-export async function simulateAuctionHandles(
+// Helper: simulate transaction, keeping its return data and the data of
+// `accounts` afterwards
+export async function simulate(
   connection: Connection,
   tx: Transaction,
-  auctionPubkey: PublicKey,
   signer: Keypair,
-  program: Program<any>
-) {
+  accounts: PublicKey[] = []
+): Promise<Simulation> {
   const { blockhash } = await connection.getLatestBlockhash();
   tx.recentBlockhash = blockhash;
   tx.feePayer = signer.publicKey;
   tx.sign(signer);
 
-  const sim = await connection.simulateTransaction(tx, undefined, [
-    auctionPubkey,
-  ]);
-  if (sim.value.err || !sim.value.accounts?.[0]?.data) {
-    throw new Error(`Simulation failed: ${JSON.stringify(sim.value.err)}`);
+  const sim = await connection.simulateTransaction(tx, undefined, accounts);
+
+  if (sim.value.err) {
+    throw new Error(
+      `Simulation failed: ${JSON.stringify(
+        sim.value.err
+      )}\nLogs:\n${sim.value.logs?.join("\n")}`
+    );
   }
 
-  const raw = Buffer.from(sim.value.accounts[0].data[0], "base64");
-  const auction: any = program.coder.accounts.decode("auctionState", raw);
+  const returnData = sim.value.returnData;
   return {
-    highestBid: BigInt(auction.highestBid.toString()),
-    reserveMetHandle: BigInt(auction.reserveMetHandle.toString()),
-    highestTimestamp: BigInt(auction.highestTimestamp.toString()),
-    secondHighestBid: auction.secondHighestBid
-      ? BigInt(auction.secondHighestBid.toString())
+    returnData: returnData
+      ? {
+          programId: returnData.programId,
+          data: Buffer.from(returnData.data[0], "base64"),
+        }
       : null,
+    accounts: (sim.value.accounts ?? []).map((account) =>
+      account ? Buffer.from(account.data[0], "base64") : null
+    ),
   };
 }
-export async function simulateCheckWinnerHandles(
+
+// Helper: simulate transaction and decode the instruction's return data
+export async function simulateReturnData(
   connection: Connection,
   tx: Transaction,
-  bidPda: PublicKey,
   signer: Keypair,
-  program: Program<any>
-) {
-  const { blockhash } = await connection.getLatestBlockhash();
-  tx.recentBlockhash = blockhash;
-  tx.feePayer = signer.publicKey;
-  tx.sign(signer);
-
-  const sim = await connection.simulateTransaction(tx, undefined, [bidPda]);
-
-  if (sim.value.err) {
-    throw new Error(
-      `Simulation failed: ${JSON.stringify(
-        sim.value.err
-      )}\nLogs:\n${sim.value.logs?.join("\n")}`
-    );
+  program: Program<any>,
+  typeName: string
+): Promise<any> {
+  const { returnData } = await simulate(connection, tx, signer);
+  if (!returnData || returnData.programId !== program.programId.toBase58()) {
+    throw new Error("No return data from the program in simulation");
   }
+  return program.coder.types.decode(typeName, returnData.data);
+}
 
-  if (!sim.value.accounts?.[0]?.data) {
-    throw new Error("No account data returned in simulation");
+// Helper: simulate transaction and decode `account` as the simulation left
+// it, with `program`'s account layout `accountName`
+export async function simulateAccount(
+  connection: Connection,
+  tx: Transaction,
+  signer: Keypair,
+  program: Program<any>,
+  accountName: string,
+  account: PublicKey
+): Promise<any> {
+  const {
+    accounts: [data],
+  } = await simulate(connection, tx, signer, [account]);
+  if (!data) {
+    throw new Error(`Simulation left no data in ${account.toBase58()}`);
   }
-
-  const raw = Buffer.from(sim.value.accounts[0].data[0], "base64");
-  const bidAcc: any = program.coder.accounts.decode("bid", raw);
-
-  return {
-    isWinner: BigInt(bidAcc.isWinnerHandle.toString()),
-  };
+  return program.coder.accounts.decode(accountName, data);
 }